@@ -0,0 +1,85 @@
+#![feature(test)]
+
+extern crate temporenc;
+extern crate test;
+extern crate rand;
+
+mod common;
+
+use test::Bencher;
+use common::{NUM_ITEMS, RandomFieldSource};
+use temporenc::*;
+
+#[bench]
+fn deserialize_slice_random_date_time(b: &mut Bencher) {
+    let mut v: Vec<u8> = Vec::with_capacity(NUM_ITEMS * DateTime::max_serialized_size());
+    let mut structs = Vec::with_capacity(NUM_ITEMS);
+
+    let mut r = RandomFieldSource::new(rand::weak_rng());
+    for _ in 0..NUM_ITEMS {
+        DateTime::new(r.year(), r.month(), r.day(), r.hour(), r.minute(), r.second()).unwrap()
+            .serialize(&mut v).unwrap();
+    }
+
+    b.bytes = v.len() as u64;
+
+    b.iter(|| {
+        let mut slice = v.as_slice();
+        for _ in 0..NUM_ITEMS {
+            let (value, len) = DateTime::deserialize_from_slice(slice).unwrap();
+            structs.push(value);
+            slice = &slice[len..];
+        }
+        structs.clear();
+    })
+}
+
+#[bench]
+fn deserialize_slice_random_date_time_subsecond(b: &mut Bencher) {
+    let mut v: Vec<u8> = Vec::with_capacity(NUM_ITEMS * DateTimeSubSecond::max_serialized_size());
+    let mut structs = Vec::with_capacity(NUM_ITEMS);
+
+    let mut r = RandomFieldSource::new(rand::weak_rng());
+    for _ in 0..NUM_ITEMS {
+        DateTimeSubSecond::new(r.year(), r.month(), r.day(), r.hour(), r.minute(), r.second(),
+                               r.fractional_second()).unwrap()
+            .serialize(&mut v).unwrap();
+    }
+
+    b.bytes = v.len() as u64;
+
+    b.iter(|| {
+        let mut slice = v.as_slice();
+        for _ in 0..NUM_ITEMS {
+            let (value, len) = DateTimeSubSecond::deserialize_from_slice(slice).unwrap();
+            structs.push(value);
+            slice = &slice[len..];
+        }
+        structs.clear();
+    })
+}
+
+#[bench]
+fn deserialize_slice_random_date_time_subsecond_offset(b: &mut Bencher) {
+    let mut v: Vec<u8> = Vec::with_capacity(NUM_ITEMS * DateTimeSubSecondOffset::max_serialized_size());
+    let mut structs = Vec::with_capacity(NUM_ITEMS);
+
+    let mut r = RandomFieldSource::new(rand::weak_rng());
+    for _ in 0..NUM_ITEMS {
+        DateTimeSubSecondOffset::new(r.year(), r.month(), r.day(), r.hour(), r.minute(), r.second(),
+                                     r.fractional_second(), r.offset()).unwrap()
+            .serialize(&mut v).unwrap();
+    }
+
+    b.bytes = v.len() as u64;
+
+    b.iter(|| {
+        let mut slice = v.as_slice();
+        for _ in 0..NUM_ITEMS {
+            let (value, len) = DateTimeSubSecondOffset::deserialize_from_slice(slice).unwrap();
+            structs.push(value);
+            slice = &slice[len..];
+        }
+        structs.clear();
+    })
+}
@@ -0,0 +1,292 @@
+use super::*;
+
+/// What to do when adding months or years lands on a day past the end of the month, like
+/// January 31 + 1 month.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MonthEnd {
+    /// Use the last day of the month instead: January 31 + 1 month is February 28 (or 29).
+    Clamp,
+    /// Carry the extra days into the next month: January 31 + 1 month is March 3 (or 2).
+    Overflow,
+    /// Return `ConversionError::InvalidDate`.
+    Reject,
+}
+
+/// Adding and subtracting exact durations and calendar units, for all types.
+///
+/// Only the fields that can be affected need to be present: adding whole hours to a value without
+/// a second or minute is fine, as is adding days to a `DateOnly` or hours to a `TimeOnly`, but
+/// adding seconds to a value without a second is an error, as is adding hours to a value with a
+/// date but no year. Fields finer than the duration are left unchanged. Second 60 is treated as
+/// the start of the next minute when adding durations that aren't whole minutes.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let jan_31 = DateOnly::new(Some(2017), Some(1), Some(31)).unwrap();
+/// assert_eq!(DateOnly::new(Some(2017), Some(2), Some(28)).unwrap(),
+///            jan_31.checked_add_months(1, MonthEnd::Clamp).unwrap());
+///
+/// let time = TimeOnly::new(Some(23), Some(30), None).unwrap();
+/// assert_eq!(TimeOnly::new(Some(1), Some(30), None).unwrap(),
+///            time.checked_sub(SignedDuration::from_hours(22)).unwrap());
+/// ```
+pub trait Arithmetic: Temporal {
+    /// Add an exact duration. The fractional second keeps its precision if it can represent the
+    /// result, and is otherwise made more precise.
+    ///
+    /// Returns an error if a field that would be affected is missing, if the type can't represent
+    /// the result (like adding an hour to a `DateOnly`, or a millisecond to a `DateTime`), or if
+    /// the result is outside the years Temporenc can represent, or past midnight for types
+    /// without a date.
+    fn checked_add(&self, duration: SignedDuration) -> Result<Self, ConversionError> {
+        let fields = checked_add_nanos::<Self>(&self.fields(), duration.as_nanos())?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `checked_add()`, but subtracting.
+    fn checked_sub(&self, duration: SignedDuration) -> Result<Self, ConversionError> {
+        let nanos = duration.as_nanos().checked_neg().ok_or(ConversionError::OutOfRange)?;
+        let fields = checked_add_nanos::<Self>(&self.fields(), nanos)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Add calendar months, handling days past the end of the resulting month as described by
+    /// `month_end`. Time fields are unchanged.
+    ///
+    /// Returns an error if the year or month is missing, or if the result is outside the years
+    /// Temporenc can represent.
+    fn checked_add_months(&self, months: i32, month_end: MonthEnd)
+                          -> Result<Self, ConversionError> {
+        let fields = add_months(&self.fields(), months as i64, month_end)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `checked_add_months()`, but subtracting.
+    fn checked_sub_months(&self, months: i32, month_end: MonthEnd)
+                          -> Result<Self, ConversionError> {
+        let fields = add_months(&self.fields(), -(months as i64), month_end)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Add calendar years, which is the same as adding 12 months, so `month_end` applies to
+    /// February 29.
+    fn checked_add_years(&self, years: i32, month_end: MonthEnd)
+                         -> Result<Self, ConversionError> {
+        let fields = add_months(&self.fields(), (years as i64) * 12, month_end)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `checked_add_years()`, but subtracting.
+    fn checked_sub_years(&self, years: i32, month_end: MonthEnd)
+                         -> Result<Self, ConversionError> {
+        let fields = add_months(&self.fields(), -(years as i64) * 12, month_end)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+}
+
+impl Arithmetic for DateOnly {}
+impl Arithmetic for TimeOnly {}
+impl Arithmetic for DateTime {}
+impl Arithmetic for DateTimeOffset {}
+impl Arithmetic for DateTimeSubSecond {}
+impl Arithmetic for DateTimeSubSecondOffset {}
+
+/// The exact time between two values of a type with a date and time.
+pub trait Elapsed: Temporal {
+    /// The time from `earlier` to `self`, which is negative if `earlier` is later. Second 60
+    /// counts as the start of the next minute, and a missing fractional second as zero. Types
+    /// with an offset take it into account; those without are treated as wall clock times in the
+    /// same time zone.
+    ///
+    /// Returns an error if any of year, month, day, hour, minute and second are missing in either
+    /// value, or if the type has an offset and either one is not a `UtcOffset`.
+    fn duration_since(&self, earlier: &Self) -> Result<SignedDuration, ConversionError> {
+        duration_since(self, earlier)
+    }
+}
+
+impl Elapsed for DateTime {}
+impl Elapsed for DateTimeOffset {}
+impl Elapsed for DateTimeSubSecond {}
+impl Elapsed for DateTimeSubSecondOffset {}
+
+/// `a - b`. Second 60 is counted as the start of the next minute, and a missing fractional
+/// second as zero.
+pub fn duration_since<T: Temporal>(a: &T, b: &T) -> Result<SignedDuration, ConversionError> {
+    Ok(SignedDuration::from_nanos(instant_nanos::<T>(&a.fields())?
+        - instant_nanos::<T>(&b.fields())?))
+}
+
+/// Nanoseconds since 1970-01-01T00:00:00, at UTC if the type has an offset.
+fn instant_nanos<T: Temporal>(fields: &TemporalFields) -> Result<i128, ConversionError> {
+    let days = calendar::fields_to_days(fields)? as i128;
+    let (hour, minute, second) = match (fields.hour, fields.minute, fields.second) {
+        (Some(h), Some(m), Some(s)) => (h as i128, m as i128, s as i128),
+        _ => return Err(ConversionError::MissingField)
+    };
+    let offset = if T::has_offset() {
+        match fields.offset {
+            OffsetValue::UtcOffset(o) => o as i128,
+            _ => return Err(ConversionError::UnknownOffset)
+        }
+    } else {
+        0
+    };
+
+    Ok(days * NANOS_PER_DAY + hour * NANOS_PER_HOUR + (minute - offset) * NANOS_PER_MINUTE
+        + second * NANOS_PER_SECOND + frac_second::nanos(&fields.frac_second) as i128)
+}
+
+fn checked_add_nanos<T: Temporal>(fields: &TemporalFields, nanos: i128)
+                                  -> Result<TemporalFields, ConversionError> {
+    match add_nanos::<T>(fields, nanos)? {
+        (result, 0) => Ok(result),
+        _ => Err(ConversionError::OutOfRange)
+    }
+}
+
+/// Add `nanos` to `fields`. For types without a date, the time wraps around midnight and the
+/// number of days carried is also returned; otherwise, that is always zero.
+pub fn add_nanos<T: Temporal>(fields: &TemporalFields, nanos: i128)
+                              -> Result<(TemporalFields, i128), ConversionError> {
+    if nanos == 0 {
+        return Ok((*fields, 0));
+    }
+
+    // the largest unit the duration is a whole number of
+    let unit = [NANOS_PER_DAY, NANOS_PER_HOUR, NANOS_PER_MINUTE, NANOS_PER_SECOND, 1].iter()
+        .cloned()
+        .find(|&u| nanos % u == 0)
+        .unwrap_or(1);
+
+    if (unit < NANOS_PER_DAY && !T::has_time())
+        || (unit < NANOS_PER_SECOND && !T::has_sub_second()) {
+        return Err(ConversionError::PrecisionLoss);
+    }
+
+    // fields finer than the unit are unaffected, so they may be missing
+    let days = if T::has_date() { calendar::fields_to_days(fields)? as i128 } else { 0 };
+    let hour = affected(fields.hour, unit < NANOS_PER_DAY)?;
+    let minute = affected(fields.minute, unit < NANOS_PER_HOUR)?;
+    let second = affected(fields.second, unit < NANOS_PER_MINUTE)?;
+    let frac_nanos = if unit < NANOS_PER_SECOND {
+        if fields.frac_second == FractionalSecond::None {
+            return Err(ConversionError::MissingField);
+        }
+        frac_second::nanos(&fields.frac_second) as i128
+    } else {
+        0
+    };
+
+    let total = (days * NANOS_PER_DAY + hour * NANOS_PER_HOUR + minute * NANOS_PER_MINUTE
+        + second * NANOS_PER_SECOND + frac_nanos)
+        .checked_add(nanos)
+        .ok_or(ConversionError::OutOfRange)?;
+    let day = total.div_euclid(NANOS_PER_DAY);
+    let time = total.rem_euclid(NANOS_PER_DAY);
+
+    let mut result = *fields;
+    let mut carried = 0;
+
+    if T::has_date() {
+        let first = calendar::days_from_civil(YEAR_MIN as i64, 1, 1) as i128;
+        let last = calendar::days_from_civil(YEAR_MAX as i64, 12, 31) as i128;
+        if day < first || day > last {
+            return Err(ConversionError::OutOfRange);
+        }
+        let (year, month, day) = calendar::civil_from_days(day as i64);
+        result.year = Some(year as u16);
+        result.month = Some(month);
+        result.day = Some(day);
+    } else {
+        carried = day;
+    }
+
+    if unit < NANOS_PER_DAY {
+        result.hour = Some((time / NANOS_PER_HOUR) as u8);
+    }
+    if unit < NANOS_PER_HOUR {
+        result.minute = Some((time % NANOS_PER_HOUR / NANOS_PER_MINUTE) as u8);
+    }
+    if unit < NANOS_PER_MINUTE {
+        result.second = Some((time % NANOS_PER_MINUTE / NANOS_PER_SECOND) as u8);
+    }
+    if unit < NANOS_PER_SECOND {
+        result.frac_second = with_precision(&fields.frac_second,
+                                            (time % NANOS_PER_SECOND) as u32);
+    }
+
+    Ok((result, carried))
+}
+
+/// The value of a field for arithmetic, which must be present if it is affected.
+fn affected(field: Option<u8>, is_affected: bool) -> Result<i128, ConversionError> {
+    match field {
+        Some(value) if is_affected => Ok(value as i128),
+        None if is_affected => Err(ConversionError::MissingField),
+        _ => Ok(0)
+    }
+}
+
+/// `nanos` at the precision of `original`, or a finer one if that can't represent it exactly.
+fn with_precision(original: &FractionalSecond, nanos: u32) -> FractionalSecond {
+    match *original {
+        FractionalSecond::Milliseconds(_) if nanos % 1_000_000 == 0 => {
+            FractionalSecond::Milliseconds((nanos / 1_000_000) as u16)
+        },
+        FractionalSecond::Milliseconds(_) | FractionalSecond::Microseconds(_)
+        if nanos % 1_000 == 0 => {
+            FractionalSecond::Microseconds(nanos / 1_000)
+        },
+        _ => FractionalSecond::Nanoseconds(nanos)
+    }
+}
+
+fn add_months(fields: &TemporalFields, months: i64, month_end: MonthEnd)
+              -> Result<TemporalFields, ConversionError> {
+    let (year, month) = match (fields.year, fields.month) {
+        (Some(y), Some(m)) => (y as i64, m as i64),
+        _ => return Err(ConversionError::MissingField)
+    };
+
+    let total = year * 12 + (month - 1) + months;
+    let (mut year, mut month) = (total.div_euclid(12), (total.rem_euclid(12) + 1) as u8);
+    let mut day = fields.day;
+
+    if let Some(d) = fields.day {
+        if year >= YEAR_MIN as i64 && year <= YEAR_MAX as i64 {
+            let last = calendar::days_in_month(year, month);
+            if d > last {
+                match month_end {
+                    MonthEnd::Clamp => day = Some(last),
+                    MonthEnd::Overflow => {
+                        let (y, m, d) = calendar::civil_from_days(
+                            calendar::days_from_civil(year, month, 1) + (d as i64) - 1);
+                        year = y;
+                        month = m;
+                        day = Some(d);
+                    },
+                    MonthEnd::Reject => return Err(ConversionError::InvalidDate)
+                }
+            }
+        }
+    }
+
+    if year < YEAR_MIN as i64 || year > YEAR_MAX as i64 {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    Ok(TemporalFields {
+        year: Some(year as u16),
+        month: Some(month),
+        day,
+        ..*fields
+    })
+}
@@ -0,0 +1,186 @@
+//! Proleptic Gregorian calendar calculations used by conversions between values.
+
+use super::*;
+
+pub const MINUTES_PER_DAY: i64 = 24 * 60;
+pub const SECONDS_PER_DAY: i64 = MINUTES_PER_DAY * 60;
+
+#[inline]
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// `month` must be in [1, 12].
+#[inline]
+pub fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+/// Days since 1970-01-01. `month` must be in [1, 12] and `day` in [1, 31].
+///
+/// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let year_of_era = y - era * 400;
+    let m = month as i64;
+    let day_of_year = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + (day as i64) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of `days_from_civil()`: (year, month, day) for the given days since 1970-01-01.
+pub fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 for `date`, or `None` if any of its fields are missing or the day
+/// doesn't exist in that month.
+pub fn date_days<D: Date + ?Sized>(date: &D) -> Option<i64> {
+    let (year, month, day) = (date.year()? as i64, date.month()?, date.day()?);
+
+    if day > days_in_month(year, month) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day))
+}
+
+/// The day of the week for `days` since 1970-01-01, which was a Thursday.
+pub fn weekday(days: i64) -> Weekday {
+    match days.rem_euclid(7) {
+        0 => Weekday::Thursday,
+        1 => Weekday::Friday,
+        2 => Weekday::Saturday,
+        3 => Weekday::Sunday,
+        4 => Weekday::Monday,
+        5 => Weekday::Tuesday,
+        _ => Weekday::Wednesday,
+    }
+}
+
+/// Days since 1970-01-01 for the date in `fields`. Returns an error if any of year, month, and
+/// day are missing, or if the day doesn't exist in that month.
+pub fn fields_to_days(fields: &TemporalFields) -> Result<i64, ConversionError> {
+    match (fields.year, fields.month, fields.day) {
+        (Some(year), Some(month), Some(day)) => {
+            if day > days_in_month(year as i64, month) {
+                return Err(ConversionError::InvalidDate);
+            }
+
+            Ok(days_from_civil(year as i64, month, day))
+        },
+        _ => Err(ConversionError::MissingField)
+    }
+}
+
+/// Move the wall clock in `fields` by `minutes`, carrying into hours, days, months and years.
+/// Seconds and the fractional second are unaffected. Year, month, day, hour and minute must be
+/// present.
+pub fn add_minutes(fields: &TemporalFields, minutes: i64)
+                   -> Result<TemporalFields, ConversionError> {
+    let days = fields_to_days(fields)?;
+    let minute_of_day = match (fields.hour, fields.minute) {
+        (Some(h), Some(m)) => (h as i64) * 60 + (m as i64),
+        _ => return Err(ConversionError::MissingField)
+    };
+
+    let total = days * MINUTES_PER_DAY + minute_of_day + minutes;
+    let (year, month, day) = civil_from_days(total.div_euclid(MINUTES_PER_DAY));
+    let minute_of_day = total.rem_euclid(MINUTES_PER_DAY);
+
+    if year < YEAR_MIN as i64 || year > YEAR_MAX as i64 {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    Ok(TemporalFields {
+        year: Some(year as u16),
+        month: Some(month),
+        day: Some(day),
+        hour: Some((minute_of_day / 60) as u8),
+        minute: Some((minute_of_day % 60) as u8),
+        ..*fields
+    })
+}
+
+/// Like `add_minutes()`, but by `seconds`, so the second must also be present.
+pub fn add_seconds(fields: &TemporalFields, seconds: i64)
+                   -> Result<TemporalFields, ConversionError> {
+    let second = fields.second.ok_or(ConversionError::MissingField)? as i64 + seconds;
+    let shifted = add_minutes(fields, second.div_euclid(60))?;
+
+    Ok(TemporalFields {
+        second: Some(second.rem_euclid(60) as u8),
+        ..shifted
+    })
+}
+
+/// Express the same instant as `fields` at a different UTC offset.
+pub fn change_offset(fields: &TemporalFields, new_offset: i16)
+                     -> Result<TemporalFields, ConversionError> {
+    let old_offset = match fields.offset {
+        OffsetValue::UtcOffset(o) => o,
+        _ => return Err(ConversionError::UnknownOffset)
+    };
+
+    offset_num(OffsetValue::UtcOffset(new_offset))
+        .map_err(|_| ConversionError::InvalidOffset)?;
+
+    let shifted = add_minutes(fields, (new_offset as i64) - (old_offset as i64))?;
+
+    Ok(TemporalFields {
+        offset: OffsetValue::UtcOffset(new_offset),
+        ..shifted
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_roundtrip_all_years() {
+        let first = days_from_civil(YEAR_MIN as i64, 1, 1);
+        let last = days_from_civil(YEAR_MAX as i64, 12, 31);
+
+        for days in first..(last + 1) {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days, days_from_civil(y, m, d));
+        }
+    }
+
+    #[test]
+    fn days_known_dates() {
+        assert_eq!(0, days_from_civil(1970, 1, 1));
+        assert_eq!(-719_528, days_from_civil(0, 1, 1));
+        assert_eq!(17_181, days_from_civil(2017, 1, 15));
+        assert_eq!((2000, 2, 29), civil_from_days(11_016));
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(0));
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(2016));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2017));
+        assert_eq!(29, days_in_month(2016, 2));
+        assert_eq!(28, days_in_month(2100, 2));
+    }
+}
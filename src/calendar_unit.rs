@@ -0,0 +1,182 @@
+use super::*;
+
+/// A unit to truncate or round a value to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+    /// Buckets of this many minutes, starting at midnight. If it doesn't divide a day evenly, the
+    /// last bucket of each day is shorter. Must be in [1, 1440].
+    Minutes(u16),
+}
+
+/// What to set the fields finer than the unit to when truncating or rounding.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FinerFields {
+    /// The start of their range: month and day 1, and zero for the time fields. A fractional
+    /// second keeps its precision, and stays `None` if it was.
+    Zero,
+    /// Missing, so that a value truncated to a month means "this month".
+    Missing,
+}
+
+/// Truncating and rounding to calendar units, for the types with a date and time.
+///
+/// These work on the local date and time. The offset, for types that have one, is unchanged.
+pub trait CalendarRounding: Temporal {
+    /// Truncate to the start of `unit`, setting the finer fields as described by `finer`.
+    ///
+    /// Returns an error if `unit` is `Minutes` with a number outside [1, 1440], or if it is
+    /// `Minutes` and the minute, or the hour for numbers that don't divide an hour evenly, is
+    /// missing.
+    fn truncate_to_unit(&self, unit: Unit, finer: FinerFields) -> Result<Self, ConversionError> {
+        let fields = truncate(&self.fields(), unit, finer)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `truncate_to_unit()`, but rounding to the nearest `unit`, with halfway values
+    /// rounding up. Missing fields finer than `unit` count as zero.
+    ///
+    /// Also returns an error if any of the fields from the year down to `unit` are missing, or if
+    /// the result is outside the years Temporenc can represent.
+    fn round_to_unit(&self, unit: Unit, finer: FinerFields) -> Result<Self, ConversionError> {
+        let fields = round(&self.fields(), unit, finer)?;
+
+        Self::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+}
+
+impl CalendarRounding for DateTime {}
+impl CalendarRounding for DateTimeOffset {}
+impl CalendarRounding for DateTimeSubSecond {}
+impl CalendarRounding for DateTimeSubSecondOffset {}
+
+/// Set the fields finer than `unit` as described by `finer`, leaving the others unchanged. Only
+/// `Minutes` needs the minute (and the hour, unless it divides an hour evenly) to be present.
+pub fn truncate(fields: &TemporalFields, unit: Unit, finer: FinerFields)
+                -> Result<TemporalFields, ConversionError> {
+    let mut result = *fields;
+
+    if let Unit::Minutes(n) = unit {
+        check_in_range(n, 1, calendar::MINUTES_PER_DAY as u16, ConversionError::InvalidUnit)?;
+        let n = n as u32;
+        let minute = fields.minute.ok_or(ConversionError::MissingField)? as u32;
+
+        if 60 % n == 0 {
+            result.minute = Some((minute / n * n) as u8);
+        } else {
+            let hour = fields.hour.ok_or(ConversionError::MissingField)? as u32;
+            let bucket = (hour * 60 + minute) / n * n;
+            result.hour = Some((bucket / 60) as u8);
+            result.minute = Some((bucket % 60) as u8);
+        }
+    }
+
+    Ok(reset_finer(&result, level(unit), finer, &fields.frac_second))
+}
+
+/// Like `truncate()`, but to the nearest unit, with halfway values rounding up. Fields finer than
+/// the unit that are missing count as zero, but all of the others must be present since the
+/// rounding may carry into them.
+pub fn round(fields: &TemporalFields, unit: Unit, finer: FinerFields)
+             -> Result<TemporalFields, ConversionError> {
+    let required = [fields.year.is_some(), fields.month.is_some(), fields.day.is_some(),
+                    fields.hour.is_some(), fields.minute.is_some(), fields.second.is_some()];
+    if required[..(level(unit) + 1)].iter().any(|present| !present) {
+        return Err(ConversionError::MissingField);
+    }
+
+    let start = truncate(fields, unit, FinerFields::Zero)?;
+    let start_nanos = naive_nanos(&start)?;
+    let value_nanos = naive_nanos(fields)?;
+
+    let next_nanos = match unit {
+        Unit::Year | Unit::Month => {
+            let (year, month) = (start.year.unwrap_or(0) as i64, start.month.unwrap_or(1));
+            let (next_year, next_month) = match (unit, month) {
+                (Unit::Month, 12) | (Unit::Year, _) => (year + 1, 1),
+                _ => (year, month + 1)
+            };
+            (calendar::days_from_civil(next_year, next_month, 1) as i128) * NANOS_PER_DAY
+        },
+        Unit::Minutes(n) => {
+            let next_bucket = start_nanos + (n as i128) * NANOS_PER_MINUTE;
+            let midnight = (start_nanos.div_euclid(NANOS_PER_DAY) + 1) * NANOS_PER_DAY;
+            next_bucket.min(midnight)
+        },
+        Unit::Second => start_nanos + NANOS_PER_SECOND,
+        Unit::Minute => start_nanos + NANOS_PER_MINUTE,
+        Unit::Hour => start_nanos + NANOS_PER_MINUTE * 60,
+        Unit::Day => start_nanos + NANOS_PER_DAY,
+    };
+
+    if value_nanos - start_nanos < next_nanos - value_nanos {
+        return Ok(reset_finer(&start, level(unit), finer, &fields.frac_second));
+    }
+
+    let next = TemporalFields {
+        frac_second: fields.frac_second,
+        offset: fields.offset,
+        ..unix_time::from_unix_nanos(next_nanos)?
+    };
+
+    Ok(reset_finer(&next, level(unit), finer, &fields.frac_second))
+}
+
+/// Nanoseconds since 1970-01-01T00:00:00 for the wall clock time in `fields`, with missing time
+/// fields as zero. Second 60 is counted as the start of the next minute.
+pub fn naive_nanos(fields: &TemporalFields) -> Result<i128, ConversionError> {
+    let days = calendar::fields_to_days(fields)? as i128;
+    let field = |f: Option<u8>| f.unwrap_or(0) as i128;
+
+    Ok(days * NANOS_PER_DAY + field(fields.hour) * NANOS_PER_MINUTE * 60
+        + field(fields.minute) * NANOS_PER_MINUTE + field(fields.second) * NANOS_PER_SECOND
+        + frac_second::nanos(&fields.frac_second) as i128)
+}
+
+/// Index of the finest field kept by `unit`, from year (0) to second (5)
+fn level(unit: Unit) -> usize {
+    match unit {
+        Unit::Year => 0,
+        Unit::Month => 1,
+        Unit::Day => 2,
+        Unit::Hour => 3,
+        Unit::Minute | Unit::Minutes(_) => 4,
+        Unit::Second => 5,
+    }
+}
+
+fn reset_finer(fields: &TemporalFields, level: usize, finer: FinerFields,
+               frac_second: &FractionalSecond) -> TemporalFields {
+    let mut result = *fields;
+    let zero = finer == FinerFields::Zero;
+
+    if level < 1 {
+        result.month = if zero { Some(1) } else { None };
+    }
+    if level < 2 {
+        result.day = if zero { Some(1) } else { None };
+    }
+    if level < 3 {
+        result.hour = if zero { Some(0) } else { None };
+    }
+    if level < 4 {
+        result.minute = if zero { Some(0) } else { None };
+    }
+    if level < 5 {
+        result.second = if zero { Some(0) } else { None };
+    }
+    result.frac_second = match (finer, *frac_second) {
+        (FinerFields::Missing, _) | (_, FractionalSecond::None) => FractionalSecond::None,
+        (_, FractionalSecond::Milliseconds(_)) => FractionalSecond::Milliseconds(0),
+        (_, FractionalSecond::Microseconds(_)) => FractionalSecond::Microseconds(0),
+        (_, FractionalSecond::Nanoseconds(_)) => FractionalSecond::Nanoseconds(0),
+    };
+
+    result
+}
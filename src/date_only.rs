@@ -1,13 +1,15 @@
+use std::fmt;
 use std::io::{Read, Write};
 
 use super::*;
 
 /// Just a Date.
-#[derive(Debug, PartialEq)]
+///
+/// Values compare in the same order as their encoded bytes.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct DateOnly {
-    year: u16,
-    month: u8,
-    day: u8
+    /// The encoded value in the low `SERIALIZED_SIZE` bytes
+    bits: u32
 }
 
 impl DateOnly {
@@ -16,37 +18,74 @@ impl DateOnly {
     #[inline]
     pub fn new(year: Option<u16>, month: Option<u8>, day: Option<u8>)
                -> Result<DateOnly, CreationError> {
-        Ok(DateOnly {
-            year: year_num(year)?,
-            month: month_num(month)?,
-            day: day_num(day)?,
-        })
+        let bits = ((DATE_TAG as u32) << TAG_SHIFT)
+            | ((year_num(year)? as u32) << YEAR_SHIFT)
+            | ((month_num(month)? as u32) << MONTH_SHIFT)
+            | day_num(day)? as u32;
+
+        Ok(DateOnly { bits })
+    }
+
+    /// The date for an ISO 8601 week date.
+    ///
+    /// Returns an error if the week doesn't exist in that year, or if the date is outside the
+    /// years Temporenc can represent.
+    pub fn from_iso_week_date(week_date: &IsoWeekDate) -> Result<DateOnly, ConversionError> {
+        let fields = week_date::week_date_to_days(week_date).and_then(week_date::date_fields)?;
+
+        DateOnly::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The ISO 8601 week date for this date.
+    ///
+    /// Returns an error if any of year, month, and day are missing, if the date doesn't exist, or
+    /// if the week-numbering year is outside the years Temporenc can represent, as it is for the
+    /// first two days of year 0.
+    pub fn to_iso_week_date(&self) -> Result<IsoWeekDate, ConversionError> {
+        week_date::days_to_week_date(calendar::fields_to_days(&self.fields())?)
+    }
+
+    /// The date for an ISO 8601 ordinal date. Returns an error if the day doesn't exist in that
+    /// year.
+    pub fn from_ordinal_date(ordinal_date: &OrdinalDate) -> Result<DateOnly, ConversionError> {
+        let fields =
+            week_date::ordinal_date_to_days(ordinal_date).and_then(week_date::date_fields)?;
+
+        DateOnly::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The ISO 8601 ordinal date for this date.
+    ///
+    /// Returns an error if any of year, month, and day are missing, or if the date doesn't exist.
+    pub fn to_ordinal_date(&self) -> Result<OrdinalDate, ConversionError> {
+        week_date::days_to_ordinal_date(calendar::fields_to_days(&self.fields())?)
+    }
+
+    /// The value packed into the low 21 bits of a `u64`, as described in
+    /// `DateTime::to_packed_u64()`.
+    pub fn to_packed_u64(&self) -> u64 {
+        key::to_packed(self) as u64
+    }
+
+    /// The inverse of `to_packed_u64()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u64(packed: u64) -> Result<DateOnly, DeserializationError> {
+        key::from_packed(packed as u128)
     }
 }
 
 impl Date for DateOnly {
     fn year(&self) -> Option<u16> {
-        if self.year == YEAR_RAW_NONE {
-            None
-        } else {
-            Some(self.year)
-        }
+        year_value(self.raw_year())
     }
 
     fn month(&self) -> Option<u8> {
-        if self.month == MONTH_RAW_NONE {
-            None
-        } else {
-            Some(self.month + 1)
-        }
+        month_value(self.raw_month())
     }
 
     fn day(&self) -> Option<u8> {
-        if self.day == DAY_RAW_NONE {
-            None
-        } else {
-            Some(self.day + 1)
-        }
+        day_value(self.raw_day())
     }
 }
 
@@ -60,49 +99,130 @@ impl Serializable for DateOnly {
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
-        let b0 = DATE_TAG | ((self.year >> 7) as u8);
-        let b1 = ((self.year << 1) as u8) | (self.month >> 3);
-        let b2 = (self.month << 5) | self.day;
-
-        write_array_map_err(&[b0, b1, b2], writer)
+        write_array_map_err(&self.bits.to_be_bytes()[(4 - SERIALIZED_SIZE)..], writer)
             .map_err(|_| SerializationError::IoError)
     }
 }
 
 impl Deserializable for DateOnly {
     fn deserialize<R: Read>(reader: &mut R) -> Result<DateOnly, DeserializationError> {
-        let mut buf = [0; SERIALIZED_SIZE];
-        read_exact(reader, &mut buf)?;
+        let mut buf = [0; 4];
+        read_exact(reader, &mut buf[(4 - SERIALIZED_SIZE)..])?;
 
-        let byte0 = buf[0];
-
-        if byte0 & 0b1110_0000 != DATE_TAG {
+        if buf[1] & 0b1110_0000 != DATE_TAG {
             return Err(DeserializationError::IncorrectTypeTag);
         }
 
         // 3-bit tag, 12-bit year, 4-bit month, 5-bit day
         // TTTY YYYY YYYY YYYM MMMD DDDD
+        let date = DateOnly { bits: u32::from_be_bytes(buf) };
+
+        date.check_fields()?;
+
+        Ok(date)
+    }
+
+    fn deserialize_from_slice(bytes: &[u8]) -> Result<(DateOnly, usize), DeserializationError> {
+        if bytes.len() < 4 {
+            return deserialize_prefix(bytes);
+        }
+
+        // drop whatever follows the value
+        let date = DateOnly { bits: load_be_u32(bytes) >> (8 * (4 - SERIALIZED_SIZE)) };
+
+        if (date.bits >> TAG_SHIFT) as u8 & 0b1110_0000 != DATE_TAG {
+            return Err(DeserializationError::IncorrectTypeTag);
+        }
+
+        date.check_fields()?;
+
+        Ok((date, SERIALIZED_SIZE))
+    }
+}
 
-        let mut raw_year = ((byte0 & 0x1F) as u16) << 7;
-        let byte1 = buf[1];
-        raw_year |= (byte1 as u16) >> 1;
+impl Temporal for DateOnly {
+    fn type_tag() -> TypeTag {
+        TypeTag::DateOnly
+    }
 
-        let mut raw_month = (byte1 & 0x01) << 3;
-        let byte2 = buf[2];
-        raw_month |= (byte2 & 0xE0) >> 5;
+    fn min_serialized_size() -> usize {
+        SERIALIZED_SIZE
+    }
 
-        let raw_day = byte2 & 0x1F;
+    fn has_date() -> bool {
+        true
+    }
 
-        // no need to check year as every possible number is a valid year
-        check_deser_in_range_or_none(raw_month, MONTH_RAW_MIN, MONTH_RAW_MAX, MONTH_RAW_NONE)?;
-        // no need to check day as every possible number is a valid day
+    fn has_time() -> bool {
+        false
+    }
 
-        Ok(DateOnly {
-            year: raw_year,
-            month: raw_month,
-            day: raw_day
-        })
+    fn has_sub_second() -> bool {
+        false
+    }
+
+    fn has_offset() -> bool {
+        false
+    }
+
+    fn fields(&self) -> TemporalFields {
+        TemporalFields {
+            year: self.year(),
+            month: self.month(),
+            day: self.day(),
+            ..TemporalFields::default()
+        }
+    }
+
+    fn from_fields(fields: TemporalFields) -> Result<DateOnly, CreationError> {
+        check_components::<DateOnly>(&fields)?;
+
+        DateOnly::new(fields.year, fields.month, fields.day)
+    }
+}
+
+impl DateOnly {
+    /// Validate every field of a deserialized value, branching only once at the end
+    #[inline]
+    fn check_fields(&self) -> Result<(), DeserializationError> {
+        // every possible year or day is valid
+        let valid = deser_in_range_or_none(self.raw_month(), MONTH_RAW_MIN, MONTH_RAW_MAX,
+                                           MONTH_RAW_NONE);
+
+        check_deser_fields(valid)
+    }
+
+    // the raw none values are all ones, so they are also the masks
+
+    #[inline]
+    fn raw_year(&self) -> u16 {
+        (self.bits >> YEAR_SHIFT) as u16 & YEAR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_month(&self) -> u8 {
+        (self.bits >> MONTH_SHIFT) as u8 & MONTH_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_day(&self) -> u8 {
+        self.bits as u8 & DAY_RAW_NONE
+    }
+}
+
+impl fmt::Debug for DateOnly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DateOnly")
+            .field("year", &self.year())
+            .field("month", &self.month())
+            .field("day", &self.day())
+            .finish()
     }
 }
 
 const SERIALIZED_SIZE: usize = 3;
+
+// bit positions in the encoded value
+const TAG_SHIFT: u32 = 16;
+const YEAR_SHIFT: u32 = 9;
+const MONTH_SHIFT: u32 = 5;
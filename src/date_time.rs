@@ -1,16 +1,16 @@
+use std::convert::TryFrom;
+use std::fmt;
 use std::io::{Read, Write};
 
 use super::*;
 
 /// A Date and Time.
-#[derive(Debug, PartialEq)]
+///
+/// Values compare in the same order as their encoded bytes.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct DateTime {
-    year: u16,
-    month: u8,
-    day: u8,
-    hour: u8,
-    minute: u8,
-    second: u8,
+    /// The encoded value in the low `SERIALIZED_SIZE` bytes
+    bits: u64
 }
 
 impl DateTime {
@@ -19,66 +19,78 @@ impl DateTime {
     #[inline]
     pub fn new(year: Option<u16>, month: Option<u8>, day: Option<u8>, hour: Option<u8>,
                minute: Option<u8>, second: Option<u8>) -> Result<DateTime, CreationError> {
-        Ok(DateTime {
-            year: year_num(year)?,
-            month: month_num(month)?,
-            day: day_num(day)?,
-            hour: hour_num(hour)?,
-            minute: minute_num(minute)?,
-            second: second_num(second)?,
-        })
+        let bits = ((DATE_TIME_TAG as u64) << TAG_SHIFT)
+            | ((year_num(year)? as u64) << YEAR_SHIFT)
+            | ((month_num(month)? as u64) << MONTH_SHIFT)
+            | ((day_num(day)? as u64) << DAY_SHIFT)
+            | ((hour_num(hour)? as u64) << HOUR_SHIFT)
+            | ((minute_num(minute)? as u64) << MINUTE_SHIFT)
+            | second_num(second)? as u64;
+
+        Ok(DateTime { bits })
+    }
+
+    /// Every complete `DateTime` from `start` (inclusive) to `end` (exclusive) that this one
+    /// matches with its missing fields as wildcards, as described by `Recurrences`. Values are
+    /// produced as the iterator is advanced, so a wide range with many matches is cheap.
+    ///
+    /// Returns an error if `start` or `end` is not complete.
+    pub fn recurrences(&self, start: &DateTime, end: &DateTime)
+                       -> Result<Recurrences, ConversionError> {
+        Recurrences::new(self, start, end)
+    }
+
+    /// The value packed into the low 38 bits of a `u64`, for integer columns and comparisons.
+    /// Packed values compare in chronological order, with missing fields after every value as in
+    /// `OrderedKey` with `MissingFields::Last`, and every value has exactly one packed form.
+    ///
+    /// ```
+    /// use temporenc::*;
+    ///
+    /// let earlier = DateTime::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30))
+    ///     .unwrap();
+    /// let later = DateTime::new(Some(2017), Some(2), Some(1), Some(0), Some(0), Some(0)).unwrap();
+    ///
+    /// assert!(earlier.to_packed_u64() < later.to_packed_u64());
+    /// assert_eq!(later, DateTime::from_packed_u64(later.to_packed_u64()).unwrap());
+    /// ```
+    pub fn to_packed_u64(&self) -> u64 {
+        key::to_packed(self) as u64
+    }
+
+    /// The inverse of `to_packed_u64()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u64(packed: u64) -> Result<DateTime, DeserializationError> {
+        key::from_packed(packed as u128)
     }
 }
 
 impl Date for DateTime {
     fn year(&self) -> Option<u16> {
-        if self.year == YEAR_RAW_NONE {
-            None
-        } else {
-            Some(self.year)
-        }
+        year_value(self.raw_year())
     }
 
     fn month(&self) -> Option<u8> {
-        if self.month == MONTH_RAW_NONE {
-            None
-        } else {
-            Some(self.month + 1)
-        }
+        month_value(self.raw_month())
     }
 
     fn day(&self) -> Option<u8> {
-        if self.day == DAY_RAW_NONE {
-            None
-        } else {
-            Some(self.day + 1)
-        }
+        day_value(self.raw_day())
     }
 }
 
 impl Time for DateTime {
     fn hour(&self) -> Option<u8> {
-        if self.hour == HOUR_RAW_NONE {
-            None
-        } else {
-            Some(self.hour)
-        }
+        hour_value(self.raw_hour())
     }
 
     fn minute(&self) -> Option<u8> {
-        if self.minute == MINUTE_RAW_NONE {
-            None
-        } else {
-            Some(self.minute)
-        }
+        minute_value(self.raw_minute())
     }
 
     fn second(&self) -> Option<u8> {
-        if self.second == SECOND_RAW_NONE {
-            None
-        } else {
-            Some(self.second)
-        }
+        second_value(self.raw_second())
     }
 }
 
@@ -92,64 +104,224 @@ impl Serializable for DateTime {
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
-        let b0 = DATE_TIME_TAG | (self.year >> 6) as u8;
-        let b1 = ((self.year << 2) as u8) | (self.month >> 2);
-        let b2 = (self.month << 6) | (self.day << 1) | (self.hour >> 4);
-        let b3 = (self.hour << 4) | (self.minute >> 2);
-        let b4 = (self.minute << 6) | self.second;
-
-        write_array_map_err(&[b0, b1, b2, b3, b4], writer)
+        write_array_map_err(&self.bits.to_be_bytes()[(8 - SERIALIZED_SIZE)..], writer)
             .map_err(|_| SerializationError::IoError)
     }
 }
 
 impl Deserializable for DateTime {
     fn deserialize<R: Read>(reader: &mut R) -> Result<DateTime, DeserializationError> {
-        let mut buf = [0; SERIALIZED_SIZE];
-        read_exact(reader, &mut buf)?;
-
-        let byte0 = buf[0];
+        let mut buf = [0; 8];
+        read_exact(reader, &mut buf[(8 - SERIALIZED_SIZE)..])?;
 
-        if byte0 & 0b1100_0000 != DATE_TIME_TAG {
+        if buf[8 - SERIALIZED_SIZE] & 0b1100_0000 != DATE_TIME_TAG {
             return Err(DeserializationError::IncorrectTypeTag);
         }
 
         // 2-bit tag, 12-bit year, 4-bit month, 5-bit day, 5-bit hour, 6-bit minute, 6-bit second
         // TTYY YYYY | YYYY YYMM | MMDD DDDH | HHHH MMMM | MMSS SSSS
+        let value = DateTime { bits: u64::from_be_bytes(buf) };
+
+        value.check_fields()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_from_slice(bytes: &[u8]) -> Result<(DateTime, usize), DeserializationError> {
+        if bytes.len() < 8 {
+            return deserialize_prefix(bytes);
+        }
+
+        // drop whatever follows the value
+        let value = DateTime { bits: load_be_u64(bytes) >> (8 * (8 - SERIALIZED_SIZE)) };
+
+        if (value.bits >> TAG_SHIFT) as u8 & 0b1100_0000 != DATE_TIME_TAG {
+            return Err(DeserializationError::IncorrectTypeTag);
+        }
+
+        value.check_fields()?;
+
+        Ok((value, SERIALIZED_SIZE))
+    }
+}
+
+impl Temporal for DateTime {
+    fn type_tag() -> TypeTag {
+        TypeTag::DateTime
+    }
+
+    fn min_serialized_size() -> usize {
+        SERIALIZED_SIZE
+    }
+
+    fn has_date() -> bool {
+        true
+    }
+
+    fn has_time() -> bool {
+        true
+    }
+
+    fn has_sub_second() -> bool {
+        false
+    }
+
+    fn has_offset() -> bool {
+        false
+    }
+
+    fn fields(&self) -> TemporalFields {
+        TemporalFields {
+            year: self.year(),
+            month: self.month(),
+            day: self.day(),
+            hour: self.hour(),
+            minute: self.minute(),
+            second: self.second(),
+            ..TemporalFields::default()
+        }
+    }
+
+    fn from_fields(fields: TemporalFields) -> Result<DateTime, CreationError> {
+        check_components::<DateTime>(&fields)?;
+
+        DateTime::new(fields.year, fields.month, fields.day, fields.hour, fields.minute,
+                      fields.second)
+    }
+}
 
-        let byte1 = buf[1];
-        let mut raw_year = ((byte0 & 0x3F) as u16) << 6;
-        raw_year |= (byte1 >> 2) as u16;
+impl TruncateFrom<DateTimeOffset> for DateTime {
+    fn truncate_from(value: &DateTimeOffset) -> DateTime {
+        // fields were already validated when creating the source
+        DateTime::new(value.year(), value.month(), value.day(), value.hour(), value.minute(),
+                      value.second()).unwrap()
+    }
+}
+
+impl TruncateFrom<DateTimeSubSecond> for DateTime {
+    fn truncate_from(value: &DateTimeSubSecond) -> DateTime {
+        DateTime::new(value.year(), value.month(), value.day(), value.hour(), value.minute(),
+                      value.second()).unwrap()
+    }
+}
+
+impl TruncateFrom<DateTimeSubSecondOffset> for DateTime {
+    fn truncate_from(value: &DateTimeSubSecondOffset) -> DateTime {
+        DateTime::new(value.year(), value.month(), value.day(), value.hour(), value.minute(),
+                      value.second()).unwrap()
+    }
+}
+
+impl TryFrom<DateTimeOffset> for DateTime {
+    type Error = NarrowingError;
+
+    /// Returns an error if the offset is anything other than `OffsetValue::None`.
+    fn try_from(value: DateTimeOffset) -> Result<DateTime, NarrowingError> {
+        if value.offset() != OffsetValue::None {
+            return Err(NarrowingError::Offset);
+        }
+
+        Ok(DateTime::truncate_from(&value))
+    }
+}
+
+impl TryFrom<DateTimeSubSecond> for DateTime {
+    type Error = NarrowingError;
+
+    /// Returns an error if the fractional second is anything other than `FractionalSecond::None`.
+    fn try_from(value: DateTimeSubSecond) -> Result<DateTime, NarrowingError> {
+        if value.fractional_second() != FractionalSecond::None {
+            return Err(NarrowingError::FractionalSecond);
+        }
+
+        Ok(DateTime::truncate_from(&value))
+    }
+}
+
+impl TryFrom<DateTimeSubSecondOffset> for DateTime {
+    type Error = NarrowingError;
+
+    /// Returns an error if the fractional second is anything other than `FractionalSecond::None`
+    /// or the offset is anything other than `OffsetValue::None`.
+    fn try_from(value: DateTimeSubSecondOffset) -> Result<DateTime, NarrowingError> {
+        if value.fractional_second() != FractionalSecond::None {
+            return Err(NarrowingError::FractionalSecond);
+        }
+
+        if value.offset() != OffsetValue::None {
+            return Err(NarrowingError::Offset);
+        }
+
+        Ok(DateTime::truncate_from(&value))
+    }
+}
+
+impl DateTime {
+    /// Validate every field of a deserialized value, branching only once at the end
+    #[inline]
+    fn check_fields(&self) -> Result<(), DeserializationError> {
+        // every possible year or day is valid
+        let valid = deser_in_range_or_none(self.raw_month(), MONTH_RAW_MIN, MONTH_RAW_MAX,
+                                           MONTH_RAW_NONE)
+            & deser_in_range_or_none(self.raw_hour(), HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)
+            & deser_in_range_or_none(self.raw_minute(), MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)
+            & deser_in_range_or_none(self.raw_second(), SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE);
+
+        check_deser_fields(valid)
+    }
 
-        let byte2 = buf[2];
-        let raw_month = ((byte1 & 0x03) << 2) | (byte2 >> 6);
+    // the raw none values are all ones, so they are also the masks
+
+    #[inline]
+    fn raw_year(&self) -> u16 {
+        (self.bits >> YEAR_SHIFT) as u16 & YEAR_RAW_NONE
+    }
 
-        let raw_day = (byte2 & 0x3E) >> 1;
+    #[inline]
+    fn raw_month(&self) -> u8 {
+        (self.bits >> MONTH_SHIFT) as u8 & MONTH_RAW_NONE
+    }
 
-        let byte3 = buf[3];
-        let raw_hour = ((byte2 & 0x01) << 4) | (byte3 >> 4);
+    #[inline]
+    fn raw_day(&self) -> u8 {
+        (self.bits >> DAY_SHIFT) as u8 & DAY_RAW_NONE
+    }
 
-        let byte4 = buf[4];
-        let raw_minute = ((byte3 & 0x0F) << 2) | (byte4 >> 6);
+    #[inline]
+    fn raw_hour(&self) -> u8 {
+        (self.bits >> HOUR_SHIFT) as u8 & HOUR_RAW_NONE
+    }
 
-        let raw_second = byte4 & 0x3F;
+    #[inline]
+    fn raw_minute(&self) -> u8 {
+        (self.bits >> MINUTE_SHIFT) as u8 & MINUTE_RAW_NONE
+    }
 
-        // no need to check year as every possible number is a valid year
-        check_deser_in_range_or_none(raw_month, MONTH_RAW_MIN, MONTH_RAW_MAX, MONTH_RAW_NONE)?;
-        // no need to check day as every possible number is a valid day
-        check_deser_in_range_or_none(raw_hour, HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_minute, MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_second, SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)?;
+    #[inline]
+    fn raw_second(&self) -> u8 {
+        self.bits as u8 & SECOND_RAW_NONE
+    }
+}
 
-        Ok(DateTime {
-            year: raw_year,
-            month: raw_month,
-            day: raw_day,
-            hour: raw_hour,
-            minute: raw_minute,
-            second: raw_second,
-        })
+impl fmt::Debug for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DateTime")
+            .field("year", &self.year())
+            .field("month", &self.month())
+            .field("day", &self.day())
+            .field("hour", &self.hour())
+            .field("minute", &self.minute())
+            .field("second", &self.second())
+            .finish()
     }
 }
 
 const SERIALIZED_SIZE: usize = 5;
+
+// bit positions in the encoded value
+const TAG_SHIFT: u32 = 32;
+const YEAR_SHIFT: u32 = 26;
+const MONTH_SHIFT: u32 = 22;
+const DAY_SHIFT: u32 = 17;
+const HOUR_SHIFT: u32 = 12;
+const MINUTE_SHIFT: u32 = 6;
@@ -1,17 +1,19 @@
+use std::convert::TryFrom;
+use std::fmt;
 use std::io::{Read, Write};
 
 use super::*;
 
 /// A Date and Time with UTC Offset.
-#[derive(Debug, PartialEq)]
+///
+/// The encoding orders values by local date and time and then by offset, which isn't the order
+/// of the instants they represent (10:00+02:00 is earlier than 09:00+00:00), so only equality is
+/// provided. Convert to a common offset with `to_utc()` to compare instants, or see `OrderedKey`
+/// for the order of the encoding.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DateTimeOffset {
-    year: u16,
-    month: u8,
-    day: u8,
-    hour: u8,
-    minute: u8,
-    second: u8,
-    offset: u8
+    /// The encoded value in the low `SERIALIZED_SIZE` bytes
+    bits: u64
 }
 
 impl DateTimeOffset {
@@ -20,77 +22,142 @@ impl DateTimeOffset {
     #[inline]
     pub fn new(year: Option<u16>, month: Option<u8>, day: Option<u8>, hour: Option<u8>,
                minute: Option<u8>, second: Option<u8>, offset: OffsetValue) -> Result<DateTimeOffset, CreationError> {
-        Ok(DateTimeOffset {
-            year: year_num(year)?,
-            month: month_num(month)?,
-            day: day_num(day)?,
-            hour: hour_num(hour)?,
-            minute: minute_num(minute)?,
-            second: second_num(second)?,
-            offset: offset_num(offset)?
-        })
+        let bits = ((DATE_TIME_OFFSET_TAG as u64) << TAG_SHIFT)
+            | ((year_num(year)? as u64) << YEAR_SHIFT)
+            | ((month_num(month)? as u64) << MONTH_SHIFT)
+            | ((day_num(day)? as u64) << DAY_SHIFT)
+            | ((hour_num(hour)? as u64) << HOUR_SHIFT)
+            | ((minute_num(minute)? as u64) << MINUTE_SHIFT)
+            | ((second_num(second)? as u64) << SECOND_SHIFT)
+            | offset_num(offset)? as u64;
+
+        Ok(DateTimeOffset { bits })
+    }
+
+    /// Combine a local date and time with an offset in seconds east of UTC, which may not be
+    /// representable in Temporenc (like local mean time), storing the offset as described by
+    /// `rounding`. Also returns how much information was lost, if any.
+    ///
+    /// Returns an error if the offset needs adjusting and any of year, month, day, hour, and
+    /// minute are missing, or if the result is out of range.
+    pub fn from_local(local: &DateTime, offset_seconds: i32, rounding: OffsetRounding)
+                      -> Result<(DateTimeOffset, OffsetAdjustment), ConversionError> {
+        let (fields, adjustment) =
+            offset_rounding::round_offset(&local.fields(), 0, offset_seconds as i64, rounding)?;
+
+        DateTimeOffset::from_fields(fields)
+            .map(|value| (value, adjustment))
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The same instant at `UtcOffset(0)`, carrying into the date as needed.
+    ///
+    /// Returns an error if the offset is not a `UtcOffset`, or if any of year, month, day, hour,
+    /// and minute are missing, since the result would then be undefined.
+    pub fn to_utc(&self) -> Result<DateTimeOffset, ConversionError> {
+        self.to_offset(0)
+    }
+
+    /// Like `to_utc()`, but without the offset.
+    pub fn to_naive_utc(&self) -> Result<DateTime, ConversionError> {
+        self.to_utc().map(|dto| dto.truncate_to())
+    }
+
+    /// The same instant at `UtcOffset(offset_minutes)`. `offset_minutes` must be a valid
+    /// `OffsetValue::UtcOffset`.
+    ///
+    /// Returns an error in the same cases as `to_utc()`.
+    pub fn to_offset(&self, offset_minutes: i16) -> Result<DateTimeOffset, ConversionError> {
+        let fields = calendar::change_offset(&self.fields(), offset_minutes)?;
+
+        DateTimeOffset::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Returns an error if the second is 60 anywhere other than at a real leap second, after
+    /// converting to UTC. Values without second 60 are always accepted.
+    ///
+    /// Also returns an error if the second is 60 and the offset is not a `UtcOffset`, or if any
+    /// of year, month, day, hour, and minute are missing, since it can't be checked then.
+    pub fn check_leap_second(&self) -> Result<(), ConversionError> {
+        leap_seconds::check_leap_second(&self.fields())
+    }
+
+    /// Seconds since 1970-01-01T00:00:00Z, not counting leap seconds. Second 60 is treated as
+    /// described by `mode`.
+    ///
+    /// Returns an error if the offset is not a `UtcOffset`, or if any of the fields are missing.
+    pub fn to_unix_seconds(&self, mode: LeapSecondMode) -> Result<i64, ConversionError> {
+        unix_time::to_unix_nanos(&self.fields(), mode)
+            .map(|nanos| nanos.div_euclid(NANOS_PER_SECOND) as i64)
+    }
+
+    /// The UTC value `unix_seconds` seconds after 1970-01-01T00:00:00Z, not counting leap
+    /// seconds.
+    ///
+    /// Returns an error if the result is outside the years Temporenc can represent.
+    pub fn from_unix_seconds(unix_seconds: i64) -> Result<DateTimeOffset, ConversionError> {
+        let fields = unix_time::from_unix_nanos((unix_seconds as i128)
+            * NANOS_PER_SECOND)?;
+
+        DateTimeOffset::from_fields(TemporalFields {
+            frac_second: FractionalSecond::None,
+            ..fields
+        }).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Convert to a type with fewer components, discarding the offset if the target type has none.
+    /// Use `TryFrom` instead if discarding it would be a mistake.
+    pub fn truncate_to<T: TruncateFrom<DateTimeOffset>>(&self) -> T {
+        T::truncate_from(self)
+    }
+
+    /// The value packed into the low 45 bits of a `u64`, as described in
+    /// `DateTime::to_packed_u64()`. The offset comes after the other fields, so values are
+    /// ordered by local time, and values with different offsets should be converted to UTC first
+    /// to order them by instant.
+    pub fn to_packed_u64(&self) -> u64 {
+        key::to_packed(self) as u64
+    }
+
+    /// The inverse of `to_packed_u64()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u64(packed: u64) -> Result<DateTimeOffset, DeserializationError> {
+        key::from_packed(packed as u128)
     }
 }
 
 impl Date for DateTimeOffset {
     fn year(&self) -> Option<u16> {
-        if self.year == YEAR_RAW_NONE {
-            None
-        } else {
-            Some(self.year)
-        }
+        year_value(self.raw_year())
     }
 
     fn month(&self) -> Option<u8> {
-        if self.month == MONTH_RAW_NONE {
-            None
-        } else {
-            Some(self.month + 1)
-        }
+        month_value(self.raw_month())
     }
 
     fn day(&self) -> Option<u8> {
-        if self.day == DAY_RAW_NONE {
-            None
-        } else {
-            Some(self.day + 1)
-        }
+        day_value(self.raw_day())
     }
 }
 
 impl Time for DateTimeOffset {
     fn hour(&self) -> Option<u8> {
-        if self.hour == HOUR_RAW_NONE {
-            None
-        } else {
-            Some(self.hour)
-        }
+        hour_value(self.raw_hour())
     }
 
     fn minute(&self) -> Option<u8> {
-        if self.minute == MINUTE_RAW_NONE {
-            None
-        } else {
-            Some(self.minute)
-        }
+        minute_value(self.raw_minute())
     }
 
     fn second(&self) -> Option<u8> {
-        if self.second == SECOND_RAW_NONE {
-            None
-        } else {
-            Some(self.second)
-        }
+        second_value(self.raw_second())
     }
 }
 
 impl Offset for DateTimeOffset {
     fn offset(&self) -> OffsetValue {
-        match self.offset {
-            127 => OffsetValue::None,
-            126 => OffsetValue::SpecifiedElsewhere,
-            x => OffsetValue::UtcOffset(((x as i16) - 64) * 15)
-        }
+        offset_value(self.raw_offset())
     }
 }
 
@@ -104,71 +171,189 @@ impl Serializable for DateTimeOffset {
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
-        let b0 = DATE_TIME_OFFSET_TAG | (self.year >> 7) as u8;
-        let b1 = ((self.year << 1) as u8) | (self.month >> 3);
-        let b2 = (self.month << 5) | self.day;
-        let b3 = (self.hour << 3) | (self.minute >> 3);
-        let b4 = (self.minute << 5) | (self.second >> 1);
-        let b5 = (self.second << 7) | self.offset;
-
-        write_array_map_err(&[b0, b1, b2, b3, b4, b5], writer)
+        write_array_map_err(&self.bits.to_be_bytes()[(8 - SERIALIZED_SIZE)..], writer)
             .map_err(|_| SerializationError::IoError)
     }
 }
 
 impl Deserializable for DateTimeOffset {
     fn deserialize<R: Read>(reader: &mut R) -> Result<DateTimeOffset, DeserializationError> {
-        let mut buf = [0; SERIALIZED_SIZE];
-        read_exact(reader, &mut buf)?;
-
-        let byte0 = buf[0];
+        let mut buf = [0; 8];
+        read_exact(reader, &mut buf[(8 - SERIALIZED_SIZE)..])?;
 
-        if byte0 & 0b1110_0000 != DATE_TIME_OFFSET_TAG {
+        if buf[8 - SERIALIZED_SIZE] & 0b1110_0000 != DATE_TIME_OFFSET_TAG {
             return Err(DeserializationError::IncorrectTypeTag);
         }
 
         // 3-bit tag, 12-bit year, 4-bit month, 5-bit day, 5-bit hour, 6-bit minute, 6-bit second,
         // 7-bit offset
         // TTTY YYYY | YYYY YYYM | MMMD DDDD | HHHH HMMM | MMMS SSSS | SOOO OOOO
+        let value = DateTimeOffset { bits: u64::from_be_bytes(buf) };
+
+        value.check_fields()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_from_slice(bytes: &[u8])
+                              -> Result<(DateTimeOffset, usize), DeserializationError> {
+        if bytes.len() < 8 {
+            return deserialize_prefix(bytes);
+        }
+
+        // drop whatever follows the value
+        let value = DateTimeOffset { bits: load_be_u64(bytes) >> (8 * (8 - SERIALIZED_SIZE)) };
 
-        let mut raw_year = ((byte0 & 0x1F) as u16) << 7;
-        let byte1 = buf[1];
-        raw_year |= (byte1 as u16) >> 1;
+        if (value.bits >> TAG_SHIFT) as u8 & 0b1110_0000 != DATE_TIME_OFFSET_TAG {
+            return Err(DeserializationError::IncorrectTypeTag);
+        }
 
-        let byte2 = buf[2];
-        let raw_month = ((byte1 & 0x01) << 3) | ((byte2 & 0xE0) >> 5);
+        value.check_fields()?;
 
-        let raw_day = byte2 & 0x1F;
+        Ok((value, SERIALIZED_SIZE))
+    }
+}
 
-        let byte3 = buf[3];
-        let raw_hour = byte3 >> 3;
+impl Temporal for DateTimeOffset {
+    fn type_tag() -> TypeTag {
+        TypeTag::DateTimeOffset
+    }
 
-        let byte4 = buf[4];
-        let raw_minute = ((byte3 & 0x07) << 3) | (byte4 >> 5);
+    fn min_serialized_size() -> usize {
+        SERIALIZED_SIZE
+    }
 
-        let byte5 = buf[5];
-        let raw_second = (byte4 & 0x1F) << 1 | (byte5 >> 7);
+    fn has_date() -> bool {
+        true
+    }
 
-        let raw_offset = byte5 & 0x7F;
+    fn has_time() -> bool {
+        true
+    }
 
-        // no need to check year as every possible number is a valid year
-        check_deser_in_range_or_none(raw_month, MONTH_RAW_MIN, MONTH_RAW_MAX, MONTH_RAW_NONE)?;
-        // no need to check day as every possible number is a valid day
-        check_deser_in_range_or_none(raw_hour, HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_minute, MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_second, SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)?;
-        // no need to check offset as every possible number is a valid offset
+    fn has_sub_second() -> bool {
+        false
+    }
 
-        Ok(DateTimeOffset {
-            year: raw_year,
-            month: raw_month,
-            day: raw_day,
-            hour: raw_hour,
-            minute: raw_minute,
-            second: raw_second,
-            offset: raw_offset
-        })
+    fn has_offset() -> bool {
+        true
+    }
+
+    fn fields(&self) -> TemporalFields {
+        TemporalFields {
+            year: self.year(),
+            month: self.month(),
+            day: self.day(),
+            hour: self.hour(),
+            minute: self.minute(),
+            second: self.second(),
+            offset: self.offset(),
+            ..TemporalFields::default()
+        }
+    }
+
+    fn from_fields(fields: TemporalFields) -> Result<DateTimeOffset, CreationError> {
+        check_components::<DateTimeOffset>(&fields)?;
+
+        DateTimeOffset::new(fields.year, fields.month, fields.day, fields.hour, fields.minute,
+                            fields.second, fields.offset)
+    }
+}
+
+impl TruncateFrom<DateTimeSubSecondOffset> for DateTimeOffset {
+    fn truncate_from(value: &DateTimeSubSecondOffset) -> DateTimeOffset {
+        // fields were already validated when creating the source
+        DateTimeOffset::new(value.year(), value.month(), value.day(), value.hour(), value.minute(),
+                            value.second(), value.offset()).unwrap()
+    }
+}
+
+impl TryFrom<DateTimeSubSecondOffset> for DateTimeOffset {
+    type Error = NarrowingError;
+
+    /// Returns an error if the fractional second is anything other than `FractionalSecond::None`.
+    fn try_from(value: DateTimeSubSecondOffset) -> Result<DateTimeOffset, NarrowingError> {
+        if value.fractional_second() != FractionalSecond::None {
+            return Err(NarrowingError::FractionalSecond);
+        }
+
+        Ok(DateTimeOffset::truncate_from(&value))
+    }
+}
+
+impl DateTimeOffset {
+    /// Validate every field of a deserialized value, branching only once at the end
+    #[inline]
+    fn check_fields(&self) -> Result<(), DeserializationError> {
+        // every possible year, day, or offset is valid
+        let valid = deser_in_range_or_none(self.raw_month(), MONTH_RAW_MIN, MONTH_RAW_MAX,
+                                           MONTH_RAW_NONE)
+            & deser_in_range_or_none(self.raw_hour(), HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)
+            & deser_in_range_or_none(self.raw_minute(), MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)
+            & deser_in_range_or_none(self.raw_second(), SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE);
+
+        check_deser_fields(valid)
+    }
+
+    // the raw none values are all ones, so they are also the masks
+
+    #[inline]
+    fn raw_year(&self) -> u16 {
+        (self.bits >> YEAR_SHIFT) as u16 & YEAR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_month(&self) -> u8 {
+        (self.bits >> MONTH_SHIFT) as u8 & MONTH_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_day(&self) -> u8 {
+        (self.bits >> DAY_SHIFT) as u8 & DAY_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_hour(&self) -> u8 {
+        (self.bits >> HOUR_SHIFT) as u8 & HOUR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_minute(&self) -> u8 {
+        (self.bits >> MINUTE_SHIFT) as u8 & MINUTE_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_second(&self) -> u8 {
+        (self.bits >> SECOND_SHIFT) as u8 & SECOND_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_offset(&self) -> u8 {
+        self.bits as u8 & OFFSET_RAW_NONE
+    }
+}
+
+impl fmt::Debug for DateTimeOffset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DateTimeOffset")
+            .field("year", &self.year())
+            .field("month", &self.month())
+            .field("day", &self.day())
+            .field("hour", &self.hour())
+            .field("minute", &self.minute())
+            .field("second", &self.second())
+            .field("offset", &self.offset())
+            .finish()
     }
 }
 
 const SERIALIZED_SIZE: usize = 6;
+
+// bit positions in the encoded value
+const TAG_SHIFT: u32 = 40;
+const YEAR_SHIFT: u32 = 33;
+const MONTH_SHIFT: u32 = 29;
+const DAY_SHIFT: u32 = 24;
+const HOUR_SHIFT: u32 = 19;
+const MINUTE_SHIFT: u32 = 13;
+const SECOND_SHIFT: u32 = 7;
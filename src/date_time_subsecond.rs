@@ -1,18 +1,19 @@
+use std::convert::TryFrom;
+use std::fmt;
 use std::io::{Read, Write};
 
 use super::*;
 use super::frac_second;
 
 /// A Date and Time with subsecond precision.
-#[derive(Debug, PartialEq)]
+///
+/// Values with the same precision compare in the same order as their encoded bytes, but since the
+/// encoding starts with the precision this isn't chronological, so only equality is provided. See
+/// `OrderedKey` for a chronological order.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DateTimeSubSecond {
-    year: u16,
-    month: u8,
-    day: u8,
-    hour: u8,
-    minute: u8,
-    second: u8,
-    frac_second_fw: u32
+    /// The encoded value in the high `serialized_size()` bytes, with the rest zero
+    bits: u128
 }
 
 impl DateTimeSubSecond {
@@ -23,73 +24,117 @@ impl DateTimeSubSecond {
                minute: Option<u8>, second: Option<u8>, frac_second: FractionalSecond) -> Result<DateTimeSubSecond, CreationError> {
         check_frac_second(frac_second)?;
 
-        Ok(DateTimeSubSecond {
-            year: year_num(year)?,
-            month: month_num(month)?,
-            day: day_num(day)?,
-            hour: hour_num(hour)?,
-            minute: minute_num(minute)?,
-            second: second_num(second)?,
-            frac_second_fw: frac_second::encode_fixed_width(&frac_second)
-        })
+        let precision = frac_second::precision(&frac_second);
+        let tag = DATE_TIME_SUBSECOND_TAG | PRECISION_TAGS[precision];
+
+        let bits = ((tag as u128) << TAG_SHIFT)
+            | ((year_num(year)? as u128) << YEAR_SHIFT)
+            | ((month_num(month)? as u128) << MONTH_SHIFT)
+            | ((day_num(day)? as u128) << DAY_SHIFT)
+            | ((hour_num(hour)? as u128) << HOUR_SHIFT)
+            | ((minute_num(minute)? as u128) << MINUTE_SHIFT)
+            | ((second_num(second)? as u128) << SECOND_SHIFT)
+            | ((frac_second::encoded_value(&frac_second) as u128) << frac_second_shift(precision));
+
+        Ok(DateTimeSubSecond { bits })
+    }
+
+    /// Convert to a type with fewer components, discarding the fractional second if the target
+    /// type has none. Use `TryFrom` instead if discarding it would be a mistake.
+    pub fn truncate_to<T: TruncateFrom<DateTimeSubSecond>>(&self) -> T {
+        T::truncate_from(self)
+    }
+
+    /// The value packed into the low 70 bits of a `u128`, as described in
+    /// `DateTime::to_packed_u64()`. The fractional second takes 32 bits holding the nanoseconds
+    /// and the precision, so that values with the same time but different precisions have
+    /// different packed forms. See `to_packed_u64()` for values without nanoseconds.
+    pub fn to_packed_u128(&self) -> u128 {
+        key::to_packed(self)
+    }
+
+    /// Like `to_packed_u128()`, but in the low 59 bits of a `u64`, in the same order. The
+    /// fractional second takes 21 bits holding the microseconds and the precision.
+    ///
+    /// Returns an error if the precision is nanoseconds, which need more bits.
+    pub fn to_packed_u64(&self) -> Result<u64, ConversionError> {
+        // one more than the value, leaving zero unused as in `OrderedKey`
+        let frac_key = match self.fractional_second() {
+            FractionalSecond::Milliseconds(ms) => (ms as u64 * 1_000 + 1) << 1,
+            FractionalSecond::Microseconds(us) => ((us as u64 + 1) << 1) | 1,
+            FractionalSecond::Nanoseconds(_) => return Err(ConversionError::PrecisionLoss),
+            FractionalSecond::None => PACKED_U64_FRAC_NONE
+        };
+
+        Ok((self.truncate_to::<DateTime>().to_packed_u64() << PACKED_U64_FRAC_BITS) | frac_key)
+    }
+
+    /// The inverse of `to_packed_u64()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u64(packed: u64) -> Result<DateTimeSubSecond, DeserializationError> {
+        let date_time = DateTime::from_packed_u64(packed >> PACKED_U64_FRAC_BITS)?;
+
+        let frac_key = packed & PACKED_U64_FRAC_NONE;
+        let frac_second = if frac_key == PACKED_U64_FRAC_NONE {
+            FractionalSecond::None
+        } else {
+            let us = (frac_key >> 1).checked_sub(1)
+                .ok_or(DeserializationError::InvalidFieldValue)? as u32;
+            match frac_key & 1 {
+                0 if us % 1_000 == 0 => FractionalSecond::Milliseconds((us / 1_000) as u16),
+                1 => FractionalSecond::Microseconds(us),
+                _ => return Err(DeserializationError::InvalidFieldValue)
+            }
+        };
+
+        DateTimeSubSecond::new(date_time.year(), date_time.month(), date_time.day(),
+                               date_time.hour(), date_time.minute(), date_time.second(),
+                               frac_second)
+            .map_err(|_| DeserializationError::InvalidFieldValue)
+    }
+
+    /// The inverse of `to_packed_u128()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u128(packed: u128) -> Result<DateTimeSubSecond, DeserializationError> {
+        key::from_packed(packed)
     }
 }
 
 impl Date for DateTimeSubSecond {
     fn year(&self) -> Option<u16> {
-        if self.year == YEAR_RAW_NONE {
-            None
-        } else {
-            Some(self.year)
-        }
+        year_value(self.raw_year())
     }
 
     fn month(&self) -> Option<u8> {
-        if self.month == MONTH_RAW_NONE {
-            None
-        } else {
-            Some(self.month + 1)
-        }
+        month_value(self.raw_month())
     }
 
     fn day(&self) -> Option<u8> {
-        if self.day == DAY_RAW_NONE {
-            None
-        } else {
-            Some(self.day + 1)
-        }
+        day_value(self.raw_day())
     }
 }
 
 impl Time for DateTimeSubSecond {
     fn hour(&self) -> Option<u8> {
-        if self.hour == HOUR_RAW_NONE {
-            None
-        } else {
-            Some(self.hour)
-        }
+        hour_value(self.raw_hour())
     }
 
     fn minute(&self) -> Option<u8> {
-        if self.minute == MINUTE_RAW_NONE {
-            None
-        } else {
-            Some(self.minute)
-        }
+        minute_value(self.raw_minute())
     }
 
     fn second(&self) -> Option<u8> {
-        if self.second == SECOND_RAW_NONE {
-            None
-        } else {
-            Some(self.second)
-        }
+        second_value(self.raw_second())
     }
 }
 
 impl SubSecond for DateTimeSubSecond {
     fn fractional_second(&self) -> FractionalSecond {
-        frac_second::decode_fixed_width(self.frac_second_fw)
+        let precision = self.precision();
+
+        frac_second::decode(precision, self.raw_frac_second(precision))
     }
 }
 
@@ -99,66 +144,18 @@ impl Serializable for DateTimeSubSecond {
     }
 
     fn serialized_size(&self) -> usize {
-        match frac_second::decode_fixed_width(self.frac_second_fw) {
-            FractionalSecond::Milliseconds(_) => 7,
-            FractionalSecond::Microseconds(_) => 8,
-            FractionalSecond::Nanoseconds(_) => MAX_SERIALIZED_SIZE,
-            FractionalSecond::None => MIN_SERIALIZED_SIZE,
-        }
+        SERIALIZED_SIZES[self.precision()]
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
-        let b0_partial = DATE_TIME_SUBSECOND_TAG | (self.year >> 8) as u8;
-
-        let b1 = self.year as u8;
-        let b2 = (self.month << 4) | (self.day >> 1);
-        let b3 = (self.day << 7) | (self.hour << 2) | (self.minute >> 4);
-        let b4 = (self.minute << 4) | (self.second >> 2);
-        let b5_partial = self.second << 6;
-
-        let mut buf = [0, b1, b2, b3, b4, 0, 0, 0, 0];
-
-        let frac_prefix = frac_second::FRAC_SECOND_FIXED_WIDTH_PREFIX_MASK & self.frac_second_fw;
-        let frac_value = frac_second::FRAC_SECOND_FIXED_WIDTH_VALUE_MASK & self.frac_second_fw;
-
-        let slice_end_index = match frac_prefix {
-            frac_second::FRAC_SECOND_FIXED_WIDTH_NONE => {
-                buf[0] = b0_partial | PRECISION_DTS_NONE_TAG;
-                buf[5] = b5_partial;
-                6
-            },
-            frac_second::FRAC_SECOND_FIXED_WIDTH_MILLI => {
-                buf[0] = b0_partial | PRECISION_DTS_MILLIS_TAG;
-                buf[5] = b5_partial | (frac_value >> 4) as u8;
-                buf[6] = (frac_value << 4) as u8;
-                7
-            },
-            frac_second::FRAC_SECOND_FIXED_WIDTH_MICRO => {
-                buf[0] = b0_partial | PRECISION_DTS_MICROS_TAG;
-                buf[5] = b5_partial | (frac_value >> 14) as u8;
-                buf[6] = (frac_value >> 6) as u8;
-                buf[7] = (frac_value << 2) as u8;
-                8
-            },
-            frac_second::FRAC_SECOND_FIXED_WIDTH_NANO => {
-                buf[0] = b0_partial | PRECISION_DTS_NANOS_TAG;
-                buf[5] = b5_partial | (frac_value >> 24) as u8;
-                buf[6] = (frac_value >> 16) as u8;
-                buf[7] = (frac_value >> 8) as u8;
-                buf[8] = frac_value as u8;
-                9
-            },
-            _ => panic!("Corrupt fixed width encoded fractional second")
-        };
-
-        write_array_map_err(&buf[0..slice_end_index], writer)
+        write_array_map_err(&self.bits.to_be_bytes()[..self.serialized_size()], writer)
             .map_err(|_| SerializationError::IoError)
     }
 }
 
 impl Deserializable for DateTimeSubSecond {
     fn deserialize<R: Read>(reader: &mut R) -> Result<DateTimeSubSecond, DeserializationError> {
-        let mut buf = [0; MAX_SERIALIZED_SIZE];
+        let mut buf = [0; 16];
         read_exact(reader, &mut buf[0..MIN_SERIALIZED_SIZE])?;
 
         let byte0 = buf[0];
@@ -172,79 +169,225 @@ impl Deserializable for DateTimeSubSecond {
         // TTPP YYYY | YYYY YYYY | MMMM DDDD | DHHH HHMM
         // MMMM SSSS | SSFF FFFF | [0, 1, 2, or 3 subsecond bytes]
 
-        let byte1 = buf[1];
-        let mut raw_year = ((byte0 & 0x0F) as u16) << 8;
-        raw_year |= byte1 as u16;
+        // every precision tag is valid
+        let precision = precision_of(byte0);
+        // constant lengths let the reads be inlined
+        match SERIALIZED_SIZES[precision] - MIN_SERIALIZED_SIZE {
+            1 => read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 1)])?,
+            2 => read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 2)])?,
+            3 => read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 3)])?,
+            _ => {}
+        }
 
-        let byte2 = buf[2];
-        let raw_month = byte2 >> 4;
+        // clear the padding at the end of the last byte
+        let bits = u128::from_be_bytes(buf) & ENCODED_MASKS[precision];
+        let value = DateTimeSubSecond { bits };
 
-        let byte3 = buf[3];
-        let raw_day = ((byte2 & 0x0F) << 1) | (byte3 >> 7);
+        value.check_fields(precision)?;
 
-        let raw_hour = (byte3 & 0x7C) >> 2;
+        Ok(value)
+    }
 
-        let byte4 = buf[4];
-        let raw_minute = ((byte3 & 0x03) << 4) | (byte4 >> 4);
+    fn deserialize_from_slice(bytes: &[u8])
+                              -> Result<(DateTimeSubSecond, usize), DeserializationError> {
+        if bytes.len() < 16 {
+            return deserialize_prefix(bytes);
+        }
 
-        let byte5 = buf[5];
-        let raw_second = ((byte4 & 0x0F) << 2) | ((byte5 & 0xC0) >> 6);
+        let wide = load_be_u128(bytes);
+        let byte0 = (wide >> TAG_SHIFT) as u8;
 
-        let frac_second_fw = match byte0 & PRECISION_DTS_MASK {
-            PRECISION_DTS_NONE_TAG => frac_second::encode_none(),
-            PRECISION_DTS_MILLIS_TAG => {
-                read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 1)])?;
-                let mut ms = ((byte5 & 0x3F) as u16) << 4;
-                ms |= (buf[6] >> 4) as u16;
+        if byte0 & 0b1100_0000 != DATE_TIME_SUBSECOND_TAG {
+            return Err(DeserializationError::IncorrectTypeTag);
+        }
 
-                check_in_range(ms, MILLIS_MIN, MILLIS_MAX,
-                               DeserializationError::InvalidFieldValue)?;
-                frac_second::encode_millis(ms)
-            }
-            PRECISION_DTS_MICROS_TAG => {
-                read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 2)])?;
-                let mut us = ((byte5 & 0x3F) as u32) << 14;
-                us |= (buf[6] as u32) << 6;
-                us |= (buf[7] >> 2) as u32;
-
-                check_in_range(us, MICROS_MIN, MICROS_MAX,
-                               DeserializationError::InvalidFieldValue)?;
-                frac_second::encode_micros(us)
-            }
-            PRECISION_DTS_NANOS_TAG  => {
-                read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..MAX_SERIALIZED_SIZE])?;
-                let mut ns = ((byte5 & 0x3F) as u32) << 24;
-                ns |= (buf[6] as u32) << 16;
-                ns |= (buf[7] as u32) << 8;
-                ns |= buf[8] as u32;
-
-                check_in_range(ns, NANOS_MIN, NANOS_MAX,
-                               DeserializationError::InvalidFieldValue)?;
-                frac_second::encode_nanos(ns)
-            },
-            _ => {
-                return Err(DeserializationError::IncorrectPrecisionTag);
-            }
-        };
+        let precision = precision_of(byte0);
+        // clear the padding and whatever follows the value
+        let value = DateTimeSubSecond { bits: wide & ENCODED_MASKS[precision] };
+        value.check_fields(precision)?;
+
+        Ok((value, SERIALIZED_SIZES[precision]))
+    }
+}
+
+impl Temporal for DateTimeSubSecond {
+    fn type_tag() -> TypeTag {
+        TypeTag::DateTimeSubSecond
+    }
+
+    fn min_serialized_size() -> usize {
+        MIN_SERIALIZED_SIZE
+    }
+
+    fn has_date() -> bool {
+        true
+    }
+
+    fn has_time() -> bool {
+        true
+    }
+
+    fn has_sub_second() -> bool {
+        true
+    }
+
+    fn has_offset() -> bool {
+        false
+    }
+
+    fn fields(&self) -> TemporalFields {
+        TemporalFields {
+            year: self.year(),
+            month: self.month(),
+            day: self.day(),
+            hour: self.hour(),
+            minute: self.minute(),
+            second: self.second(),
+            frac_second: self.fractional_second(),
+            ..TemporalFields::default()
+        }
+    }
 
-        // no need to check year as every possible number is a valid year
-        check_deser_in_range_or_none(raw_month, MONTH_RAW_MIN, MONTH_RAW_MAX, MONTH_RAW_NONE)?;
-        // no need to check day as every possible number is a valid day
-        check_deser_in_range_or_none(raw_hour, HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_minute, MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_second, SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)?;
-
-        Ok(DateTimeSubSecond {
-            year: raw_year,
-            month: raw_month,
-            day: raw_day,
-            hour: raw_hour,
-            minute: raw_minute,
-            second: raw_second,
-            frac_second_fw: frac_second_fw
-        })
+    fn from_fields(fields: TemporalFields) -> Result<DateTimeSubSecond, CreationError> {
+        check_components::<DateTimeSubSecond>(&fields)?;
+
+        DateTimeSubSecond::new(fields.year, fields.month, fields.day, fields.hour, fields.minute,
+                               fields.second, fields.frac_second)
     }
 }
 
+impl TruncateFrom<DateTimeSubSecondOffset> for DateTimeSubSecond {
+    fn truncate_from(value: &DateTimeSubSecondOffset) -> DateTimeSubSecond {
+        // fields were already validated when creating the source
+        DateTimeSubSecond::new(value.year(), value.month(), value.day(), value.hour(),
+                               value.minute(), value.second(), value.fractional_second()).unwrap()
+    }
+}
+
+impl TryFrom<DateTimeSubSecondOffset> for DateTimeSubSecond {
+    type Error = NarrowingError;
+
+    /// Returns an error if the offset is anything other than `OffsetValue::None`.
+    fn try_from(value: DateTimeSubSecondOffset) -> Result<DateTimeSubSecond, NarrowingError> {
+        if value.offset() != OffsetValue::None {
+            return Err(NarrowingError::Offset);
+        }
+
+        Ok(DateTimeSubSecond::truncate_from(&value))
+    }
+}
+
+impl DateTimeSubSecond {
+    /// Validate every field of a deserialized value, branching only once at the end
+    #[inline]
+    fn check_fields(&self, precision: usize) -> Result<(), DeserializationError> {
+        // every possible year or day is valid
+        let valid = deser_in_range_or_none(self.raw_month(), MONTH_RAW_MIN, MONTH_RAW_MAX,
+                                           MONTH_RAW_NONE)
+            & deser_in_range_or_none(self.raw_hour(), HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)
+            & deser_in_range_or_none(self.raw_minute(), MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)
+            & deser_in_range_or_none(self.raw_second(), SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)
+            & (self.raw_frac_second(precision) <= frac_second::MAX_VALUES[precision]);
+
+        check_deser_fields(valid)
+    }
+
+    // the raw none values are all ones, so they are also the masks
+
+    #[inline]
+    fn raw_year(&self) -> u16 {
+        (self.bits >> YEAR_SHIFT) as u16 & YEAR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_month(&self) -> u8 {
+        (self.bits >> MONTH_SHIFT) as u8 & MONTH_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_day(&self) -> u8 {
+        (self.bits >> DAY_SHIFT) as u8 & DAY_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_hour(&self) -> u8 {
+        (self.bits >> HOUR_SHIFT) as u8 & HOUR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_minute(&self) -> u8 {
+        (self.bits >> MINUTE_SHIFT) as u8 & MINUTE_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_second(&self) -> u8 {
+        (self.bits >> SECOND_SHIFT) as u8 & SECOND_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_frac_second(&self, precision: usize) -> u32 {
+        (self.low_bits() >> (frac_second_shift(precision) - LOW_SHIFT)) as u32
+            & ((1 << frac_second::ENCODED_BITS[precision]) - 1)
+    }
+
+    /// The bits after `LOW_SHIFT`, which hold the fields whose position depends on the
+    /// precision. Shifting a `u64` by a variable amount is much cheaper than a `u128`.
+    #[inline]
+    fn low_bits(&self) -> u64 {
+        (self.bits >> LOW_SHIFT) as u64
+    }
+
+    #[inline]
+    fn precision(&self) -> usize {
+        precision_of((self.bits >> TAG_SHIFT) as u8)
+    }
+}
+
+impl fmt::Debug for DateTimeSubSecond {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DateTimeSubSecond")
+            .field("year", &self.year())
+            .field("month", &self.month())
+            .field("day", &self.day())
+            .field("hour", &self.hour())
+            .field("minute", &self.minute())
+            .field("second", &self.second())
+            .field("fractional_second", &self.fractional_second())
+            .finish()
+    }
+}
+
+/// The precision from the first byte of the encoded value. The precision tags are the
+/// precision shifted into place.
+#[inline]
+fn precision_of(byte0: u8) -> usize {
+    ((byte0 & PRECISION_DTS_MASK) >> PRECISION_DTS_MASK.trailing_zeros()) as usize
+}
+
+/// The position of the fractional second, which follows the second
+#[inline]
+fn frac_second_shift(precision: usize) -> u32 {
+    SECOND_SHIFT - frac_second::ENCODED_BITS[precision]
+}
+
+/// By precision, as in `frac_second::precision()`
+const PRECISION_TAGS: [u8; 4] = [PRECISION_DTS_MILLIS_TAG, PRECISION_DTS_MICROS_TAG,
+                                 PRECISION_DTS_NANOS_TAG, PRECISION_DTS_NONE_TAG];
+/// By precision, all ones for the bits of the encoded value
+const ENCODED_MASKS: [u128; 4] = [!0 << 76, !0 << 66, !0 << 56, !0 << 86];
+/// Width of the fractional second in `to_packed_u64()`, and its value when absent
+const PACKED_U64_FRAC_BITS: u32 = 21;
+const PACKED_U64_FRAC_NONE: u64 = (1 << PACKED_U64_FRAC_BITS) - 1;
+const SERIALIZED_SIZES: [usize; 4] = [7, 8, 9, 6];
 const MIN_SERIALIZED_SIZE: usize = 6;
 const MAX_SERIALIZED_SIZE: usize = 9;
+
+// bit positions in the encoded value, which is in the high bits
+const TAG_SHIFT: u32 = 120;
+const LOW_SHIFT: u32 = 56;
+const YEAR_SHIFT: u32 = 112;
+const MONTH_SHIFT: u32 = 108;
+const DAY_SHIFT: u32 = 103;
+const HOUR_SHIFT: u32 = 98;
+const MINUTE_SHIFT: u32 = 92;
+const SECOND_SHIFT: u32 = 86;
@@ -1,19 +1,18 @@
+use std::fmt;
 use std::io::{Read, Write};
 
 use super::*;
 use super::frac_second;
 
 /// A Date and Time with subsecond precision and UTC offset.
-#[derive(Debug, PartialEq)]
+///
+/// Values with the same precision compare in the same order as their encoded bytes, but since the
+/// encoding starts with the precision this isn't chronological, so only equality is provided. See
+/// `OrderedKey` for a chronological order.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DateTimeSubSecondOffset {
-    year: u16,
-    month: u8,
-    day: u8,
-    hour: u8,
-    minute: u8,
-    second: u8,
-    frac_second_fw: u32,
-    offset: u8
+    /// The encoded value in the high `serialized_size()` bytes, with the rest zero
+    bits: u128
 }
 
 impl DateTimeSubSecondOffset {
@@ -25,84 +24,197 @@ impl DateTimeSubSecondOffset {
                offset: OffsetValue) -> Result<DateTimeSubSecondOffset, CreationError> {
         check_frac_second(frac_second)?;
 
-        Ok(DateTimeSubSecondOffset {
-            year: year_num(year)?,
-            month: month_num(month)?,
-            day: day_num(day)?,
-            hour: hour_num(hour)?,
-            minute: minute_num(minute)?,
-            second: second_num(second)?,
-            frac_second_fw: frac_second::encode_fixed_width(&frac_second),
-            offset: offset_num(offset)?
-        })
+        let precision = frac_second::precision(&frac_second);
+        let tag = DATE_TIME_SUBSECOND_OFFSET_TAG | PRECISION_TAGS[precision];
+
+        let bits = ((tag as u128) << TAG_SHIFT)
+            | ((year_num(year)? as u128) << YEAR_SHIFT)
+            | ((month_num(month)? as u128) << MONTH_SHIFT)
+            | ((day_num(day)? as u128) << DAY_SHIFT)
+            | ((hour_num(hour)? as u128) << HOUR_SHIFT)
+            | ((minute_num(minute)? as u128) << MINUTE_SHIFT)
+            | ((second_num(second)? as u128) << SECOND_SHIFT)
+            | ((frac_second::encoded_value(&frac_second) as u128) << frac_second_shift(precision))
+            | ((offset_num(offset)? as u128) << offset_shift(precision));
+
+        Ok(DateTimeSubSecondOffset { bits })
+    }
+
+    /// Combine a local date and time with an offset in seconds east of UTC, which may not be
+    /// representable in Temporenc (like local mean time), storing the offset as described by
+    /// `rounding`. Also returns how much information was lost, if any.
+    ///
+    /// Returns an error if the offset needs adjusting and any of year, month, day, hour, and
+    /// minute are missing, or if the result is out of range.
+    pub fn from_local(local: &DateTimeSubSecond, offset_seconds: i32, rounding: OffsetRounding)
+                      -> Result<(DateTimeSubSecondOffset, OffsetAdjustment), ConversionError> {
+        let (fields, adjustment) =
+            offset_rounding::round_offset(&local.fields(), 0, offset_seconds as i64, rounding)?;
+
+        DateTimeSubSecondOffset::from_fields(fields)
+            .map(|value| (value, adjustment))
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The same instant at `UtcOffset(0)`, carrying into the date as needed.
+    ///
+    /// Returns an error if the offset is not a `UtcOffset`, or if any of year, month, day, hour,
+    /// and minute are missing, since the result would then be undefined.
+    pub fn to_utc(&self) -> Result<DateTimeSubSecondOffset, ConversionError> {
+        self.to_offset(0)
+    }
+
+    /// Like `to_utc()`, but without the offset.
+    pub fn to_naive_utc(&self) -> Result<DateTimeSubSecond, ConversionError> {
+        self.to_utc().map(|dtso| dtso.truncate_to())
+    }
+
+    /// The same instant at `UtcOffset(offset_minutes)`. `offset_minutes` must be a valid
+    /// `OffsetValue::UtcOffset`.
+    ///
+    /// Returns an error in the same cases as `to_utc()`.
+    pub fn to_offset(&self, offset_minutes: i16)
+                     -> Result<DateTimeSubSecondOffset, ConversionError> {
+        let fields = calendar::change_offset(&self.fields(), offset_minutes)?;
+
+        DateTimeSubSecondOffset::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Returns an error if the second is 60 anywhere other than at a real leap second, after
+    /// converting to UTC. Values without second 60 are always accepted.
+    ///
+    /// Also returns an error if the second is 60 and the offset is not a `UtcOffset`, or if any
+    /// of year, month, day, hour, and minute are missing, since it can't be checked then.
+    pub fn check_leap_second(&self) -> Result<(), ConversionError> {
+        leap_seconds::check_leap_second(&self.fields())
+    }
+
+    /// Nanoseconds since 1970-01-01T00:00:00Z, not counting leap seconds. Second 60 is treated
+    /// as described by `mode`, and a missing fractional second as zero.
+    ///
+    /// Returns an error if the offset is not a `UtcOffset`, or if any of year, month, day, hour,
+    /// minute, and second are missing.
+    pub fn to_unix_nanos(&self, mode: LeapSecondMode) -> Result<i128, ConversionError> {
+        unix_time::to_unix_nanos(&self.fields(), mode)
+    }
+
+    /// The UTC value `unix_nanos` nanoseconds after 1970-01-01T00:00:00Z, not counting leap
+    /// seconds, with nanosecond precision.
+    ///
+    /// Returns an error if the result is outside the years Temporenc can represent.
+    pub fn from_unix_nanos(unix_nanos: i128) -> Result<DateTimeSubSecondOffset, ConversionError> {
+        DateTimeSubSecondOffset::from_fields(unix_time::from_unix_nanos(unix_nanos)?)
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The inverse of `to_unix_nanos()` with `LeapSecondMode::Smear`: the UTC value for
+    /// `unix_nanos` from a clock that smears leap seconds, with nanosecond precision. Instants
+    /// during a leap second have second 60.
+    ///
+    /// Returns an error if the result is outside the years Temporenc can represent.
+    pub fn from_smeared_unix_nanos(unix_nanos: i128)
+                                   -> Result<DateTimeSubSecondOffset, ConversionError> {
+        DateTimeSubSecondOffset::from_fields(unix_time::from_smeared_unix_nanos(unix_nanos)?)
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The UTC value for `tai`, a date and time in International Atomic Time, with the same
+    /// fractional second.
+    ///
+    /// Returns an error if any of year, month, day, hour, minute, and second are missing, if the
+    /// second is 60 (TAI has no leap seconds), or if `tai` is before 1972.
+    pub fn from_tai(tai: &DateTimeSubSecond) -> Result<DateTimeSubSecondOffset, ConversionError> {
+        DateTimeSubSecondOffset::from_fields(time_scales::tai_to_utc(&tai.fields())?)
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The same instant in International Atomic Time, with the same fractional second.
+    ///
+    /// Returns an error if the offset is not a `UtcOffset`, if any of year, month, day, hour,
+    /// minute, and second are missing, if the second is 60 anywhere other than at a leap second,
+    /// or if the value is before 1972, when UTC started using leap seconds.
+    pub fn to_tai(&self) -> Result<DateTimeSubSecond, ConversionError> {
+        DateTimeSubSecond::from_fields(time_scales::utc_to_tai(&self.fields())?)
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The UTC value for a GPS time, with nanosecond precision.
+    ///
+    /// Returns an error if `seconds_of_week` or `nanos` are out of range, or if the result is
+    /// outside the years Temporenc can represent.
+    pub fn from_gps(gps: &GpsTime) -> Result<DateTimeSubSecondOffset, ConversionError> {
+        let utc = time_scales::tai_to_utc(&time_scales::gps_to_tai(gps)?)?;
+
+        DateTimeSubSecondOffset::from_fields(utc).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The same instant in GPS time. A missing fractional second is treated as zero.
+    ///
+    /// Returns an error in the same cases as `to_tai()`, or if the value is before GPS time
+    /// started at 1980-01-06T00:00:00Z.
+    pub fn to_gps(&self) -> Result<GpsTime, ConversionError> {
+        time_scales::tai_to_gps(&time_scales::utc_to_tai(&self.fields())?)
+    }
+
+    /// Convert to a type with fewer components, discarding the fractional second and/or offset if
+    /// the target type has none. Use `TryFrom` instead if discarding them would be a mistake.
+    pub fn truncate_to<T: TruncateFrom<DateTimeSubSecondOffset>>(&self) -> T {
+        T::truncate_from(self)
+    }
+
+    /// The value packed into the low 77 bits of a `u128`, as described in
+    /// `DateTimeSubSecond::to_packed_u128()` and `DateTimeOffset::to_packed_u64()`.
+    pub fn to_packed_u128(&self) -> u128 {
+        key::to_packed(self)
+    }
+
+    /// The inverse of `to_packed_u128()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u128(packed: u128) -> Result<DateTimeSubSecondOffset, DeserializationError> {
+        key::from_packed(packed)
     }
 }
 
 impl Date for DateTimeSubSecondOffset {
     fn year(&self) -> Option<u16> {
-        if self.year == YEAR_RAW_NONE {
-            None
-        } else {
-            Some(self.year)
-        }
+        year_value(self.raw_year())
     }
 
     fn month(&self) -> Option<u8> {
-        if self.month == MONTH_RAW_NONE {
-            None
-        } else {
-            Some(self.month + 1)
-        }
+        month_value(self.raw_month())
     }
 
     fn day(&self) -> Option<u8> {
-        if self.day == DAY_RAW_NONE {
-            None
-        } else {
-            Some(self.day + 1)
-        }
+        day_value(self.raw_day())
     }
 }
 
 impl Time for DateTimeSubSecondOffset {
     fn hour(&self) -> Option<u8> {
-        if self.hour == HOUR_RAW_NONE {
-            None
-        } else {
-            Some(self.hour)
-        }
+        hour_value(self.raw_hour())
     }
 
     fn minute(&self) -> Option<u8> {
-        if self.minute == MINUTE_RAW_NONE {
-            None
-        } else {
-            Some(self.minute)
-        }
+        minute_value(self.raw_minute())
     }
 
     fn second(&self) -> Option<u8> {
-        if self.second == SECOND_RAW_NONE {
-            None
-        } else {
-            Some(self.second)
-        }
+        second_value(self.raw_second())
     }
 }
 
 impl SubSecond for DateTimeSubSecondOffset {
     fn fractional_second(&self) -> FractionalSecond {
-        frac_second::decode_fixed_width(self.frac_second_fw)
+        let precision = self.precision();
+
+        frac_second::decode(precision, self.raw_frac_second(precision))
     }
 }
 
 impl Offset for DateTimeSubSecondOffset {
     fn offset(&self) -> OffsetValue {
-        match self.offset {
-            127 => OffsetValue::None,
-            126 => OffsetValue::SpecifiedElsewhere,
-            x => OffsetValue::UtcOffset(((x as i16) - 64) * 15)
-        }
+        offset_value(self.raw_offset())
     }
 }
 
@@ -112,69 +224,18 @@ impl Serializable for DateTimeSubSecondOffset {
     }
 
     fn serialized_size(&self) -> usize {
-        match frac_second::decode_fixed_width(self.frac_second_fw) {
-            FractionalSecond::Milliseconds(_) => 8,
-            FractionalSecond::Microseconds(_) => 9,
-            FractionalSecond::Nanoseconds(_) => MAX_SERIALIZED_SIZE,
-            FractionalSecond::None => MIN_SERIALIZED_SIZE,
-        }
+        SERIALIZED_SIZES[self.precision()]
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
-        let b0_partial = DATE_TIME_SUBSECOND_OFFSET_TAG | (self.year >> 9) as u8;
-        let b1 = (self.year >> 1) as u8;
-        let b2 = (self.year << 7) as u8 | (self.month << 3) | (self.day >> 2);
-        let b3 = (self.day << 6) | (self.hour << 1) | (self.minute >> 5);
-        let b4 = (self.minute << 3) | (self.second >> 3);
-        let b5_partial = self.second << 5;
-
-        let mut buf = [0, b1, b2, b3, b4, 0, 0, 0, 0, 0];
-
-        let frac_prefix = frac_second::FRAC_SECOND_FIXED_WIDTH_PREFIX_MASK & self.frac_second_fw;
-        let frac_value = frac_second::FRAC_SECOND_FIXED_WIDTH_VALUE_MASK & self.frac_second_fw;
-
-        let slice_end_index = match frac_prefix {
-            frac_second::FRAC_SECOND_FIXED_WIDTH_NONE => {
-                buf[0] = b0_partial | PRECISION_DTSO_NONE_TAG;
-                buf[5] = b5_partial | self.offset >> 2;
-                buf[6] = self.offset << 6;
-                7
-            },
-            frac_second::FRAC_SECOND_FIXED_WIDTH_MILLI => {
-                buf[0] = b0_partial | PRECISION_DTSO_MILLIS_TAG;
-                buf[5] = b5_partial | (frac_value >> 5) as u8;
-                buf[6] = ((frac_value << 3) as u8) | (self.offset >> 4);
-                buf[7] = self.offset << 4;
-                8
-            },
-            frac_second::FRAC_SECOND_FIXED_WIDTH_MICRO => {
-                buf[0] = b0_partial | PRECISION_DTSO_MICROS_TAG;
-                buf[5] = b5_partial | (frac_value >> 15) as u8;
-                buf[6] = (frac_value >> 7) as u8;
-                buf[7] = ((frac_value << 1) as u8) | self.offset >> 6;
-                buf[8] = self.offset << 2;
-                9
-            },
-            frac_second::FRAC_SECOND_FIXED_WIDTH_NANO => {
-                buf[0] = b0_partial | PRECISION_DTSO_NANOS_TAG;
-                buf[5] = b5_partial | (frac_value >> 25) as u8;
-                buf[6] = (frac_value >> 17) as u8;
-                buf[7] = (frac_value >> 9) as u8;
-                buf[8] = (frac_value >> 1) as u8;
-                buf[9] = (frac_value << 7) as u8 | self.offset;
-                10
-            },
-            _ => panic!("Corrupt fixed width encoded fractional second")
-        };
-
-        write_array_map_err(&buf[0..slice_end_index], writer)
+        write_array_map_err(&self.bits.to_be_bytes()[..self.serialized_size()], writer)
             .map_err(|_| SerializationError::IoError)
     }
 }
 
 impl Deserializable for DateTimeSubSecondOffset {
     fn deserialize<R: Read>(reader: &mut R) -> Result<DateTimeSubSecondOffset, DeserializationError> {
-        let mut buf = [0; MAX_SERIALIZED_SIZE];
+        let mut buf = [0; 16];
         read_exact(reader, &mut buf[0..MIN_SERIALIZED_SIZE])?;
 
         let byte0 = buf[0];
@@ -191,96 +252,215 @@ impl Deserializable for DateTimeSubSecondOffset {
         // SSSF FFFF | FFFF FFFF | FFFF FFFF | FFFF FFFF | FOOO OOOO [nanos]
         // SSSO OOOO | OO__ ____ [none]
 
-        let byte1 = buf[1];
-        let byte2 = buf[2];
-        let mut raw_year = ((byte0 & 0x07) as u16) << 9;
-        raw_year |= (byte1 as u16) << 1;
-        raw_year |= ((byte2 as u16) & 0x80) >> 7;
-
-        let raw_month = (byte2 & 0x78) >> 3;
-
-        let byte3 = buf[3];
-        let raw_day = ((byte2 & 0x07) << 2) | (byte3 >> 6);
-
-        let raw_hour = (byte3 & 0x3E) >> 1;
-
-        let byte4 = buf[4];
-        let raw_minute = ((byte3 & 0x01) << 5) | (byte4 >> 3);
-
-        let byte5 = buf[5];
-        let raw_second = ((byte4 & 0x07) << 3) | (byte5 >> 5);
-
-        let (frac_second_fw, raw_offset) = match byte0 & PRECISION_DTSO_MASK {
-            PRECISION_DTSO_MILLIS_TAG => {
-                read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 1)])?;
-                let mut ms = ((byte5 & 0x1F) as u16) << 5;
-                let byte6 = buf[6];
-                ms |= (byte6 >> 3) as u16;
-
-                check_in_range(ms, MILLIS_MIN, MILLIS_MAX,
-                               DeserializationError::InvalidFieldValue)?;
-
-                let raw_offset = ((byte6 & 0x07) << 4) | (buf[7] >> 4);
-                (frac_second::encode_millis(ms), raw_offset)
-            }
-            PRECISION_DTSO_MICROS_TAG => {
-                read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 2)])?;
-                let mut us = ((byte5 & 0x1F) as u32) << 15;
-                us |= (buf[6] as u32) << 7;
-                let byte7 = buf[7];
-                us |= (byte7 >> 1) as u32;
-
-                check_in_range(us, MICROS_MIN, MICROS_MAX,
-                               DeserializationError::InvalidFieldValue)?;
-
-                let raw_offset = ((byte7 & 0x01) << 6) | (buf[8] >> 2);
-
-                (frac_second::encode_micros(us), raw_offset)
-            }
-            PRECISION_DTSO_NANOS_TAG => {
-                read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..MAX_SERIALIZED_SIZE])?;
-                let mut ns = ((byte5 & 0x1F) as u32) << 25;
-                ns |= (buf[6] as u32) << 17;
-                ns |= (buf[7] as u32) << 9;
-                ns |= (buf[8] as u32) << 1;
-                let byte9 = buf[9];
-                ns |= (byte9 >> 7) as u32;
-
-                check_in_range(ns, NANOS_MIN, NANOS_MAX,
-                               DeserializationError::InvalidFieldValue)?;
-
-                let raw_offset = byte9 & 0x7F;
-                (frac_second::encode_nanos(ns), raw_offset)
-            },
-            PRECISION_DTSO_NONE_TAG => {
-                let raw_offset = ((byte5 & 0x1F) << 2) | (buf[6] >> 6);
-                (frac_second::encode_none(), raw_offset)
-            },
-            _ => {
-                return Err(DeserializationError::IncorrectPrecisionTag);
-            }
-        };
-
-        // no need to check year as every possible number is a valid year
-        check_deser_in_range_or_none(raw_month, MONTH_RAW_MIN, MONTH_RAW_MAX, MONTH_RAW_NONE)?;
-        // no need to check day as every possible number is a valid day
-        check_deser_in_range_or_none(raw_hour, HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_minute, MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_second, SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)?;
-        // no need to check offset as every possible number is a valid offset
-
-        Ok(DateTimeSubSecondOffset {
-            year: raw_year,
-            month: raw_month,
-            day: raw_day,
-            hour: raw_hour,
-            minute: raw_minute,
-            second: raw_second,
-            frac_second_fw: frac_second_fw,
-            offset: raw_offset
-        })
+        // every precision tag is valid
+        let precision = precision_of(byte0);
+        // constant lengths let the reads be inlined
+        match SERIALIZED_SIZES[precision] - MIN_SERIALIZED_SIZE {
+            1 => read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 1)])?,
+            2 => read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 2)])?,
+            3 => read_exact(reader, &mut buf[MIN_SERIALIZED_SIZE..(MIN_SERIALIZED_SIZE + 3)])?,
+            _ => {}
+        }
+
+        // clear the padding at the end of the last byte
+        let bits = u128::from_be_bytes(buf) & ENCODED_MASKS[precision];
+        let value = DateTimeSubSecondOffset { bits };
+
+        value.check_fields(precision)?;
+
+        Ok(value)
+    }
+
+    fn deserialize_from_slice(bytes: &[u8])
+                              -> Result<(DateTimeSubSecondOffset, usize), DeserializationError> {
+        if bytes.len() < 16 {
+            return deserialize_prefix(bytes);
+        }
+
+        let wide = load_be_u128(bytes);
+        let byte0 = (wide >> TAG_SHIFT) as u8;
+
+        if byte0 & 0b1110_0000 != DATE_TIME_SUBSECOND_OFFSET_TAG {
+            return Err(DeserializationError::IncorrectTypeTag);
+        }
+
+        let precision = precision_of(byte0);
+        // clear the padding and whatever follows the value
+        let value = DateTimeSubSecondOffset { bits: wide & ENCODED_MASKS[precision] };
+        value.check_fields(precision)?;
+
+        Ok((value, SERIALIZED_SIZES[precision]))
+    }
+}
+
+impl Temporal for DateTimeSubSecondOffset {
+    fn type_tag() -> TypeTag {
+        TypeTag::DateTimeSubSecondOffset
+    }
+
+    fn min_serialized_size() -> usize {
+        MIN_SERIALIZED_SIZE
+    }
+
+    fn has_date() -> bool {
+        true
+    }
+
+    fn has_time() -> bool {
+        true
+    }
+
+    fn has_sub_second() -> bool {
+        true
+    }
+
+    fn has_offset() -> bool {
+        true
+    }
+
+    fn fields(&self) -> TemporalFields {
+        TemporalFields {
+            year: self.year(),
+            month: self.month(),
+            day: self.day(),
+            hour: self.hour(),
+            minute: self.minute(),
+            second: self.second(),
+            frac_second: self.fractional_second(),
+            offset: self.offset(),
+        }
+    }
+
+    fn from_fields(fields: TemporalFields) -> Result<DateTimeSubSecondOffset, CreationError> {
+        check_components::<DateTimeSubSecondOffset>(&fields)?;
+
+        DateTimeSubSecondOffset::new(fields.year, fields.month, fields.day, fields.hour,
+                                     fields.minute, fields.second, fields.frac_second,
+                                     fields.offset)
     }
 }
 
+impl DateTimeSubSecondOffset {
+    /// Validate every field of a deserialized value, branching only once at the end
+    #[inline]
+    fn check_fields(&self, precision: usize) -> Result<(), DeserializationError> {
+        // every possible year, day, or offset is valid
+        let valid = deser_in_range_or_none(self.raw_month(), MONTH_RAW_MIN, MONTH_RAW_MAX,
+                                           MONTH_RAW_NONE)
+            & deser_in_range_or_none(self.raw_hour(), HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)
+            & deser_in_range_or_none(self.raw_minute(), MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)
+            & deser_in_range_or_none(self.raw_second(), SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)
+            & (self.raw_frac_second(precision) <= frac_second::MAX_VALUES[precision]);
+
+        check_deser_fields(valid)
+    }
+
+    // the raw none values are all ones, so they are also the masks
+
+    #[inline]
+    fn raw_year(&self) -> u16 {
+        (self.bits >> YEAR_SHIFT) as u16 & YEAR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_month(&self) -> u8 {
+        (self.bits >> MONTH_SHIFT) as u8 & MONTH_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_day(&self) -> u8 {
+        (self.bits >> DAY_SHIFT) as u8 & DAY_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_hour(&self) -> u8 {
+        (self.bits >> HOUR_SHIFT) as u8 & HOUR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_minute(&self) -> u8 {
+        (self.bits >> MINUTE_SHIFT) as u8 & MINUTE_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_second(&self) -> u8 {
+        (self.bits >> SECOND_SHIFT) as u8 & SECOND_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_offset(&self) -> u8 {
+        (self.low_bits() >> (offset_shift(self.precision()) - LOW_SHIFT)) as u8 & OFFSET_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_frac_second(&self, precision: usize) -> u32 {
+        (self.low_bits() >> (frac_second_shift(precision) - LOW_SHIFT)) as u32
+            & ((1 << frac_second::ENCODED_BITS[precision]) - 1)
+    }
+
+    /// The bits after `LOW_SHIFT`, which hold the fields whose position depends on the
+    /// precision. Shifting a `u64` by a variable amount is much cheaper than a `u128`.
+    #[inline]
+    fn low_bits(&self) -> u64 {
+        (self.bits >> LOW_SHIFT) as u64
+    }
+
+    #[inline]
+    fn precision(&self) -> usize {
+        precision_of((self.bits >> TAG_SHIFT) as u8)
+    }
+}
+
+impl fmt::Debug for DateTimeSubSecondOffset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DateTimeSubSecondOffset")
+            .field("year", &self.year())
+            .field("month", &self.month())
+            .field("day", &self.day())
+            .field("hour", &self.hour())
+            .field("minute", &self.minute())
+            .field("second", &self.second())
+            .field("fractional_second", &self.fractional_second())
+            .field("offset", &self.offset())
+            .finish()
+    }
+}
+
+/// The precision from the first byte of the encoded value. The precision tags are the
+/// precision shifted into place.
+#[inline]
+fn precision_of(byte0: u8) -> usize {
+    ((byte0 & PRECISION_DTSO_MASK) >> PRECISION_DTSO_MASK.trailing_zeros()) as usize
+}
+
+/// The position of the fractional second, which follows the second
+#[inline]
+fn frac_second_shift(precision: usize) -> u32 {
+    SECOND_SHIFT - frac_second::ENCODED_BITS[precision]
+}
+
+/// The position of the offset, which follows the fractional second
+#[inline]
+fn offset_shift(precision: usize) -> u32 {
+    frac_second_shift(precision) - OFFSET_BITS
+}
+
+/// By precision, as in `frac_second::precision()`
+const PRECISION_TAGS: [u8; 4] = [PRECISION_DTSO_MILLIS_TAG, PRECISION_DTSO_MICROS_TAG,
+                                 PRECISION_DTSO_NANOS_TAG, PRECISION_DTSO_NONE_TAG];
+/// By precision, all ones for the bits of the encoded value
+const ENCODED_MASKS: [u128; 4] = [!0 << 68, !0 << 58, !0 << 48, !0 << 78];
+const SERIALIZED_SIZES: [usize; 4] = [8, 9, 10, 7];
 const MIN_SERIALIZED_SIZE: usize = 7;
 const MAX_SERIALIZED_SIZE: usize = 10;
+
+// bit positions in the encoded value, which is in the high bits
+const TAG_SHIFT: u32 = 120;
+const LOW_SHIFT: u32 = 48;
+const YEAR_SHIFT: u32 = 111;
+const MONTH_SHIFT: u32 = 107;
+const DAY_SHIFT: u32 = 102;
+const HOUR_SHIFT: u32 = 97;
+const MINUTE_SHIFT: u32 = 91;
+const SECOND_SHIFT: u32 = 85;
+const OFFSET_BITS: u32 = 7;
@@ -0,0 +1,55 @@
+use super::{NANOS_PER_DAY, NANOS_PER_HOUR, NANOS_PER_MINUTE, NANOS_PER_SECOND};
+
+/// An exact, signed length of time with nanosecond resolution. A day is always 86400 seconds.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct SignedDuration {
+    nanos: i128,
+}
+
+impl SignedDuration {
+    #[inline]
+    pub fn from_nanos(nanos: i128) -> SignedDuration {
+        SignedDuration { nanos }
+    }
+
+    #[inline]
+    pub fn from_micros(micros: i64) -> SignedDuration {
+        SignedDuration::from_nanos((micros as i128) * 1_000)
+    }
+
+    #[inline]
+    pub fn from_millis(millis: i64) -> SignedDuration {
+        SignedDuration::from_nanos((millis as i128) * 1_000_000)
+    }
+
+    #[inline]
+    pub fn from_secs(secs: i64) -> SignedDuration {
+        SignedDuration::from_nanos((secs as i128) * NANOS_PER_SECOND)
+    }
+
+    #[inline]
+    pub fn from_minutes(minutes: i64) -> SignedDuration {
+        SignedDuration::from_nanos((minutes as i128) * NANOS_PER_MINUTE)
+    }
+
+    #[inline]
+    pub fn from_hours(hours: i64) -> SignedDuration {
+        SignedDuration::from_nanos((hours as i128) * NANOS_PER_HOUR)
+    }
+
+    #[inline]
+    pub fn from_days(days: i64) -> SignedDuration {
+        SignedDuration::from_nanos((days as i128) * NANOS_PER_DAY)
+    }
+
+    /// The whole duration in nanoseconds.
+    #[inline]
+    pub fn as_nanos(&self) -> i128 {
+        self.nanos
+    }
+
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.nanos < 0
+    }
+}
@@ -6,53 +6,55 @@ pub enum FractionalSecond {
     None
 }
 
-pub fn encode_fixed_width(f: &FractionalSecond) -> u32 {
-    match f {
-        &FractionalSecond::Milliseconds(x) => encode_millis(x),
-        &FractionalSecond::Microseconds(x) => encode_micros(x),
-        &FractionalSecond::Nanoseconds(x) => encode_nanos(x),
-        &FractionalSecond::None => FRAC_SECOND_FIXED_WIDTH_NONE
+/// The precision as in the precision tags, before being shifted into place: 0 for milliseconds,
+/// 1 for microseconds, 2 for nanoseconds, and 3 for none.
+#[inline]
+pub fn precision(f: &FractionalSecond) -> usize {
+    match *f {
+        FractionalSecond::Milliseconds(_) => 0,
+        FractionalSecond::Microseconds(_) => 1,
+        FractionalSecond::Nanoseconds(_) => 2,
+        FractionalSecond::None => 3
     }
 }
 
-pub fn encode_millis(millis: u16) -> u32 {
-    FRAC_SECOND_FIXED_WIDTH_MILLI | (millis as u32)
-}
-
-pub fn encode_micros(micros: u32) -> u32 {
-    FRAC_SECOND_FIXED_WIDTH_MICRO | micros
+/// The value as it is encoded, with `None` as zero.
+#[inline]
+pub fn encoded_value(f: &FractionalSecond) -> u32 {
+    match *f {
+        FractionalSecond::Milliseconds(x) => x as u32,
+        FractionalSecond::Microseconds(x) | FractionalSecond::Nanoseconds(x) => x,
+        FractionalSecond::None => 0
+    }
 }
 
-pub fn encode_nanos(nanos: u32) -> u32 {
-    FRAC_SECOND_FIXED_WIDTH_NANO | nanos
+/// The inverse of `precision()` and `encoded_value()`.
+#[inline]
+pub fn decode(precision: usize, value: u32) -> FractionalSecond {
+    match precision {
+        0 => FractionalSecond::Milliseconds(value as u16),
+        1 => FractionalSecond::Microseconds(value),
+        2 => FractionalSecond::Nanoseconds(value),
+        _ => FractionalSecond::None
+    }
 }
 
-pub fn encode_none() -> u32 {
-    FRAC_SECOND_FIXED_WIDTH_NONE
-}
+/// The number of bits the value takes in the encoding, by precision
+pub const ENCODED_BITS: [u32; 4] = [10, 20, 30, 0];
 
-#[inline]
-pub fn decode_fixed_width(encoded: u32) -> FractionalSecond {
-    let prefix = FRAC_SECOND_FIXED_WIDTH_PREFIX_MASK & encoded;
-    let value = FRAC_SECOND_FIXED_WIDTH_VALUE_MASK & encoded;
+/// The largest encoded value, by precision
+pub const MAX_VALUES: [u32; 4] = [super::MILLIS_MAX as u32, super::MICROS_MAX, super::NANOS_MAX, 0];
 
-    match prefix {
-        FRAC_SECOND_FIXED_WIDTH_MILLI => FractionalSecond::Milliseconds(value as u16),
-        FRAC_SECOND_FIXED_WIDTH_MICRO => FractionalSecond::Microseconds(value),
-        FRAC_SECOND_FIXED_WIDTH_NANO => FractionalSecond::Nanoseconds(value),
-        FRAC_SECOND_FIXED_WIDTH_NONE => FractionalSecond::None,
-        _ => panic!("Corrupt fixed width encoded fractional second")
+/// The fractional second in nanoseconds, with `None` as zero.
+pub fn nanos(f: &FractionalSecond) -> u32 {
+    match *f {
+        FractionalSecond::Milliseconds(x) => (x as u32) * 1_000_000,
+        FractionalSecond::Microseconds(x) => x * 1_000,
+        FractionalSecond::Nanoseconds(x) => x,
+        FractionalSecond::None => 0
     }
 }
 
-// 2-bit indicators for fixed-width encoding of fractional seconds
-pub const FRAC_SECOND_FIXED_WIDTH_NONE: u32 = 0x00_00_00_00;
-pub const FRAC_SECOND_FIXED_WIDTH_MILLI: u32 = 0x40_00_00_00;
-pub const FRAC_SECOND_FIXED_WIDTH_MICRO: u32 = 0x80_00_00_00;
-pub const FRAC_SECOND_FIXED_WIDTH_NANO: u32 = 0xC0_00_00_00;
-pub const FRAC_SECOND_FIXED_WIDTH_PREFIX_MASK: u32 = 0xC0_00_00_00;
-pub const FRAC_SECOND_FIXED_WIDTH_VALUE_MASK: u32 = !FRAC_SECOND_FIXED_WIDTH_PREFIX_MASK;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,29 +62,30 @@ mod tests {
 
 
     #[test]
-    fn fixed_width_roundtrip_millis() {
+    fn encoded_roundtrip_millis() {
         roundtrip(FractionalSecond::Milliseconds(MILLIS_MIN));
         roundtrip(FractionalSecond::Milliseconds(MILLIS_MIN));
     }
 
     #[test]
-    fn fixed_width_roundtrip_micros() {
+    fn encoded_roundtrip_micros() {
         roundtrip(FractionalSecond::Microseconds(MICROS_MIN));
         roundtrip(FractionalSecond::Microseconds(MICROS_MAX));
     }
 
     #[test]
-    fn fixed_width_roundtrip_nanos() {
+    fn encoded_roundtrip_nanos() {
         roundtrip(FractionalSecond::Nanoseconds(NANOS_MIN));
         roundtrip(FractionalSecond::Nanoseconds(NANOS_MAX));
     }
 
     #[test]
-    fn fixed_width_roundtrip_none() {
+    fn encoded_roundtrip_none() {
         roundtrip(FractionalSecond::None);
     }
 
     fn roundtrip(f: FractionalSecond) {
-        assert_eq!(f, decode_fixed_width(encode_fixed_width(&f)));
+        assert_eq!(f, decode(precision(&f), encoded_value(&f)));
+        assert!(encoded_value(&f) < 1 << ENCODED_BITS[precision(&f)]);
     }
 }
@@ -0,0 +1,154 @@
+use super::*;
+
+/// Partial and low-precision values as the span of time they cover: a `DateOnly` without a day
+/// covers a whole month, and a `DateTimeSubSecond` with a fractional second of
+/// `Milliseconds(123)` covers a millisecond. Implemented for all types with a date.
+///
+/// Only trailing fields may be missing, since a value like "the 15th of every month of 2017"
+/// isn't a single span of time, and the year must be present.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let march = DateOnly::new(Some(2017), Some(3), None).unwrap();
+/// assert_eq!(DateOnly::new(Some(2017), Some(3), Some(31)).unwrap(), march.latest().unwrap());
+///
+/// let event = DateTime::new(Some(2017), Some(3), Some(15), Some(18), Some(45), Some(0)).unwrap();
+/// assert!(march.contains(&event).unwrap());
+/// ```
+pub trait Interval: Temporal {
+    /// The earliest complete value this one covers, with missing fields at the start of their
+    /// range. The fractional second, for types that have one, is in nanoseconds.
+    ///
+    /// Returns an error if the year is missing, if a field is present after a missing one (like
+    /// a day without a month), or if the date doesn't exist.
+    fn earliest(&self) -> Result<Self, ConversionError> {
+        let (earliest, _) = bounds(&self.fields())?;
+
+        Self::from_fields(for_type::<Self>(earliest)).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `earliest()`, but the latest complete value this one covers. A missing second is
+    /// 59, since only a few minutes have a leap second.
+    fn latest(&self) -> Result<Self, ConversionError> {
+        let (_, latest) = bounds(&self.fields())?;
+
+        Self::from_fields(for_type::<Self>(latest)).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// True if all of the time `other` covers is also covered by this value.
+    ///
+    /// Values with UTC offsets are compared as instants, and values without an offset as local
+    /// times. Returns an error as for `earliest()`, or `UnknownOffset` if only one of them has an
+    /// offset or either offset is `SpecifiedElsewhere`.
+    fn contains<T: Interval>(&self, other: &T) -> Result<bool, ConversionError> {
+        let ((start, end), (other_start, other_end)) = comparable(&self.fields(),
+                                                                  &other.fields())?;
+
+        Ok(start <= other_start && other_end <= end)
+    }
+
+    /// Like `contains()`, but true if any of the time `other` covers is also covered by this
+    /// value.
+    fn overlaps<T: Interval>(&self, other: &T) -> Result<bool, ConversionError> {
+        let ((start, end), (other_start, other_end)) = comparable(&self.fields(),
+                                                                  &other.fields())?;
+
+        Ok(start <= other_end && other_start <= end)
+    }
+}
+
+impl Interval for DateOnly {}
+impl Interval for DateTime {}
+impl Interval for DateTimeOffset {}
+impl Interval for DateTimeSubSecond {}
+impl Interval for DateTimeSubSecondOffset {}
+
+/// The earliest and latest values covered by `fields`, with every field present and the
+/// fractional second in nanoseconds. The offset is unchanged.
+fn bounds(fields: &TemporalFields) -> Result<(TemporalFields, TemporalFields), ConversionError> {
+    let year = fields.year.ok_or(ConversionError::MissingField)?;
+
+    let present = [fields.month.is_some(), fields.day.is_some(), fields.hour.is_some(),
+                   fields.minute.is_some(), fields.second.is_some(), fields.has_sub_second()];
+    if let Some(first_missing) = present.iter().position(|p| !p) {
+        if present[first_missing..].iter().any(|&p| p) {
+            return Err(ConversionError::MissingField);
+        }
+    }
+
+    let earliest = TemporalFields {
+        year: Some(year),
+        month: Some(fields.month.unwrap_or(MONTH_MIN)),
+        day: Some(fields.day.unwrap_or(DAY_MIN)),
+        hour: Some(fields.hour.unwrap_or(HOUR_MIN)),
+        minute: Some(fields.minute.unwrap_or(MINUTE_MIN)),
+        second: Some(fields.second.unwrap_or(SECOND_MIN)),
+        frac_second: FractionalSecond::Nanoseconds(frac_second::nanos(&fields.frac_second)),
+        offset: fields.offset,
+    };
+    // rejects days that don't exist, like February 30
+    calendar::fields_to_days(&earliest)?;
+
+    let last_month = fields.month.unwrap_or(MONTH_MAX);
+    let last_nanos = match fields.frac_second {
+        FractionalSecond::Milliseconds(ms) => (ms as u32) * 1_000_000 + 999_999,
+        FractionalSecond::Microseconds(us) => us * 1_000 + 999,
+        FractionalSecond::Nanoseconds(ns) => ns,
+        FractionalSecond::None => NANOS_MAX
+    };
+    let latest = TemporalFields {
+        month: Some(last_month),
+        day: Some(fields.day.unwrap_or_else(|| calendar::days_in_month(year as i64, last_month))),
+        hour: Some(fields.hour.unwrap_or(HOUR_MAX)),
+        minute: Some(fields.minute.unwrap_or(MINUTE_MAX)),
+        second: Some(fields.second.unwrap_or(SECOND_MAX - 1)),
+        frac_second: FractionalSecond::Nanoseconds(last_nanos),
+        ..earliest
+    };
+
+    Ok((earliest, latest))
+}
+
+/// Keep only the fields of the components `T` has.
+fn for_type<T: Temporal>(fields: TemporalFields) -> TemporalFields {
+    TemporalFields {
+        hour: fields.hour.filter(|_| T::has_time()),
+        minute: fields.minute.filter(|_| T::has_time()),
+        second: fields.second.filter(|_| T::has_time()),
+        frac_second: if T::has_sub_second() { fields.frac_second } else { FractionalSecond::None },
+        ..fields
+    }
+}
+
+/// The first and last nanosecond covered by a value
+type Span = (i128, i128);
+
+/// The spans covered by `a` and by `b`, at UTC if they have offsets.
+fn comparable(a: &TemporalFields, b: &TemporalFields) -> Result<(Span, Span), ConversionError> {
+    let (a_start, a_end, a_offset) = nanos_bounds(a)?;
+    let (b_start, b_end, b_offset) = nanos_bounds(b)?;
+
+    if a_offset != b_offset {
+        return Err(ConversionError::UnknownOffset);
+    }
+
+    Ok(((a_start, a_end), (b_start, b_end)))
+}
+
+/// The first and last nanosecond since the epoch covered by `fields`, and whether they are at
+/// UTC rather than local time.
+fn nanos_bounds(fields: &TemporalFields) -> Result<(i128, i128, bool), ConversionError> {
+    let (earliest, latest) = bounds(fields)?;
+
+    let offset = match fields.offset {
+        OffsetValue::UtcOffset(o) => Some(o),
+        OffsetValue::None => None,
+        OffsetValue::SpecifiedElsewhere => return Err(ConversionError::UnknownOffset)
+    };
+    let shift = offset.unwrap_or(0) as i128 * NANOS_PER_MINUTE;
+
+    Ok((calendar_unit::naive_nanos(&earliest)? - shift,
+        calendar_unit::naive_nanos(&latest)? - shift,
+        offset.is_some()))
+}
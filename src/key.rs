@@ -0,0 +1,413 @@
+//! Keys for ordered key-value stores, whose byte order is the order of the values.
+
+use std::cmp::Ordering;
+use std::io::{Read, Write};
+
+use super::*;
+
+/// Where missing fields sort in keys, relative to the values the field can have.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MissingFields {
+    /// Before every value, so that a month without a day sorts before the first day of that
+    /// month. A `None` offset sorts before `SpecifiedElsewhere`, which sorts before UTC offsets.
+    First,
+    /// After every value, as in the Temporenc encoding. A `None` offset sorts after
+    /// `SpecifiedElsewhere`, which sorts after UTC offsets.
+    Last,
+}
+
+/// Fixed-size keys for ordered key-value stores like RocksDB or sled, where comparing keys
+/// bytewise gives the same order as comparing the values they encode.
+///
+/// Keys are ordered by year, month, day, hour, minute, second, fractional second and offset, in
+/// that order, with missing fields placed as described by `MissingFields`. The fractional
+/// second is ordered by its value regardless of precision, so `Milliseconds(123)` sorts between
+/// `Microseconds(122999)` and `Microseconds(123001)`. When two have the same value, milliseconds
+/// sort before microseconds, which sort before nanoseconds, so that every value has a distinct
+/// key.
+///
+/// Offsets are compared after the other fields, so values with different offsets are ordered
+/// by their local time. Convert them to a common offset (as with `to_utc()`) first to order them
+/// by instant.
+///
+/// Keys don't include the type, so keys of different types should not be mixed.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let millis = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+///     Some(30), FractionalSecond::Milliseconds(124)).unwrap();
+/// let nanos = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+///     Some(30), FractionalSecond::Nanoseconds(123_999_999)).unwrap();
+///
+/// let mut millis_key = Vec::new();
+/// millis.serialize_key(MissingFields::Last, &mut millis_key).unwrap();
+/// let mut nanos_key = Vec::new();
+/// nanos.serialize_key(MissingFields::Last, &mut nanos_key).unwrap();
+///
+/// assert_eq!(DateTimeSubSecond::key_size(), millis_key.len());
+/// assert!(nanos_key < millis_key);
+/// ```
+pub trait OrderedKey: Temporal {
+    /// The size of every key for this type.
+    fn key_size() -> usize {
+        (key_bits::<Self>() as usize + 7) / 8
+    }
+
+    /// Serialize as a key into the provided writer. Returns the number of bytes written, which
+    /// is always `key_size()`.
+    fn serialize_key<W: Write>(&self, missing: MissingFields, writer: &mut W)
+                               -> Result<usize, SerializationError> {
+        let size = Self::key_size();
+        let padding = size * 8 - key_bits::<Self>() as usize;
+        let bits = pack::<Self>(&self.fields(), missing) << padding;
+
+        write_array_map_err(&bits.to_be_bytes()[(16 - size)..], writer)
+            .map_err(|_| SerializationError::IoError)
+    }
+
+    /// Deserialize a key written by `serialize_key()` with the same `missing`.
+    fn deserialize_key<R: Read>(reader: &mut R, missing: MissingFields)
+                                -> Result<Self, DeserializationError> {
+        let size = Self::key_size();
+        let mut buf = [0; 16];
+        read_exact(reader, &mut buf[(16 - size)..])?;
+
+        let padding = size * 8 - key_bits::<Self>() as usize;
+        let bits = u128::from_be_bytes(buf);
+        if bits & ((1 << padding) - 1) != 0 {
+            return Err(DeserializationError::InvalidFieldValue);
+        }
+
+        let fields = unpack::<Self>(bits >> padding, missing)?;
+
+        Self::from_fields(fields).map_err(|_| DeserializationError::InvalidFieldValue)
+    }
+}
+
+impl OrderedKey for DateOnly {}
+impl OrderedKey for TimeOnly {}
+impl OrderedKey for DateTime {}
+impl OrderedKey for DateTimeOffset {}
+impl OrderedKey for DateTimeSubSecond {}
+impl OrderedKey for DateTimeSubSecondOffset {}
+
+/// Compare two values in the Temporenc encoding in the order described by `OrderedKey` with
+/// `MissingFields::Last`, which for types other than `DateTimeSubSecond` and
+/// `DateTimeSubSecondOffset` is the same as comparing the encoded bytes. Those two types put the
+/// precision before the other fields and have a different size for each precision, so their
+/// encoded bytes don't sort in order.
+///
+/// Any bytes after the values, as in composite keys, are compared bytewise when the values are
+/// equal, so this can be used as the comparator of a key-value store.
+///
+/// Returns an error if either value can't be deserialized, or if they are of different types.
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use temporenc::*;
+///
+/// let mut millis = Vec::new();
+/// DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+///     FractionalSecond::Milliseconds(124)).unwrap().serialize(&mut millis).unwrap();
+/// let mut micros = Vec::new();
+/// DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+///     FractionalSecond::Microseconds(123_456)).unwrap().serialize(&mut micros).unwrap();
+///
+/// // the millisecond value's encoded bytes sort first, because of the precision tag
+/// assert!(millis < micros);
+/// assert_eq!(Ordering::Greater, compare_encoded(&millis, &micros).unwrap());
+/// ```
+pub fn compare_encoded(a: &[u8], b: &[u8]) -> Result<Ordering, DeserializationError> {
+    let type_tag = encoded_type(a)?;
+    if encoded_type(b)? != type_tag {
+        return Err(DeserializationError::IncorrectTypeTag);
+    }
+
+    let (a_bits, a_rest) = encoded_bits(type_tag, a)?;
+    let (b_bits, b_rest) = encoded_bits(type_tag, b)?;
+
+    Ok(a_bits.cmp(&b_bits).then_with(|| a_rest.cmp(b_rest)))
+}
+
+/// The type of the encoded value at the start of `bytes`, from its type tag.
+fn encoded_type(bytes: &[u8]) -> Result<TypeTag, DeserializationError> {
+    let byte0 = *bytes.first().ok_or(DeserializationError::IoError)?;
+
+    if byte0 & 0b1100_0000 == DATE_TIME_TAG {
+        Ok(TypeTag::DateTime)
+    } else if byte0 & 0b1100_0000 == DATE_TIME_SUBSECOND_TAG {
+        Ok(TypeTag::DateTimeSubSecond)
+    } else if byte0 & 0b1110_0000 == DATE_TAG {
+        Ok(TypeTag::DateOnly)
+    } else if byte0 & 0b1111_1110 == TIME_TAG {
+        Ok(TypeTag::TimeOnly)
+    } else if byte0 & 0b1110_0000 == DATE_TIME_OFFSET_TAG {
+        Ok(TypeTag::DateTimeOffset)
+    } else if byte0 & 0b1110_0000 == DATE_TIME_SUBSECOND_OFFSET_TAG {
+        Ok(TypeTag::DateTimeSubSecondOffset)
+    } else {
+        Err(DeserializationError::IncorrectTypeTag)
+    }
+}
+
+/// The packed key of the encoded value at the start of `bytes`, and the bytes after it.
+fn encoded_bits(type_tag: TypeTag, bytes: &[u8]) -> Result<(u128, &[u8]), DeserializationError> {
+    fn bits_of<T: Temporal>(mut bytes: &[u8]) -> Result<(u128, &[u8]), DeserializationError> {
+        let value = T::deserialize(&mut bytes)?;
+        Ok((pack::<T>(&value.fields(), MissingFields::Last), bytes))
+    }
+
+    match type_tag {
+        TypeTag::DateOnly => bits_of::<DateOnly>(bytes),
+        TypeTag::TimeOnly => bits_of::<TimeOnly>(bytes),
+        TypeTag::DateTime => bits_of::<DateTime>(bytes),
+        TypeTag::DateTimeOffset => bits_of::<DateTimeOffset>(bytes),
+        TypeTag::DateTimeSubSecond => bits_of::<DateTimeSubSecond>(bytes),
+        TypeTag::DateTimeSubSecondOffset => bits_of::<DateTimeSubSecondOffset>(bytes),
+    }
+}
+
+/// The number of bits in a packed key for `T`.
+pub fn key_bits<T: Temporal>() -> u32 {
+    let mut bits = 0;
+
+    if T::has_date() {
+        bits += YEAR_BITS + MONTH_BITS + DAY_BITS;
+    }
+    if T::has_time() {
+        bits += HOUR_BITS + MINUTE_BITS + SECOND_BITS;
+    }
+    if T::has_sub_second() {
+        bits += FRAC_SECOND_BITS;
+    }
+    if T::has_offset() {
+        bits += OFFSET_BITS;
+    }
+
+    bits
+}
+
+/// The number of bits at the end of a packed key for `T` taken by the fields missing from
+/// `fields`. Returns an error if a field is present after a missing one.
+pub fn missing_bits<T: Temporal>(fields: &TemporalFields) -> Result<u32, ConversionError> {
+    let mut bits = 0;
+    let mut gap = false;
+    {
+        // every width is nonzero, so any bits so far mean an earlier field is missing
+        let mut push = |present: bool, width: u32| if present {
+            gap |= bits > 0;
+        } else {
+            bits += width;
+        };
+
+        if T::has_date() {
+            push(fields.year.is_some(), YEAR_BITS);
+            push(fields.month.is_some(), MONTH_BITS);
+            push(fields.day.is_some(), DAY_BITS);
+        }
+        if T::has_time() {
+            push(fields.hour.is_some(), HOUR_BITS);
+            push(fields.minute.is_some(), MINUTE_BITS);
+            push(fields.second.is_some(), SECOND_BITS);
+        }
+        if T::has_sub_second() {
+            push(fields.has_sub_second(), FRAC_SECOND_BITS);
+        }
+        if T::has_offset() {
+            push(fields.offset != OffsetValue::None, OFFSET_BITS);
+        }
+    }
+
+    if gap {
+        return Err(ConversionError::MissingField);
+    }
+
+    Ok(bits)
+}
+
+/// The fields of the components `T` has, packed big-endian into the low `key_bits::<T>()` bits.
+pub fn pack<T: Temporal>(fields: &TemporalFields, missing: MissingFields) -> u128 {
+    let mut bits: u128 = 0;
+    {
+        let mut push = |key: u32, width: u32| bits = (bits << width) | key as u128;
+
+        if T::has_date() {
+            push(field_key(fields.year.map(u32::from), YEAR_RAW_NONE as u32, missing),
+                 YEAR_BITS);
+            push(field_key(fields.month.map(|m| m as u32 - 1), MONTH_RAW_NONE as u32, missing),
+                 MONTH_BITS);
+            push(field_key(fields.day.map(|d| d as u32 - 1), DAY_RAW_NONE as u32, missing),
+                 DAY_BITS);
+        }
+        if T::has_time() {
+            push(field_key(fields.hour.map(u32::from), HOUR_RAW_NONE as u32, missing), HOUR_BITS);
+            push(field_key(fields.minute.map(u32::from), MINUTE_RAW_NONE as u32, missing),
+                 MINUTE_BITS);
+            push(field_key(fields.second.map(u32::from), SECOND_RAW_NONE as u32, missing),
+                 SECOND_BITS);
+        }
+        if T::has_sub_second() {
+            push(frac_second_key(fields.frac_second, missing), FRAC_SECOND_BITS);
+        }
+        if T::has_offset() {
+            push(offset_key(fields.offset, missing), OFFSET_BITS);
+        }
+    }
+
+    bits
+}
+
+/// The inverse of `pack()`. The fields are not checked to be in range.
+pub fn unpack<T: Temporal>(bits: u128, missing: MissingFields)
+                           -> Result<TemporalFields, DeserializationError> {
+    let mut remaining = key_bits::<T>();
+    let mut pop = |width: u32| {
+        remaining -= width;
+        ((bits >> remaining) & ((1 << width) - 1)) as u32
+    };
+    let mut fields = TemporalFields::default();
+
+    if T::has_date() {
+        fields.year = field_value(pop(YEAR_BITS), YEAR_RAW_NONE as u32, missing)
+            .map(|y| y as u16);
+        fields.month = field_value(pop(MONTH_BITS), MONTH_RAW_NONE as u32, missing)
+            .map(|m| m as u8 + 1);
+        fields.day = field_value(pop(DAY_BITS), DAY_RAW_NONE as u32, missing)
+            .map(|d| d as u8 + 1);
+    }
+    if T::has_time() {
+        fields.hour = field_value(pop(HOUR_BITS), HOUR_RAW_NONE as u32, missing)
+            .map(|h| h as u8);
+        fields.minute = field_value(pop(MINUTE_BITS), MINUTE_RAW_NONE as u32, missing)
+            .map(|m| m as u8);
+        fields.second = field_value(pop(SECOND_BITS), SECOND_RAW_NONE as u32, missing)
+            .map(|s| s as u8);
+    }
+    if T::has_sub_second() {
+        fields.frac_second = frac_second_value(pop(FRAC_SECOND_BITS), missing)?;
+    }
+    if T::has_offset() {
+        fields.offset = offset_value(pop(OFFSET_BITS), missing)?;
+    }
+
+    Ok(fields)
+}
+
+/// A value packed into an integer, as in `to_packed_u64()`: the key bits of
+/// `OrderedKey::serialize_key()` with `MissingFields::Last`, without padding. Integers compare
+/// in the same order as the keys, and every value has exactly one packed form.
+pub fn to_packed<T: Temporal>(value: &T) -> u128 {
+    pack::<T>(&value.fields(), MissingFields::Last)
+}
+
+/// The inverse of `to_packed()`. Returns an error if bits above `key_bits::<T>()` are set or a
+/// field is out of range.
+pub fn from_packed<T: Temporal>(packed: u128) -> Result<T, DeserializationError> {
+    if packed >> key_bits::<T>() != 0 {
+        return Err(DeserializationError::InvalidFieldValue);
+    }
+
+    let fields = unpack::<T>(packed, MissingFields::Last)?;
+
+    T::from_fields(fields).map_err(|_| DeserializationError::InvalidFieldValue)
+}
+
+/// `value` is the field as it is encoded (like the month minus one), and `none` is how a missing
+/// field is encoded, which is the largest value that fits.
+fn field_key(value: Option<u32>, none: u32, missing: MissingFields) -> u32 {
+    match (value, missing) {
+        (Some(v), MissingFields::Last) => v,
+        (Some(v), MissingFields::First) => v + 1,
+        (None, MissingFields::Last) => none,
+        (None, MissingFields::First) => 0,
+    }
+}
+
+fn field_value(key: u32, none: u32, missing: MissingFields) -> Option<u32> {
+    match missing {
+        MissingFields::Last if key == none => None,
+        MissingFields::Last => Some(key),
+        MissingFields::First if key == 0 => None,
+        MissingFields::First => Some(key - 1),
+    }
+}
+
+/// The value in nanoseconds plus one, followed by 2 bits for the precision, so that every
+/// precision of the same value has a distinct key.
+fn frac_second_key(frac_second: FractionalSecond, missing: MissingFields) -> u32 {
+    let precision = match frac_second {
+        FractionalSecond::Milliseconds(_) => 0,
+        FractionalSecond::Microseconds(_) => 1,
+        FractionalSecond::Nanoseconds(_) => 2,
+        FractionalSecond::None => {
+            return match missing {
+                MissingFields::First => 0,
+                MissingFields::Last => !0,
+            };
+        }
+    };
+
+    ((frac_second::nanos(&frac_second) + 1) << 2) | precision
+}
+
+fn frac_second_value(key: u32, missing: MissingFields)
+                     -> Result<FractionalSecond, DeserializationError> {
+    let none = match missing {
+        MissingFields::First => 0,
+        MissingFields::Last => !0,
+    };
+    if key == none {
+        return Ok(FractionalSecond::None);
+    }
+
+    let nanos = (key >> 2).checked_sub(1).ok_or(DeserializationError::InvalidFieldValue)?;
+    match key & 0b11 {
+        0 if nanos % 1_000_000 == 0 => {
+            Ok(FractionalSecond::Milliseconds((nanos / 1_000_000) as u16))
+        },
+        1 if nanos % 1_000 == 0 => Ok(FractionalSecond::Microseconds(nanos / 1_000)),
+        2 => Ok(FractionalSecond::Nanoseconds(nanos)),
+        _ => Err(DeserializationError::InvalidFieldValue)
+    }
+}
+
+fn offset_key(offset: OffsetValue, missing: MissingFields) -> u32 {
+    let raw = offset_num(offset).unwrap_or(OFFSET_RAW_NONE) as u32;
+
+    match (offset, missing) {
+        (_, MissingFields::Last) => raw,
+        (OffsetValue::None, MissingFields::First) => 0,
+        (OffsetValue::SpecifiedElsewhere, MissingFields::First) => 1,
+        (OffsetValue::UtcOffset(_), MissingFields::First) => raw + 2,
+    }
+}
+
+fn offset_value(key: u32, missing: MissingFields) -> Result<OffsetValue, DeserializationError> {
+    let raw = match missing {
+        MissingFields::Last => key,
+        MissingFields::First => match key {
+            0 => OFFSET_RAW_NONE as u32,
+            1 => OFFSET_RAW_ELSEWHERE as u32,
+            k => k - 2
+        }
+    };
+
+    match raw as u8 {
+        OFFSET_RAW_NONE => Ok(OffsetValue::None),
+        OFFSET_RAW_ELSEWHERE => Ok(OffsetValue::SpecifiedElsewhere),
+        r if r <= OFFSET_RAW_MAX => Ok(OffsetValue::UtcOffset(((r as i16) - 64) * 15)),
+        _ => Err(DeserializationError::InvalidFieldValue)
+    }
+}
+
+const YEAR_BITS: u32 = 12;
+const MONTH_BITS: u32 = 4;
+const DAY_BITS: u32 = 5;
+const HOUR_BITS: u32 = 5;
+const MINUTE_BITS: u32 = 6;
+const SECOND_BITS: u32 = 6;
+const FRAC_SECOND_BITS: u32 = 32;
+const OFFSET_BITS: u32 = 7;
+// the largest encoded UTC offset
+const OFFSET_RAW_MAX: u8 = 125;
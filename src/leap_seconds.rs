@@ -0,0 +1,145 @@
+//! The leap seconds inserted into UTC so far, for validating second 60 and for converting to
+//! time scales without leap seconds.
+
+use super::*;
+
+/// How to treat second 60 when converting to a time scale without leap seconds, like Unix time.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LeapSecondMode {
+    /// Accept second 60 at any time, and map all of it to the last nanosecond of second 59.
+    Clamp,
+    /// Like `Clamp`, but return an error unless second 60 is at a real leap second.
+    Strict,
+    /// Spread each leap second over the 24 hours from noon to noon UTC around it, so that each
+    /// Unix second in that window lasts 86401/86400 SI seconds, as some NTP servers do.
+    /// Like `Strict`, second 60 must be at a real leap second.
+    Smear,
+}
+
+/// True if the UTC minute in `fields` ends with a leap second. Year, month, day, hour, and minute
+/// must be present.
+pub fn is_leap_second_minute(fields: &TemporalFields) -> bool {
+    match (fields.year, fields.month, fields.day, fields.hour, fields.minute) {
+        (Some(year), Some(month), Some(day), Some(23), Some(59)) => {
+            day == calendar::days_in_month(year as i64, month)
+                && LEAP_SECONDS.iter().any(|&(y, m)| y == year && m == month)
+        },
+        _ => false
+    }
+}
+
+/// Returns an error if `fields` has second 60 anywhere other than at a leap second, after
+/// converting to UTC.
+pub fn check_leap_second(fields: &TemporalFields) -> Result<(), ConversionError> {
+    if fields.second != Some(60) {
+        return Ok(());
+    }
+
+    if is_leap_second_minute(&calendar::change_offset(fields, 0)?) {
+        Ok(())
+    } else {
+        Err(ConversionError::InvalidLeapSecond)
+    }
+}
+
+/// TAI - UTC in seconds at `unix_seconds` seconds after 1970-01-01T00:00:00Z, not counting leap
+/// seconds.
+///
+/// Returns an error before 1972, when UTC was not yet a whole number of seconds from TAI.
+pub fn tai_minus_utc(unix_seconds: i64) -> Result<i64, ConversionError> {
+    if unix_seconds < UTC_1972 {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    Ok(TAI_MINUS_UTC_1972
+        + (0..LEAP_SECONDS.len()).filter(|&i| after_leap_second(i) <= unix_seconds).count() as i64)
+}
+
+/// The inverse of `tai_minus_utc()`: the Unix time for `tai_seconds` seconds after
+/// 1970-01-01T00:00:00 TAI, and whether that second is a leap second, in which case the Unix time
+/// is that of the second before.
+pub fn utc_from_tai(tai_seconds: i64) -> Result<(i64, bool), ConversionError> {
+    let mut offset = TAI_MINUS_UTC_1972;
+    if tai_seconds < UTC_1972 + offset {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    for i in 0..LEAP_SECONDS.len() {
+        // the leap second is the TAI second just before `after_leap_second(i) + offset + 1`
+        let leap = after_leap_second(i) + offset;
+        if tai_seconds < leap {
+            break;
+        } else if tai_seconds == leap {
+            return Ok((leap - offset - 1, true));
+        }
+        offset += 1;
+    }
+
+    Ok((tai_seconds - offset, false))
+}
+
+/// Unix time of the first second after a leap second whose smear window includes
+/// `unix_seconds`, if there is one.
+pub fn smear_window(unix_seconds: i64) -> Option<i64> {
+    (0..LEAP_SECONDS.len())
+        .map(after_leap_second)
+        .find(|&after| unix_seconds >= after - SMEAR_HALF_WINDOW
+            && unix_seconds < after + SMEAR_HALF_WINDOW)
+}
+
+/// Half of the 24 hour smear window, in seconds
+pub const SMEAR_HALF_WINDOW: i64 = 12 * 3600;
+
+/// Unix time of the first second after the leap second at `LEAP_SECONDS[index]`
+fn after_leap_second(index: usize) -> i64 {
+    let (year, month) = LEAP_SECONDS[index];
+    let last_day = calendar::days_from_civil(year as i64, month,
+                                             calendar::days_in_month(year as i64, month));
+
+    (last_day + 1) * calendar::SECONDS_PER_DAY
+}
+
+/// 1972-01-01T00:00:00Z, when UTC started using leap seconds
+const UTC_1972: i64 = 63_072_000;
+const TAI_MINUS_UTC_1972: i64 = 10;
+
+/// The months at the end of which a leap second was inserted, as 23:59:60 UTC on the last day.
+/// Every leap second so far has been positive, and there have been none since the end of 2016.
+const LEAP_SECONDS: [(u16, u8); 27] = [
+    (1972, 6), (1972, 12), (1973, 12), (1974, 12), (1975, 12), (1976, 12), (1977, 12),
+    (1978, 12), (1979, 12), (1981, 6), (1982, 6), (1983, 6), (1985, 6), (1987, 12), (1989, 12),
+    (1990, 12), (1992, 6), (1993, 6), (1994, 6), (1995, 12), (1997, 6), (1998, 12), (2005, 12),
+    (2008, 12), (2012, 6), (2015, 6), (2016, 12),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_ordered_june_and_december() {
+        assert!(LEAP_SECONDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(LEAP_SECONDS.iter().all(|&(_, m)| m == 6 || m == 12));
+    }
+
+    #[test]
+    fn tai_minus_utc_known_values() {
+        assert_eq!(Err(ConversionError::OutOfRange), tai_minus_utc(UTC_1972 - 1));
+        assert_eq!(Ok(10), tai_minus_utc(UTC_1972));
+        // 2017-01-01T00:00:00Z
+        assert_eq!(Ok(36), tai_minus_utc(1_483_228_799));
+        assert_eq!(Ok(37), tai_minus_utc(1_483_228_800));
+    }
+
+    #[test]
+    fn utc_from_tai_inverse() {
+        for i in 0..LEAP_SECONDS.len() {
+            let after = after_leap_second(i);
+            let tai_after = after + tai_minus_utc(after).unwrap();
+
+            assert_eq!(Ok((after, false)), utc_from_tai(tai_after));
+            assert_eq!(Ok((after - 1, true)), utc_from_tai(tai_after - 1));
+            assert_eq!(Ok((after - 1, false)), utc_from_tai(tai_after - 2));
+        }
+    }
+}
@@ -11,7 +11,9 @@
 //! the accessors expose `Option<T>` or an enum with a `None` variant.
 //!
 //! All of the structs implement `Serializable` and `Deserializable` which, surprisingly enough,
-//! provide methods related to serialization and deserialization.
+//! provide methods related to serialization and deserialization. They also implement `Temporal`,
+//! which exposes all of a value's fields at once as `TemporalFields` for code that needs to work
+//! with any of the types.
 //!
 //! ```
 //! use temporenc::*;
@@ -66,13 +68,113 @@ pub trait Serializable {
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError>;
 }
 
+/// An object-safe counterpart to `Serializable`, so that values of different types can be
+/// serialized via `&dyn DynSerializable` or `Box<dyn DynSerializable>`. Implemented for every
+/// `Serializable` type.
+pub trait DynSerializable {
+    /// The encoded size of this instance. The same as `Serializable::serialized_size()`.
+    fn encoded_len(&self) -> usize;
+    /// Serialize into the provided writer with the Temporenc format. Returns the number of bytes
+    /// written, which will be the same as `encoded_len()`.
+    fn serialize_dyn(&self, writer: &mut dyn Write) -> Result<usize, SerializationError>;
+}
+
+impl<T: Serializable> DynSerializable for T {
+    fn encoded_len(&self) -> usize {
+        self.serialized_size()
+    }
+
+    fn serialize_dyn(&self, mut writer: &mut dyn Write) -> Result<usize, SerializationError> {
+        self.serialize(&mut writer)
+    }
+}
+
 /// Deserialize from the Temporenc binary format.
 pub trait Deserializable: Sized {
     /// Deserialize from the provided reader with the Temporenc format.
     fn deserialize<R: Read>(reader: &mut R) -> Result<Self, DeserializationError>;
+
+    /// Deserialize from the start of `bytes`, returning the value and the number of bytes used.
+    /// Errors are the same as for `deserialize()`, with `IoError` if `bytes` is too short.
+    ///
+    /// The Temporenc types are much faster here than via `deserialize()` when `bytes` extends
+    /// far enough past the value for a whole 4, 8, or 16 byte load (whichever is the smallest
+    /// that covers `max_serialized_size()`), as in a buffer holding many values.
+    fn deserialize_from_slice(bytes: &[u8]) -> Result<(Self, usize), DeserializationError> {
+        deserialize_prefix(bytes)
+    }
+}
+
+/// Implemented by all of the Temporenc types so that code can be written once for any of them.
+///
+/// Fields are accessed all at once via `fields()`, where the fields of components that the type
+/// doesn't have are simply absent.
+pub trait Temporal: Serializable + Deserializable {
+    /// The tag identifying this type.
+    fn type_tag() -> TypeTag;
+    /// The smallest encoded size of any instance of the type. The same as `max_serialized_size()`
+    /// for types that don't have variable precision.
+    fn min_serialized_size() -> usize;
+    /// True if the type has the Date component.
+    fn has_date() -> bool;
+    /// True if the type has the Time component.
+    fn has_time() -> bool;
+    /// True if the type has the Sub-second precision component.
+    fn has_sub_second() -> bool;
+    /// True if the type has the UTC Offset component.
+    fn has_offset() -> bool;
+    /// All of the fields, with those in components the type doesn't have reported as absent.
+    fn fields(&self) -> TemporalFields;
+    /// Returns an error if any of the fields have invalid values, like a month of 18, or if a
+    /// field is present in a component that the type doesn't have.
+    fn from_fields(fields: TemporalFields) -> Result<Self, CreationError>;
+
+    /// True if every field of the type's components is present. `OffsetValue::SpecifiedElsewhere`
+    /// counts as present.
+    fn is_complete(&self) -> bool {
+        let fields = self.fields();
+
+        (!Self::has_date()
+            || (fields.year.is_some() && fields.month.is_some() && fields.day.is_some()))
+            && (!Self::has_time()
+                || (fields.hour.is_some() && fields.minute.is_some() && fields.second.is_some()))
+            && (!Self::has_sub_second() || fields.has_sub_second())
+            && (!Self::has_offset() || fields.has_offset())
+    }
+
+    /// True if `concrete` is one of the values this one describes when its missing fields are
+    /// treated as wildcards, as described by `TemporalFields::matches()`. A `DateOnly` without a
+    /// year matches that day of every year, and a `TimeOnly` without a second matches every
+    /// second of that minute.
+    ///
+    /// `concrete` is normally complete, and is of the same type or one with more components,
+    /// like a `DateTime` for a `DateOnly`. Returns false if its type is missing any of the
+    /// components of this type.
+    ///
+    /// ```
+    /// use temporenc::*;
+    ///
+    /// let every_jan_15 = DateOnly::new(None, Some(1), Some(15)).unwrap();
+    /// let dt = DateTime::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(0)).unwrap();
+    /// assert!(every_jan_15.matches(&dt));
+    /// ```
+    fn matches<T: Temporal>(&self, concrete: &T) -> bool {
+        if (Self::has_date() && !T::has_date())
+            || (Self::has_time() && !T::has_time())
+            || (Self::has_sub_second() && !T::has_sub_second())
+            || (Self::has_offset() && !T::has_offset()) {
+            return false;
+        }
+
+        self.fields().matches(&concrete.fields())
+    }
 }
 
 /// Represents the Temporenc "Date" component.
+///
+/// Besides the fields, facts derived from them with the proleptic Gregorian calendar are
+/// available. They are `None` if a field they need is missing, or if the date doesn't exist, like
+/// February 30.
 pub trait Date {
     /// If present, the year. In range [0, 4094].
     fn year(&self) -> Option<u16>;
@@ -80,6 +182,63 @@ pub trait Date {
     fn month(&self) -> Option<u8>;
     /// If present, the day. In range [1, 31].
     fn day(&self) -> Option<u8>;
+
+    /// The day of the week.
+    fn weekday(&self) -> Option<Weekday> {
+        calendar::date_days(self).map(calendar::weekday)
+    }
+
+    /// The day of the year, in range [1, 366].
+    fn day_of_year(&self) -> Option<u16> {
+        let days = calendar::date_days(self)?;
+        let jan_1 = calendar::days_from_civil(self.year()? as i64, 1, 1);
+
+        Some((days - jan_1 + 1) as u16)
+    }
+
+    /// Whether the year has a February 29. Only needs the year.
+    fn is_leap_year(&self) -> Option<bool> {
+        self.year().map(|y| calendar::is_leap_year(y as i64))
+    }
+
+    /// The number of days in the month. Only needs the month, and the year if it is February.
+    fn days_in_month(&self) -> Option<u8> {
+        match (self.year(), self.month()) {
+            (Some(y), Some(m)) => Some(calendar::days_in_month(y as i64, m)),
+            (None, Some(m)) if m != 2 => Some(calendar::days_in_month(0, m)),
+            _ => None
+        }
+    }
+
+    /// The Julian Day Number: the Julian Day at noon on this date, counting days from noon on
+    /// November 24, 4714 BC in the proleptic Gregorian calendar.
+    fn julian_day_number(&self) -> Option<i64> {
+        calendar::date_days(self).map(|days| days + JDN_UNIX_EPOCH)
+    }
+
+    /// The Modified Julian Date: days since November 17, 1858.
+    fn modified_julian_date(&self) -> Option<i64> {
+        calendar::date_days(self).map(|days| days + MJD_UNIX_EPOCH)
+    }
+}
+
+/// A day of the week.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// The ISO 8601 day number, from 1 for Monday to 7 for Sunday.
+    pub fn number_from_monday(&self) -> u8 {
+        *self as u8 + 1
+    }
 }
 
 /// Represents the Temporenc "Time" component.
@@ -102,6 +261,12 @@ pub trait Offset {
     fn offset(&self) -> OffsetValue;
 }
 
+/// Lossy conversion into a type with fewer components than `T`, discarding whatever the target
+/// type can't represent. The `TryFrom` impls between the same types are the checked equivalent.
+pub trait TruncateFrom<T> {
+    fn truncate_from(value: &T) -> Self;
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum OffsetValue {
     /// Offset not specified.
@@ -120,6 +285,26 @@ mod date_time_offset;
 mod date_time_subsecond;
 mod date_time_subsecond_offset;
 mod frac_second;
+mod calendar;
+mod zone;
+mod posix_tz;
+mod offset_rounding;
+mod leap_seconds;
+mod unix_time;
+mod time_scales;
+mod duration;
+mod arithmetic;
+mod calendar_unit;
+mod week_date;
+mod parser;
+mod recurrence;
+mod interval;
+mod key;
+mod scan;
+
+#[cfg(feature = "zoneinfo")]
+pub mod zoneinfo;
+mod temporal_fields;
 
 pub use date_only::DateOnly;
 pub use time_only::TimeOnly;
@@ -128,6 +313,32 @@ pub use date_time_offset::DateTimeOffset;
 pub use date_time_subsecond::DateTimeSubSecond;
 pub use date_time_subsecond_offset::DateTimeSubSecondOffset;
 pub use frac_second::FractionalSecond;
+pub use temporal_fields::TemporalFields;
+pub use zone::{Disambiguation, TimeZoneRules};
+pub use posix_tz::{PosixTz, PosixTzError};
+pub use offset_rounding::{OffsetAdjustment, OffsetRounding};
+pub use leap_seconds::LeapSecondMode;
+pub use time_scales::GpsTime;
+pub use duration::SignedDuration;
+pub use arithmetic::{Arithmetic, Elapsed, MonthEnd};
+pub use calendar_unit::{CalendarRounding, FinerFields, Unit};
+pub use week_date::{IsoDateText, IsoText, IsoWeekDate, OrdinalDate};
+pub use recurrence::Recurrences;
+pub use interval::Interval;
+pub use key::{compare_encoded, MissingFields, OrderedKey};
+pub use scan::{KeyRange, OrderedEncoding, RangeEnd};
+
+/// Identifies one of the Temporenc types, each of which is represented by the struct of the same
+/// name.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TypeTag {
+    DateOnly,
+    TimeOnly,
+    DateTime,
+    DateTimeOffset,
+    DateTimeSubSecond,
+    DateTimeSubSecondOffset,
+}
 
 /// Used when creating a struct via `::new()`.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -148,6 +359,67 @@ pub enum SerializationError {
     IoError
 }
 
+/// Used when creating and serializing in one step, as with `TemporalFields::serialize_smallest()`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EncodingError {
+    InvalidFieldValue,
+    IoError,
+}
+
+/// Used when deriving one value from another in a way that depends on the calendar or the
+/// instant a value represents, as with `DateTimeOffset::to_utc()`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConversionError {
+    /// A field needed for the conversion is missing.
+    MissingField,
+    /// The offset is `None` or `SpecifiedElsewhere`, so the instant is not known.
+    UnknownOffset,
+    /// The offset is not a valid Temporenc offset.
+    InvalidOffset,
+    /// The date does not exist, like February 30.
+    InvalidDate,
+    /// The result is outside the range Temporenc can represent.
+    OutOfRange,
+    /// The local time occurs twice in a time zone, and `Disambiguation::Reject` was requested.
+    AmbiguousLocalTime,
+    /// The local time never occurs in a time zone, and `Disambiguation::Reject` was requested.
+    NonexistentLocalTime,
+    /// The second is 60, but there was no leap second at that instant.
+    InvalidLeapSecond,
+    /// The result needs more precision than the type has, like adding a millisecond to a
+    /// `DateTime`.
+    PrecisionLoss,
+    /// The unit is not valid, like a bucket of zero minutes.
+    InvalidUnit,
+}
+
+/// Used when parsing text, as with `DateTime::parse_iso_week_date()`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseError {
+    /// The text is not in the expected form.
+    InvalidSyntax,
+    /// A field is out of range or not allowed in the type, or the date doesn't exist, like week
+    /// 53 of a year with 52 weeks.
+    InvalidFieldValue,
+}
+
+/// Used when a `TryFrom` conversion to a type with fewer components would lose information. The
+/// variant names the component that is present in the source but absent from the target type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NarrowingError {
+    FractionalSecond,
+    Offset,
+}
+
+// Julian Day Number and Modified Julian Date of 1970-01-01
+const JDN_UNIX_EPOCH: i64 = 2_440_588;
+const MJD_UNIX_EPOCH: i64 = 40_587;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_MINUTE: i128 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i128 = 60 * NANOS_PER_MINUTE;
+const NANOS_PER_DAY: i128 = 24 * NANOS_PER_HOUR;
+
 // human-visible range ends (not necessarily internal encoding)
 pub const YEAR_MIN: u16 = 0;
 pub const YEAR_MAX: u16 = 4094;
@@ -242,14 +514,53 @@ fn check_in_range<T: PartialOrd, E: Copy>(v: T, min: T, max: T, err_val: E)
     Ok(v)
 }
 
-fn check_deser_in_range_or_none<T: PartialOrd>(v: T, min: T, max: T, none: T) -> Result<(), DeserializationError> {
-    if (v >= min && v <= max) || v == none {
+/// True if a deserialized raw field value is in range or is the none value. Uses non-short-circuit
+/// operators so that the results for several fields can be combined with `&` and checked with a
+/// single branch.
+#[inline]
+fn deser_in_range_or_none<T: PartialOrd>(v: T, min: T, max: T, none: T) -> bool {
+    ((v >= min) & (v <= max)) | (v == none)
+}
+
+fn check_deser_fields(valid: bool) -> Result<(), DeserializationError> {
+    if valid {
         Ok(())
     } else {
         Err(DeserializationError::InvalidFieldValue)
     }
 }
 
+/// `Deserializable::deserialize_from_slice()` by way of `Deserializable::deserialize()`
+fn deserialize_prefix<T: Deserializable>(bytes: &[u8]) -> Result<(T, usize), DeserializationError> {
+    let mut rest = bytes;
+    let value = T::deserialize(&mut rest)?;
+
+    Ok((value, bytes.len() - rest.len()))
+}
+
+// Big-endian loads of the first 4, 8, or 16 bytes, which the caller has checked are present
+
+#[inline]
+fn load_be_u32(bytes: &[u8]) -> u32 {
+    let mut buf = [0; 4];
+    buf.copy_from_slice(&bytes[..4]);
+    u32::from_be_bytes(buf)
+}
+
+#[inline]
+fn load_be_u64(bytes: &[u8]) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[..8]);
+    u64::from_be_bytes(buf)
+}
+
+#[inline]
+fn load_be_u128(bytes: &[u8]) -> u128 {
+    let mut buf = [0; 16];
+    buf.copy_from_slice(&bytes[..16]);
+    u128::from_be_bytes(buf)
+}
+
 #[inline]
 fn year_num(year: Option<u16>) -> Result<u16, CreationError> {
     check_option_in_range(year, YEAR_MIN, YEAR_MAX, YEAR_RAW_NONE)
@@ -307,6 +618,58 @@ fn offset_num(offset: OffsetValue) -> Result<u8, CreationError> {
     }
 }
 
+// The inverses of the above, for raw values that are known to be in range or none
+
+#[inline]
+fn year_value(raw: u16) -> Option<u16> {
+    if raw == YEAR_RAW_NONE { None } else { Some(raw) }
+}
+
+#[inline]
+fn month_value(raw: u8) -> Option<u8> {
+    if raw == MONTH_RAW_NONE { None } else { Some(raw + 1) }
+}
+
+#[inline]
+fn day_value(raw: u8) -> Option<u8> {
+    if raw == DAY_RAW_NONE { None } else { Some(raw + 1) }
+}
+
+#[inline]
+fn hour_value(raw: u8) -> Option<u8> {
+    if raw == HOUR_RAW_NONE { None } else { Some(raw) }
+}
+
+#[inline]
+fn minute_value(raw: u8) -> Option<u8> {
+    if raw == MINUTE_RAW_NONE { None } else { Some(raw) }
+}
+
+#[inline]
+fn second_value(raw: u8) -> Option<u8> {
+    if raw == SECOND_RAW_NONE { None } else { Some(raw) }
+}
+
+#[inline]
+fn offset_value(raw: u8) -> OffsetValue {
+    match raw {
+        OFFSET_RAW_NONE => OffsetValue::None,
+        OFFSET_RAW_ELSEWHERE => OffsetValue::SpecifiedElsewhere,
+        x => OffsetValue::UtcOffset(((x as i16) - 64) * 15)
+    }
+}
+
+fn check_components<T: Temporal>(fields: &TemporalFields) -> Result<(), CreationError> {
+    if (fields.has_date() && !T::has_date())
+        || (fields.has_time() && !T::has_time())
+        || (fields.has_sub_second() && !T::has_sub_second())
+        || (fields.has_offset() && !T::has_offset()) {
+        return Err(CreationError::InvalidFieldValue);
+    }
+
+    Ok(())
+}
+
 fn check_frac_second(frac_second: FractionalSecond) -> Result<(), CreationError> {
     match frac_second {
         FractionalSecond::None => {},
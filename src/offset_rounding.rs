@@ -0,0 +1,83 @@
+use super::*;
+
+/// How to store a UTC offset that Temporenc can't represent, either because it isn't a whole
+/// number of quarter hours (like the local mean time offsets in historical time zone data, e.g.
+/// +00:19:32 for Amsterdam) or because it is out of range.
+///
+/// Offsets that are already representable are always stored unchanged.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OffsetRounding {
+    /// Round the offset to the nearest quarter hour (halfway cases round east) and move the wall
+    /// clock time by the difference, so the instant is preserved. Offsets that are out of range
+    /// even after rounding are still an error.
+    NearestQuarterHour,
+    /// Convert the wall clock time to UTC and store `UtcOffset(0)`, preserving the instant but
+    /// not the original offset.
+    Utc,
+}
+
+/// How much information was lost when storing an offset according to an `OffsetRounding`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct OffsetAdjustment {
+    /// The original offset minus the stored offset, in seconds.
+    pub offset_seconds: i32,
+    /// The stored instant minus the original instant, in seconds. This is only nonzero when the
+    /// second is missing, since the wall clock time can then only be moved by whole minutes.
+    pub instant_seconds: i32,
+}
+
+impl OffsetAdjustment {
+    /// True if the original offset and instant were both stored exactly.
+    pub fn is_exact(&self) -> bool {
+        self.offset_seconds == 0 && self.instant_seconds == 0
+    }
+}
+
+/// Store the wall clock time in `fields`, moved by `wall_shift_seconds`, at `offset_seconds`.
+/// The offset in `fields` is ignored.
+pub fn round_offset(fields: &TemporalFields, wall_shift_seconds: i64, offset_seconds: i64,
+                    rounding: OffsetRounding)
+                    -> Result<(TemporalFields, OffsetAdjustment), ConversionError> {
+    let stored = if is_representable(offset_seconds) {
+        offset_seconds
+    } else {
+        match rounding {
+            OffsetRounding::NearestQuarterHour => {
+                let rounded = (offset_seconds + QUARTER_HOUR / 2).div_euclid(QUARTER_HOUR)
+                    * QUARTER_HOUR;
+                if !is_representable(rounded) {
+                    return Err(ConversionError::InvalidOffset);
+                }
+                rounded
+            },
+            OffsetRounding::Utc => 0
+        }
+    };
+
+    let shift = wall_shift_seconds + stored - offset_seconds;
+    let (shifted, instant_seconds) = if shift == 0 {
+        (*fields, 0)
+    } else if fields.second.is_some() {
+        (calendar::add_seconds(fields, shift)?, 0)
+    } else {
+        // round to the nearest minute, since that's all that can be stored
+        let minutes = (shift + 30).div_euclid(60);
+        (calendar::add_minutes(fields, minutes)?, minutes * 60 - shift)
+    };
+
+    let adjustment = OffsetAdjustment {
+        offset_seconds: (offset_seconds - stored) as i32,
+        instant_seconds: instant_seconds as i32,
+    };
+
+    Ok((TemporalFields { offset: OffsetValue::UtcOffset((stored / 60) as i16), ..shifted },
+        adjustment))
+}
+
+fn is_representable(offset_seconds: i64) -> bool {
+    offset_seconds % QUARTER_HOUR == 0
+        && offset_seconds >= (OFFSET_MIN as i64) * 60
+        && offset_seconds <= (OFFSET_MAX as i64) * 60
+}
+
+const QUARTER_HOUR: i64 = 15 * 60;
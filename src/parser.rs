@@ -0,0 +1,72 @@
+/// A cursor over ASCII text, shared by the text formats. Each format has its own error type, so
+/// the error to report for text that doesn't match is supplied up front.
+pub struct Parser<'a, E> {
+    bytes: &'a [u8],
+    pos: usize,
+    syntax_error: E,
+}
+
+impl<'a, E: Copy> Parser<'a, E> {
+    pub fn new(text: &'a str, syntax_error: E) -> Parser<'a, E> {
+        Parser { bytes: text.as_bytes(), pos: 0, syntax_error }
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).cloned()
+    }
+
+    /// The text that hasn't been consumed yet
+    pub fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    pub fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, b: u8) -> Result<(), E> {
+        if self.eat(b) { Ok(()) } else { Err(self.syntax_error) }
+    }
+
+    pub fn end(&self) -> Result<(), E> {
+        if self.at_end() { Ok(()) } else { Err(self.syntax_error) }
+    }
+
+    /// Consume bytes while `f` is true of them, returning how many there were
+    pub fn skip_while<F: Fn(u8) -> bool>(&mut self, f: F) -> usize {
+        let count = self.rest().iter().take_while(|&&b| f(b)).count();
+        self.pos += count;
+        count
+    }
+
+    /// Exactly `count` digits
+    pub fn digits(&mut self, count: usize) -> Result<u32, E> {
+        let digits = self.bytes.get(self.pos..(self.pos + count)).ok_or(self.syntax_error)?;
+        if !digits.iter().all(|b| b.is_ascii_digit()) {
+            return Err(self.syntax_error);
+        }
+
+        self.pos += count;
+        Ok(digits.iter().fold(0, |n, &b| n * 10 + (b - b'0') as u32))
+    }
+
+    /// One or more digits, saturating at `u32::MAX` so that callers can range check the result
+    pub fn number(&mut self) -> Result<u32, E> {
+        let start = self.pos;
+        if self.skip_while(|b| b.is_ascii_digit()) == 0 {
+            return Err(self.syntax_error);
+        }
+
+        Ok(self.bytes[start..self.pos].iter()
+            .fold(0, |n: u32, &b| n.saturating_mul(10).saturating_add((b - b'0') as u32)))
+    }
+}
@@ -0,0 +1,215 @@
+use super::*;
+use super::parser::Parser;
+
+/// Time zone rules described by a POSIX TZ string like `CET-1CEST,M3.5.0,M10.5.0/3`, as used
+/// for the `TZ` environment variable. Useful where there is no zoneinfo database. The TZif
+/// extensions from RFC 8536 (transition times that are negative or past 24 hours) are supported.
+///
+/// Note that POSIX offsets are positive west of UTC, the opposite of Temporenc offsets.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let tz = PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+/// let local = DateTime::new(Some(2017), Some(7), Some(1), Some(12), Some(0), Some(0)).unwrap();
+/// let dto = tz.resolve_date_time(&local, Disambiguation::Reject).unwrap();
+/// assert_eq!(OffsetValue::UtcOffset(120), dto.offset());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PosixTz {
+    /// Standard time offset in seconds east of UTC
+    std_offset: i32,
+    dst: Option<DstRule>,
+}
+
+/// Used when parsing a `PosixTz`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PosixTzError {
+    /// The string doesn't follow the POSIX TZ syntax.
+    InvalidSyntax,
+    /// A number is out of range, like month 13.
+    InvalidValue,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct DstRule {
+    /// Daylight saving time offset in seconds east of UTC
+    offset: i32,
+    /// Switch to DST, in local standard time
+    start: Transition,
+    /// Switch back to standard time, in local daylight saving time
+    end: Transition,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Transition {
+    date: TransitionDate,
+    /// Seconds after midnight
+    time: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum TransitionDate {
+    /// `Jn`: day in [1, 365], never counting February 29
+    JulianNoLeap(u16),
+    /// `n`: zero-based day in [0, 365], counting February 29
+    Julian(u16),
+    /// `Mm.w.d`: day `d` (0 is Sunday) of week `w` (5 is the last) of month `m`
+    MonthWeekDay(u8, u8, u8),
+}
+
+impl PosixTz {
+    /// Parse a TZ string. If a DST name is given without rules for when DST starts and ends, the
+    /// current US rules (`M3.2.0,M11.1.0`) are used, as glibc does.
+    pub fn parse(tz: &str) -> Result<PosixTz, PosixTzError> {
+        let mut p = Parser::new(tz, PosixTzError::InvalidSyntax);
+
+        name(&mut p)?;
+        let std_offset = -hms(&mut p, 24)?;
+
+        if p.at_end() {
+            return Ok(PosixTz { std_offset, dst: None });
+        }
+
+        name(&mut p)?;
+        let dst_offset = match p.peek() {
+            Some(b) if b.is_ascii_digit() || b == b'+' || b == b'-' => -hms(&mut p, 24)?,
+            _ => std_offset + 3600
+        };
+
+        let (start, end) = if p.at_end() {
+            (Transition { date: TransitionDate::MonthWeekDay(3, 2, 0), time: DEFAULT_TIME },
+             Transition { date: TransitionDate::MonthWeekDay(11, 1, 0), time: DEFAULT_TIME })
+        } else {
+            p.expect(b',')?;
+            let start = transition(&mut p)?;
+            p.expect(b',')?;
+            let end = transition(&mut p)?;
+            (start, end)
+        };
+
+        p.end()?;
+
+        Ok(PosixTz {
+            std_offset,
+            dst: Some(DstRule { offset: dst_offset, start, end }),
+        })
+    }
+}
+
+impl TimeZoneRules for PosixTz {
+    fn offset_at_unix_seconds(&self, unix_seconds: i64) -> i32 {
+        let dst = match self.dst {
+            Some(dst) => dst,
+            None => return self.std_offset
+        };
+
+        let days = unix_seconds.div_euclid(calendar::SECONDS_PER_DAY);
+        let (year, _, _) = calendar::civil_from_days(days);
+        let start = dst.start.unix_seconds(year) - self.std_offset as i64;
+        let end = dst.end.unix_seconds(year) - dst.offset as i64;
+
+        let in_dst = if start <= end {
+            unix_seconds >= start && unix_seconds < end
+        } else {
+            // southern hemisphere: DST spans the new year
+            unix_seconds < end || unix_seconds >= start
+        };
+
+        if in_dst { dst.offset } else { self.std_offset }
+    }
+}
+
+impl Transition {
+    /// The transition in `year` as seconds since the epoch, treating local time as if it was UTC
+    fn unix_seconds(&self, year: i64) -> i64 {
+        let days = match self.date {
+            TransitionDate::JulianNoLeap(n) => {
+                let leap_day = if calendar::is_leap_year(year) && n >= 60 { 1 } else { 0 };
+                calendar::days_from_civil(year, 1, 1) + (n as i64) - 1 + leap_day
+            },
+            TransitionDate::Julian(n) => calendar::days_from_civil(year, 1, 1) + (n as i64),
+            TransitionDate::MonthWeekDay(month, week, weekday) => {
+                let first = calendar::days_from_civil(year, month, 1);
+                // 1970-01-01 was a Thursday
+                let first_weekday = (first + 4).rem_euclid(7);
+                let mut day = 1 + ((weekday as i64) - first_weekday).rem_euclid(7)
+                    + ((week as i64) - 1) * 7;
+                if day > calendar::days_in_month(year, month) as i64 {
+                    day -= 7;
+                }
+                first + day - 1
+            }
+        };
+
+        days * calendar::SECONDS_PER_DAY + (self.time as i64)
+    }
+}
+
+type TzParser<'a> = Parser<'a, PosixTzError>;
+
+/// A zone abbreviation: at least 3 letters, or `<...>` with letters, digits, `+` and `-`. The
+/// name itself isn't needed.
+fn name(p: &mut TzParser) -> Result<(), PosixTzError> {
+    let quoted = p.eat(b'<');
+    let len = p.skip_while(|b| {
+        b.is_ascii_alphabetic() || (quoted && (b.is_ascii_digit() || b == b'+' || b == b'-'))
+    });
+
+    if len < 3 || (quoted && !p.eat(b'>')) {
+        return Err(PosixTzError::InvalidSyntax);
+    }
+
+    Ok(())
+}
+
+fn number(p: &mut TzParser, max: u32) -> Result<u32, PosixTzError> {
+    check_in_range(p.number()?, 0, max, PosixTzError::InvalidValue)
+}
+
+/// `[+|-]hh[:mm[:ss]]` as seconds
+fn hms(p: &mut TzParser, max_hours: u32) -> Result<i32, PosixTzError> {
+    let negative = p.eat(b'-');
+    if !negative {
+        p.eat(b'+');
+    }
+
+    let mut seconds = number(p, max_hours)? * 3600;
+    if p.eat(b':') {
+        seconds += number(p, 59)? * 60;
+        if p.eat(b':') {
+            seconds += number(p, 59)?;
+        }
+    }
+
+    Ok(if negative { -(seconds as i32) } else { seconds as i32 })
+}
+
+fn transition(p: &mut TzParser) -> Result<Transition, PosixTzError> {
+    let date = if p.eat(b'J') {
+        let n = number(p, 365)?;
+        if n == 0 {
+            return Err(PosixTzError::InvalidValue);
+        }
+        TransitionDate::JulianNoLeap(n as u16)
+    } else if p.eat(b'M') {
+        let month = number(p, 12)?;
+        p.expect(b'.')?;
+        let week = number(p, 5)?;
+        p.expect(b'.')?;
+        let weekday = number(p, 6)?;
+        if month == 0 || week == 0 {
+            return Err(PosixTzError::InvalidValue);
+        }
+        TransitionDate::MonthWeekDay(month as u8, week as u8, weekday as u8)
+    } else {
+        TransitionDate::Julian(number(p, 365)? as u16)
+    };
+
+    let time = if p.eat(b'/') { hms(p, 167)? } else { DEFAULT_TIME };
+
+    Ok(Transition { date, time })
+}
+
+/// 02:00:00
+const DEFAULT_TIME: i32 = 2 * 3600;
@@ -0,0 +1,130 @@
+use super::*;
+
+/// The complete `DateTime`s in a range that a partial `DateTime` matches with its missing fields
+/// as wildcards, in chronological order. Created by `DateTime::recurrences()`.
+///
+/// Days that don't exist, like February 30, are skipped. A second of 60 is never produced, since
+/// only a few minutes have a leap second, so a pattern with a second of 60 matches nothing.
+///
+/// ```
+/// use temporenc::*;
+///
+/// // 09:00:00 on the 31st of every month
+/// let pattern = DateTime::new(None, None, Some(31), Some(9), Some(0), Some(0)).unwrap();
+/// let start = DateTime::new(Some(2017), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap();
+/// let end = DateTime::new(Some(2018), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap();
+///
+/// let months: Vec<u8> = pattern.recurrences(&start, &end).unwrap()
+///     .map(|dt| dt.month().unwrap())
+///     .collect();
+/// assert_eq!(vec![1, 3, 5, 7, 8, 10, 12], months);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recurrences {
+    /// Year, month, day, hour, minute and second, with `None` for wildcards
+    pattern: [Option<u16>; 6],
+    /// The earliest value that may be produced next, or `None` when there are no more
+    next: Option<[u16; 6]>,
+    /// Exclusive
+    end: [u16; 6],
+}
+
+impl Recurrences {
+    /// Returns an error if `start` or `end` is not complete.
+    pub fn new(pattern: &DateTime, start: &DateTime, end: &DateTime)
+               -> Result<Recurrences, ConversionError> {
+        if !start.is_complete() || !end.is_complete() {
+            return Err(ConversionError::MissingField);
+        }
+
+        let pattern = [pattern.year(), pattern.month().map(u16::from),
+                       pattern.day().map(u16::from), pattern.hour().map(u16::from),
+                       pattern.minute().map(u16::from), pattern.second().map(u16::from)];
+        let next = if pattern[SECOND] == Some(SECOND_MAX as u16) {
+            None
+        } else {
+            Some(civil(start))
+        };
+
+        Ok(Recurrences { pattern, next, end: civil(end) })
+    }
+
+    /// The first value at or after `value` that matches the pattern, if it is before the end.
+    fn next_match(&self, mut value: [u16; 6]) -> Option<[u16; 6]> {
+        loop {
+            if value >= self.end {
+                return None;
+            }
+
+            let days_in_month = calendar::days_in_month(value[YEAR] as i64, value[MONTH] as u8);
+            let max = [YEAR_MAX, MONTH_MAX as u16, days_in_month as u16, HOUR_MAX as u16,
+                       MINUTE_MAX as u16, (SECOND_MAX - 1) as u16];
+
+            // the coarsest field that is out of range or doesn't match
+            let mismatch = (0..value.len()).find(|&i| {
+                value[i] > max[i] || self.pattern[i].map_or(false, |p| p != value[i])
+            });
+
+            let i = match mismatch {
+                Some(i) => i,
+                None => return Some(value)
+            };
+
+            match self.pattern[i] {
+                // skip ahead to the value in the pattern
+                Some(p) if p > value[i] && p <= max[i] => {
+                    value[i] = p;
+                    reset_finer(&mut value, i + 1);
+                },
+                // no match until the next coarser unit
+                _ => {
+                    if i == YEAR {
+                        return None;
+                    }
+                    value[i - 1] += 1;
+                    reset_finer(&mut value, i);
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Recurrences {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        let value = self.next.and_then(|v| self.next_match(v));
+
+        self.next = value.map(|mut v| {
+            // may be out of range, which the next search handles
+            v[SECOND] += 1;
+            v
+        });
+
+        value.and_then(|v| {
+            DateTime::new(Some(v[YEAR]), Some(v[MONTH] as u8), Some(v[DAY] as u8),
+                          Some(v[HOUR] as u8), Some(v[MINUTE] as u8), Some(v[SECOND] as u8)).ok()
+        })
+    }
+}
+
+/// The fields of a complete `DateTime`
+fn civil(dt: &DateTime) -> [u16; 6] {
+    [dt.year().unwrap_or(0), dt.month().unwrap_or(1) as u16, dt.day().unwrap_or(1) as u16,
+     dt.hour().unwrap_or(0) as u16, dt.minute().unwrap_or(0) as u16,
+     dt.second().unwrap_or(0) as u16]
+}
+
+/// Set the fields from index `from` onward to the start of their range.
+fn reset_finer(value: &mut [u16; 6], from: usize) {
+    for (i, field) in value.iter_mut().enumerate().skip(from) {
+        *field = if i == MONTH || i == DAY { 1 } else { 0 };
+    }
+}
+
+const YEAR: usize = 0;
+const MONTH: usize = 1;
+const DAY: usize = 2;
+const HOUR: usize = 3;
+const MINUTE: usize = 4;
+const SECOND: usize = 5;
@@ -0,0 +1,212 @@
+//! Bounds for prefix and range scans of ordered key-value stores.
+
+use std::io::Write;
+use std::ops::Bound;
+
+use super::*;
+
+/// Implemented by the types whose Temporenc encoding sorts bytewise in the order described by
+/// `OrderedKey` with `MissingFields::Last`. That's all of them but `DateTimeSubSecond` and
+/// `DateTimeSubSecondOffset`, whose encoding starts with the precision of the fractional second.
+pub trait OrderedEncoding: OrderedKey {}
+
+impl OrderedEncoding for DateOnly {}
+impl OrderedEncoding for TimeOnly {}
+impl OrderedEncoding for DateTime {}
+impl OrderedEncoding for DateTimeOffset {}
+
+/// Whether the end of a `KeyRange` is written as the last key in the range or the first key
+/// after it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RangeEnd {
+    Inclusive,
+    Exclusive,
+}
+
+/// A range of keys to scan in an ordered key-value store, for either the keys written by
+/// `OrderedKey::serialize_key()` or values in the Temporenc encoding.
+///
+/// The range can be written as a start key, which is the first key in the range, and an end key
+/// that is either the last key in the range or the first key after it. Keys that start with a
+/// key in the range, as in composite keys, sort before the exclusive end key.
+///
+/// ```
+/// use temporenc::*;
+///
+/// // every DateTimeOffset in January 2017
+/// let january = DateTimeOffset::new(Some(2017), Some(1), None, None, None, None,
+///     OffsetValue::None).unwrap();
+/// let range = KeyRange::for_partial_encoded(&january).unwrap();
+///
+/// let mut start = Vec::new();
+/// range.write_start(&mut start).unwrap();
+/// let mut end = Vec::new();
+/// range.write_end(RangeEnd::Exclusive, &mut end).unwrap();
+///
+/// let mut value = Vec::new();
+/// DateTimeOffset::new(Some(2017), Some(1), Some(31), Some(23), Some(59), Some(59),
+///     OffsetValue::UtcOffset(60)).unwrap().serialize(&mut value).unwrap();
+/// assert!(start <= value && value < end);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct KeyRange {
+    /// The first and last packed keys in the range, without the tag or padding. `first` is
+    /// larger than `last` if the range is empty.
+    first: u128,
+    last: u128,
+    /// The type tag, shifted into place above the fields, for the Temporenc encoding
+    tag: u128,
+    /// Bits in a packed key
+    bits: u32,
+    /// Zero bits after the fields
+    padding: u32,
+    /// Bytes in a key
+    size: usize,
+}
+
+impl KeyRange {
+    /// The keys of all values of the type that have the present fields of `partial`, such as all
+    /// values in a month for a value with just a year and a month. Those include values with
+    /// missing fields, like one with no day in that month.
+    ///
+    /// Returns an error if a field is present after a missing one in key order (year, month,
+    /// day, hour, minute, second, fractional second, offset), since those values wouldn't have
+    /// adjacent keys.
+    pub fn for_partial<T: OrderedKey>(partial: &T, missing: MissingFields)
+                                      -> Result<KeyRange, ConversionError> {
+        let range = KeyRange::key_format::<T>();
+
+        range.with_prefix::<T>(&partial.fields(), missing)
+    }
+
+    /// Like `for_partial()`, but for values in the Temporenc encoding.
+    pub fn for_partial_encoded<T: OrderedEncoding>(partial: &T)
+                                                   -> Result<KeyRange, ConversionError> {
+        let range = KeyRange::encoded_format::<T>();
+
+        range.with_prefix::<T>(&partial.fields(), MissingFields::Last)
+    }
+
+    /// The keys from `start` to `end`, which are compared in the order described by
+    /// `OrderedKey`.
+    pub fn for_range<T: OrderedKey>(start: Bound<&T>, end: Bound<&T>, missing: MissingFields)
+                                    -> KeyRange {
+        KeyRange::key_format::<T>().with_bounds(start, end, missing)
+    }
+
+    /// Like `for_range()`, but for values in the Temporenc encoding.
+    pub fn for_range_encoded<T: OrderedEncoding>(start: Bound<&T>, end: Bound<&T>) -> KeyRange {
+        KeyRange::encoded_format::<T>().with_bounds(start, end, MissingFields::Last)
+    }
+
+    /// True if no key is in the range.
+    pub fn is_empty(&self) -> bool {
+        self.first > self.last
+    }
+
+    /// The size of the start and end keys.
+    pub fn key_size(&self) -> usize {
+        self.size
+    }
+
+    /// Write the first key in the range. Returns the number of bytes written.
+    pub fn write_start<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
+        self.write_key(self.first, writer)
+    }
+
+    /// Write the last key in the range, or the first key after it. Returns the number of bytes
+    /// written. For an empty range, the end is before the start.
+    pub fn write_end<W: Write>(&self, end: RangeEnd, writer: &mut W)
+                               -> Result<usize, SerializationError> {
+        let last = self.aligned(self.last);
+        let key = match end {
+            RangeEnd::Inclusive => last,
+            // The smallest key after every key that starts with the last one. Keys have padding
+            // bits, and the tags of the types with an ordered encoding aren't all ones, so this
+            // fits in the same number of bytes.
+            RangeEnd::Exclusive => last + 1,
+        };
+
+        self.write_aligned(key, writer)
+    }
+
+    fn key_format<T: OrderedKey>() -> KeyRange {
+        let bits = key::key_bits::<T>();
+        let size = T::key_size();
+        // keys are left-aligned
+        let padding = size as u32 * 8 - bits;
+
+        KeyRange { first: 0, last: max_packed(bits), tag: 0, bits, padding, size }
+    }
+
+    fn encoded_format<T: OrderedEncoding>() -> KeyRange {
+        let bits = key::key_bits::<T>();
+        let tag = match T::type_tag() {
+            TypeTag::DateOnly => DATE_TAG,
+            TypeTag::TimeOnly => TIME_TAG,
+            TypeTag::DateTime => DATE_TIME_TAG,
+            TypeTag::DateTimeOffset => DATE_TIME_OFFSET_TAG,
+            TypeTag::DateTimeSubSecond => DATE_TIME_SUBSECOND_TAG,
+            TypeTag::DateTimeSubSecondOffset => DATE_TIME_SUBSECOND_OFFSET_TAG,
+        };
+        let size = T::max_serialized_size();
+        // the tag is at the top of the first byte, and the fields take the rest of the bits
+        let tag = (tag as u128) << (size * 8 - 8);
+
+        KeyRange { first: 0, last: max_packed(bits), tag, bits, padding: 0, size }
+    }
+
+    fn with_prefix<T: Temporal>(self, fields: &TemporalFields, missing: MissingFields)
+                                -> Result<KeyRange, ConversionError> {
+        let rest = max_packed(key::missing_bits::<T>(fields)?);
+        let packed = key::pack::<T>(fields, missing);
+
+        Ok(KeyRange { first: packed & !rest, last: packed | rest, ..self })
+    }
+
+    fn with_bounds<T: OrderedKey>(self, start: Bound<&T>, end: Bound<&T>, missing: MissingFields)
+                                  -> KeyRange {
+        let max = max_packed(self.bits);
+        let packed = |value: &T| key::pack::<T>(&value.fields(), missing);
+
+        let first = match start {
+            Bound::Included(value) => packed(value),
+            Bound::Excluded(value) => packed(value) + 1,
+            Bound::Unbounded => 0,
+        };
+        let last = match end {
+            Bound::Included(value) => Some(packed(value)),
+            Bound::Excluded(value) => packed(value).checked_sub(1),
+            Bound::Unbounded => Some(max),
+        };
+
+        match last {
+            Some(last) if first <= max => KeyRange { first, last, ..self },
+            // empty
+            _ => KeyRange { first: 1, last: 0, ..self }
+        }
+    }
+
+    /// A key as an integer of `size` bytes
+    fn aligned(&self, packed: u128) -> u128 {
+        self.tag | (packed << self.padding)
+    }
+
+    fn write_key<W: Write>(&self, packed: u128, writer: &mut W)
+                           -> Result<usize, SerializationError> {
+        self.write_aligned(self.aligned(packed), writer)
+    }
+
+    fn write_aligned<W: Write>(&self, key: u128, writer: &mut W)
+                               -> Result<usize, SerializationError> {
+        let bytes = key.to_be_bytes();
+
+        write_array_map_err(&bytes[(bytes.len() - self.size)..], writer)
+            .map_err(|_| SerializationError::IoError)
+    }
+}
+
+/// The largest packed key with `bits` bits
+fn max_packed(bits: u32) -> u128 {
+    (1 << bits) - 1
+}
@@ -0,0 +1,137 @@
+use std::io::Write;
+
+use super::*;
+
+/// Every field that any of the Temporenc types can hold, independent of which type will hold
+/// them. Absent fields are represented the same way as they are by the component accessors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TemporalFields {
+    pub year: Option<u16>,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub second: Option<u8>,
+    pub frac_second: FractionalSecond,
+    pub offset: OffsetValue,
+}
+
+impl TemporalFields {
+    /// True if any of year, month, or day are present.
+    pub fn has_date(&self) -> bool {
+        self.year.is_some() || self.month.is_some() || self.day.is_some()
+    }
+
+    /// True if any of hour, minute, or second are present.
+    pub fn has_time(&self) -> bool {
+        self.hour.is_some() || self.minute.is_some() || self.second.is_some()
+    }
+
+    /// True if the fractional second is present.
+    pub fn has_sub_second(&self) -> bool {
+        self.frac_second != FractionalSecond::None
+    }
+
+    /// True if the offset is present (including `OffsetValue::SpecifiedElsewhere`).
+    pub fn has_offset(&self) -> bool {
+        self.offset != OffsetValue::None
+    }
+
+    /// True if every present field equals the same field in `concrete`, treating missing fields as
+    /// wildcards. A fractional second matches a more precise one that starts with the same digits,
+    /// so 123 milliseconds matches 123456 microseconds but not the other way around. Offsets are
+    /// compared as they are, without converting between them.
+    pub fn matches(&self, concrete: &TemporalFields) -> bool {
+        fn field_matches<T: PartialEq>(pattern: Option<T>, concrete: Option<T>) -> bool {
+            pattern.is_none() || pattern == concrete
+        }
+
+        let frac_second_matches = match (self.frac_second, concrete.frac_second) {
+            (FractionalSecond::None, _) => true,
+            (_, FractionalSecond::None) => false,
+            (pattern, c) => {
+                let unit = match pattern {
+                    FractionalSecond::Milliseconds(_) => 1_000_000,
+                    FractionalSecond::Microseconds(_) => 1_000,
+                    _ => 1
+                };
+                frac_second::nanos(&pattern) == frac_second::nanos(&c) / unit * unit
+            }
+        };
+
+        field_matches(self.year, concrete.year)
+            && field_matches(self.month, concrete.month)
+            && field_matches(self.day, concrete.day)
+            && field_matches(self.hour, concrete.hour)
+            && field_matches(self.minute, concrete.minute)
+            && field_matches(self.second, concrete.second)
+            && frac_second_matches
+            && (self.offset == OffsetValue::None || self.offset == concrete.offset)
+    }
+
+    /// The type with the smallest encoded size that can represent all of the present fields.
+    ///
+    /// If there is neither a date nor a time, `DateOnly` is chosen.
+    pub fn smallest_type(&self) -> TypeTag {
+        match (self.has_sub_second(), self.has_offset()) {
+            (false, false) => {
+                if !self.has_time() {
+                    TypeTag::DateOnly
+                } else if !self.has_date() {
+                    TypeTag::TimeOnly
+                } else {
+                    TypeTag::DateTime
+                }
+            },
+            (false, true) => TypeTag::DateTimeOffset,
+            (true, false) => TypeTag::DateTimeSubSecond,
+            (true, true) => TypeTag::DateTimeSubSecondOffset,
+        }
+    }
+
+    /// Serialize with the type chosen by `smallest_type()`. Returns that type and the number of
+    /// bytes written.
+    ///
+    /// Returns an error if any of the fields have invalid values, like a month of 18.
+    pub fn serialize_smallest<W: Write>(&self, writer: &mut W)
+                                        -> Result<(TypeTag, usize), EncodingError> {
+        let tag = self.smallest_type();
+
+        let written = match tag {
+            TypeTag::DateOnly => serialize_as::<DateOnly, W>(self, writer),
+            TypeTag::TimeOnly => serialize_as::<TimeOnly, W>(self, writer),
+            TypeTag::DateTime => serialize_as::<DateTime, W>(self, writer),
+            TypeTag::DateTimeOffset => serialize_as::<DateTimeOffset, W>(self, writer),
+            TypeTag::DateTimeSubSecond => serialize_as::<DateTimeSubSecond, W>(self, writer),
+            TypeTag::DateTimeSubSecondOffset => {
+                serialize_as::<DateTimeSubSecondOffset, W>(self, writer)
+            },
+        }?;
+
+        Ok((tag, written))
+    }
+}
+
+impl Default for TemporalFields {
+    /// All fields absent.
+    fn default() -> TemporalFields {
+        TemporalFields {
+            year: None,
+            month: None,
+            day: None,
+            hour: None,
+            minute: None,
+            second: None,
+            frac_second: FractionalSecond::None,
+            offset: OffsetValue::None,
+        }
+    }
+}
+
+fn serialize_as<T: Temporal, W: Write>(fields: &TemporalFields, writer: &mut W)
+                                       -> Result<usize, EncodingError> {
+    T::from_fields(*fields)
+        .map_err(|_| EncodingError::InvalidFieldValue)?
+        .serialize(writer)
+        .map_err(|_| EncodingError::IoError)
+}
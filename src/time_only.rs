@@ -1,13 +1,16 @@
+use std::convert::TryFrom;
+use std::fmt;
 use std::io::{Read, Write};
 
 use super::*;
 
 /// Just a Time.
-#[derive(Debug, PartialEq)]
+///
+/// Values compare in the same order as their encoded bytes.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct TimeOnly {
-    hour: u8,
-    minute: u8,
-    second: u8,
+    /// The encoded value in the low `SERIALIZED_SIZE` bytes
+    bits: u32
 }
 
 impl TimeOnly {
@@ -15,37 +18,91 @@ impl TimeOnly {
     /// Returns an error if any of the arguments have invalid values, like a minute of 70.
     #[inline]
     pub fn new(hour: Option<u8>, minute: Option<u8>, second: Option<u8>) -> Result<TimeOnly, CreationError> {
-        Ok(TimeOnly {
-            hour: hour_num(hour)?,
-            minute: minute_num(minute)?,
-            second: second_num(second)?,
-        })
+        let bits = ((TIME_TAG as u32) << TAG_SHIFT)
+            | ((hour_num(hour)? as u32) << HOUR_SHIFT)
+            | ((minute_num(minute)? as u32) << MINUTE_SHIFT)
+            | second_num(second)? as u32;
+
+        Ok(TimeOnly { bits })
+    }
+
+    /// The time at `seconds` after midnight, which must be at most 86400. 86400 is 23:59:60, the
+    /// end of a day with a leap second.
+    pub fn from_seconds_since_midnight(seconds: u32) -> Result<TimeOnly, CreationError> {
+        if seconds == calendar::SECONDS_PER_DAY as u32 {
+            return TimeOnly::new(Some(23), Some(59), Some(60));
+        }
+
+        check_in_range(seconds, 0, calendar::SECONDS_PER_DAY as u32 - 1,
+                       CreationError::InvalidFieldValue)?;
+
+        TimeOnly::new(Some((seconds / 3600) as u8), Some((seconds / 60 % 60) as u8),
+                      Some((seconds % 60) as u8))
+    }
+
+    /// Seconds since midnight, or `None` if any of the fields are missing. Second 60 is counted
+    /// as 60, so 23:59:60 is 86400.
+    pub fn seconds_since_midnight(&self) -> Option<u32> {
+        match (self.hour(), self.minute(), self.second()) {
+            (Some(h), Some(m), Some(s)) => Some((h as u32) * 3600 + (m as u32) * 60 + (s as u32)),
+            _ => None
+        }
+    }
+
+    /// Add `duration`, wrapping around midnight, and return the result along with the number of
+    /// days carried, which is negative if the result is on an earlier day.
+    ///
+    /// As with `checked_add()`, fields finer than the duration may be missing and are left
+    /// unchanged: 09:xx + 25 hours is 10:xx the next day. Returns an error if a field that would
+    /// be affected is missing, or if the duration isn't a whole number of seconds.
+    pub fn wrapping_add(&self, duration: SignedDuration)
+                        -> Result<(TimeOnly, i64), ConversionError> {
+        TimeOnly::wrapping_add_nanos(&self.fields(), duration.as_nanos())
+    }
+
+    /// Like `wrapping_add()`, but subtracting.
+    pub fn wrapping_sub(&self, duration: SignedDuration)
+                        -> Result<(TimeOnly, i64), ConversionError> {
+        let nanos = duration.as_nanos().checked_neg().ok_or(ConversionError::OutOfRange)?;
+
+        TimeOnly::wrapping_add_nanos(&self.fields(), nanos)
+    }
+
+    fn wrapping_add_nanos(fields: &TemporalFields, nanos: i128)
+                          -> Result<(TimeOnly, i64), ConversionError> {
+        let (fields, days) = arithmetic::add_nanos::<TimeOnly>(fields, nanos)?;
+        let days = i64::try_from(days).map_err(|_| ConversionError::OutOfRange)?;
+
+        TimeOnly::from_fields(fields)
+            .map(|time| (time, days))
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The value packed into the low 17 bits of a `u64`, as described in
+    /// `DateTime::to_packed_u64()`.
+    pub fn to_packed_u64(&self) -> u64 {
+        key::to_packed(self) as u64
+    }
+
+    /// The inverse of `to_packed_u64()`.
+    ///
+    /// Returns an error if unused bits are set or a field is out of range.
+    pub fn from_packed_u64(packed: u64) -> Result<TimeOnly, DeserializationError> {
+        key::from_packed(packed as u128)
     }
 }
 
 impl Time for TimeOnly {
     fn hour(&self) -> Option<u8> {
-        if self.hour == HOUR_RAW_NONE {
-            None
-        } else {
-            Some(self.hour)
-        }
+        hour_value(self.raw_hour())
     }
 
     fn minute(&self) -> Option<u8> {
-        if self.minute == MINUTE_RAW_NONE {
-            None
-        } else {
-            Some(self.minute)
-        }
+        minute_value(self.raw_minute())
     }
 
     fn second(&self) -> Option<u8> {
-        if self.second == SECOND_RAW_NONE {
-            None
-        } else {
-            Some(self.second)
-        }
+        second_value(self.raw_second())
     }
 }
 
@@ -59,11 +116,7 @@ impl Serializable for TimeOnly {
     }
 
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<usize, SerializationError> {
-        let b0 = TIME_TAG | self.hour >> 4;
-        let b1 = (self.hour << 4) | (self.minute >> 2);
-        let b2 = (self.minute << 6) | (self.second);
-
-        write_array_map_err(&[b0, b1, b2], writer)
+        write_array_map_err(&self.bits.to_be_bytes()[(4 - SERIALIZED_SIZE)..], writer)
             .map_err(|_| SerializationError::IoError)
     }
 
@@ -71,37 +124,123 @@ impl Serializable for TimeOnly {
 
 impl Deserializable for TimeOnly {
     fn deserialize<R: Read>(reader: &mut R) -> Result<TimeOnly, DeserializationError> {
-        let mut buf = [0; SERIALIZED_SIZE];
-        read_exact(reader, &mut buf)?;
+        let mut buf = [0; 4];
+        read_exact(reader, &mut buf[(4 - SERIALIZED_SIZE)..])?;
 
-        let byte0 = buf[0];
-        if byte0 & 0b1111_1110 != TIME_TAG {
+        if buf[1] & 0b1111_1110 != TIME_TAG {
             return Err(DeserializationError::IncorrectTypeTag);
         }
 
         // 7-bit tag, 5-bit hour, 6-bit minute, 6-bit second
         // TTTT TTTH HHHH MMMM MMSS SSSS
+        let time = TimeOnly { bits: u32::from_be_bytes(buf) };
+
+        time.check_fields()?;
+
+        Ok(time)
+    }
 
-        let mut raw_hour = byte0 << 4;
-        let byte1 = buf[1];
-        raw_hour |= (byte1 & 0xF0) >> 4;
+    fn deserialize_from_slice(bytes: &[u8]) -> Result<(TimeOnly, usize), DeserializationError> {
+        if bytes.len() < 4 {
+            return deserialize_prefix(bytes);
+        }
 
-        let mut raw_minute = (byte1 & 0x0F) << 2;
-        let byte2 = buf[2];
-        raw_minute |= (byte2 & 0xC0) >> 6;
+        // drop whatever follows the value
+        let time = TimeOnly { bits: load_be_u32(bytes) >> (8 * (4 - SERIALIZED_SIZE)) };
 
-        let raw_second = byte2 & 0x3F;
+        if (time.bits >> TAG_SHIFT) as u8 & 0b1111_1110 != TIME_TAG {
+            return Err(DeserializationError::IncorrectTypeTag);
+        }
 
-        check_deser_in_range_or_none(raw_hour, HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_minute, MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)?;
-        check_deser_in_range_or_none(raw_second, SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE)?;
+        time.check_fields()?;
 
-        Ok(TimeOnly {
-            hour: raw_hour,
-            minute: raw_minute,
-            second: raw_second
-        })
+        Ok((time, SERIALIZED_SIZE))
+    }
+}
+
+impl Temporal for TimeOnly {
+    fn type_tag() -> TypeTag {
+        TypeTag::TimeOnly
+    }
+
+    fn min_serialized_size() -> usize {
+        SERIALIZED_SIZE
+    }
+
+    fn has_date() -> bool {
+        false
+    }
+
+    fn has_time() -> bool {
+        true
+    }
+
+    fn has_sub_second() -> bool {
+        false
+    }
+
+    fn has_offset() -> bool {
+        false
+    }
+
+    fn fields(&self) -> TemporalFields {
+        TemporalFields {
+            hour: self.hour(),
+            minute: self.minute(),
+            second: self.second(),
+            ..TemporalFields::default()
+        }
+    }
+
+    fn from_fields(fields: TemporalFields) -> Result<TimeOnly, CreationError> {
+        check_components::<TimeOnly>(&fields)?;
+
+        TimeOnly::new(fields.hour, fields.minute, fields.second)
+    }
+}
+
+impl TimeOnly {
+    /// Validate every field of a deserialized value, branching only once at the end
+    #[inline]
+    fn check_fields(&self) -> Result<(), DeserializationError> {
+        let valid = deser_in_range_or_none(self.raw_hour(), HOUR_MIN, HOUR_MAX, HOUR_RAW_NONE)
+            & deser_in_range_or_none(self.raw_minute(), MINUTE_MIN, MINUTE_MAX, MINUTE_RAW_NONE)
+            & deser_in_range_or_none(self.raw_second(), SECOND_MIN, SECOND_MAX, SECOND_RAW_NONE);
+
+        check_deser_fields(valid)
+    }
+
+    // the raw none values are all ones, so they are also the masks
+
+    #[inline]
+    fn raw_hour(&self) -> u8 {
+        (self.bits >> HOUR_SHIFT) as u8 & HOUR_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_minute(&self) -> u8 {
+        (self.bits >> MINUTE_SHIFT) as u8 & MINUTE_RAW_NONE
+    }
+
+    #[inline]
+    fn raw_second(&self) -> u8 {
+        self.bits as u8 & SECOND_RAW_NONE
+    }
+}
+
+impl fmt::Debug for TimeOnly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimeOnly")
+            .field("hour", &self.hour())
+            .field("minute", &self.minute())
+            .field("second", &self.second())
+            .finish()
     }
 }
 
 const SERIALIZED_SIZE: usize = 3;
+
+// bit positions in the encoded value
+const TAG_SHIFT: u32 = 16;
+const HOUR_SHIFT: u32 = 12;
+const MINUTE_SHIFT: u32 = 6;
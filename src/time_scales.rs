@@ -0,0 +1,108 @@
+//! Conversions between UTC and the TAI and GPS time scales, which don't have leap seconds.
+
+use super::*;
+
+/// A time in the GPS time scale, as reported by GNSS receivers. GPS time has no leap seconds, so
+/// it has drifted ahead of UTC since it started at 1980-01-06T00:00:00Z, and is always 19 seconds
+/// behind TAI.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let gps = GpsTime { week: 1930, seconds_of_week: 18, nanos: 0 };
+/// let utc = DateTimeSubSecondOffset::from_gps(&gps).unwrap();
+/// assert_eq!(Some(2017), utc.year());
+/// assert_eq!(Some(0), utc.second());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GpsTime {
+    /// Weeks since the start of GPS time. This is the full week number, not the week modulo 1024
+    /// that is broadcast by satellites.
+    pub week: u32,
+    /// Seconds since the start of the week, in [0, 604799].
+    pub seconds_of_week: u32,
+    /// Nanoseconds since the start of the second, in [0, 999999999].
+    pub nanos: u32,
+}
+
+/// The same instant as the UTC `fields` in TAI, as fields with no offset. The fractional second
+/// is unchanged.
+pub fn utc_to_tai(fields: &TemporalFields) -> Result<TemporalFields, ConversionError> {
+    let utc = calendar::change_offset(fields, 0)?;
+    let unix = whole_seconds(&utc)?;
+
+    let offset = if utc.second == Some(60) {
+        if !leap_seconds::is_leap_second_minute(&utc) {
+            return Err(ConversionError::InvalidLeapSecond);
+        }
+        // in effect during the leap second, which has the Unix time of the second before
+        leap_seconds::tai_minus_utc(unix - 1)?
+    } else {
+        leap_seconds::tai_minus_utc(unix)?
+    };
+
+    fields_from_seconds(unix + offset, utc.frac_second, OffsetValue::None)
+}
+
+/// The inverse of `utc_to_tai()`, producing fields at `UtcOffset(0)`.
+pub fn tai_to_utc(fields: &TemporalFields) -> Result<TemporalFields, ConversionError> {
+    if fields.second == Some(60) {
+        // TAI has no leap seconds
+        return Err(ConversionError::InvalidLeapSecond);
+    }
+
+    let (unix, leap) = leap_seconds::utc_from_tai(whole_seconds(fields)?)?;
+    let utc = fields_from_seconds(unix, fields.frac_second, OffsetValue::UtcOffset(0))?;
+
+    Ok(if leap { TemporalFields { second: Some(60), ..utc } } else { utc })
+}
+
+/// Returns an error if `gps` is not a valid GPS time.
+pub fn gps_to_tai(gps: &GpsTime) -> Result<TemporalFields, ConversionError> {
+    if gps.seconds_of_week as i64 >= SECONDS_PER_WEEK
+        || gps.nanos as i128 >= NANOS_PER_SECOND {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    let seconds = GPS_EPOCH_TAI + (gps.week as i64) * SECONDS_PER_WEEK
+        + gps.seconds_of_week as i64;
+
+    fields_from_seconds(seconds, FractionalSecond::Nanoseconds(gps.nanos), OffsetValue::None)
+}
+
+/// The inverse of `gps_to_tai()`.
+pub fn tai_to_gps(fields: &TemporalFields) -> Result<GpsTime, ConversionError> {
+    let seconds = whole_seconds(fields)? - GPS_EPOCH_TAI;
+    if seconds < 0 {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    Ok(GpsTime {
+        week: (seconds / SECONDS_PER_WEEK) as u32,
+        seconds_of_week: (seconds % SECONDS_PER_WEEK) as u32,
+        nanos: frac_second::nanos(&fields.frac_second),
+    })
+}
+
+/// Seconds since 1970-01-01T00:00:00 in whatever time scale `fields` is in, ignoring the offset
+/// and fractional second. Second 60 is counted as the start of the next minute.
+fn whole_seconds(fields: &TemporalFields) -> Result<i64, ConversionError> {
+    let days = calendar::fields_to_days(fields)?;
+
+    match (fields.hour, fields.minute, fields.second) {
+        (Some(h), Some(m), Some(s)) => Ok(days * calendar::SECONDS_PER_DAY + (h as i64) * 3600
+            + (m as i64) * 60 + (s as i64)),
+        _ => Err(ConversionError::MissingField)
+    }
+}
+
+fn fields_from_seconds(seconds: i64, frac_second: FractionalSecond, offset: OffsetValue)
+                       -> Result<TemporalFields, ConversionError> {
+    let fields = unix_time::from_unix_nanos((seconds as i128) * NANOS_PER_SECOND)?;
+
+    Ok(TemporalFields { frac_second, offset, ..fields })
+}
+
+const SECONDS_PER_WEEK: i64 = 7 * calendar::SECONDS_PER_DAY;
+/// 1980-01-06T00:00:19 TAI, the start of GPS time
+const GPS_EPOCH_TAI: i64 = 315_964_800 + 19;
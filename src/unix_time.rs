@@ -0,0 +1,120 @@
+//! Conversions between values with a UTC offset and Unix time.
+
+use super::*;
+
+/// Nanoseconds since 1970-01-01T00:00:00Z, not counting leap seconds, for the instant in
+/// `fields`. A missing fractional second is treated as zero.
+pub fn to_unix_nanos(fields: &TemporalFields, mode: LeapSecondMode)
+                     -> Result<i128, ConversionError> {
+    let utc = calendar::change_offset(fields, 0)?;
+    let days = calendar::fields_to_days(&utc)?;
+
+    let (hour, minute, second) = match (utc.hour, utc.minute, utc.second) {
+        (Some(h), Some(m), Some(s)) => (h as i64, m as i64, s as i64),
+        _ => return Err(ConversionError::MissingField)
+    };
+    let mut nanos = frac_second::nanos(&utc.frac_second) as i128;
+
+    if second == 60 && mode != LeapSecondMode::Clamp
+        && !leap_seconds::is_leap_second_minute(&utc) {
+        return Err(ConversionError::InvalidLeapSecond);
+    }
+
+    let minutes = days * calendar::MINUTES_PER_DAY + hour * 60 + minute;
+
+    if mode == LeapSecondMode::Smear {
+        // counting second 60 as the start of the next minute
+        let seconds = minutes * 60 + second;
+        return Ok(match leap_seconds::smear_window(seconds) {
+            Some(after) => smear((seconds as i128) * NANOS_PER_SECOND + nanos, after,
+                                 second == 60),
+            None => (seconds as i128) * NANOS_PER_SECOND + nanos
+        });
+    }
+
+    let second = if second == 60 {
+        nanos = NANOS_PER_SECOND - 1;
+        59
+    } else {
+        second
+    };
+
+    Ok(((minutes * 60 + second) as i128) * NANOS_PER_SECOND + nanos)
+}
+
+/// The inverse of `to_unix_nanos()` with `LeapSecondMode::Smear`, producing second 60 for
+/// instants during a leap second.
+pub fn from_smeared_unix_nanos(unix_nanos: i128) -> Result<TemporalFields, ConversionError> {
+    let seconds = unix_nanos.div_euclid(NANOS_PER_SECOND);
+
+    let after = match leap_seconds::smear_window(seconds as i64) {
+        Some(after) => after,
+        None => return from_unix_nanos(unix_nanos)
+    };
+
+    // nanoseconds since the smear started, in real seconds rather than smeared ones, rounded up
+    // so that smearing the result gives `unix_nanos` again
+    let start = ((after - leap_seconds::SMEAR_HALF_WINDOW) as i128) * NANOS_PER_SECOND;
+    let elapsed = ((unix_nanos - start) * SMEAR_SECONDS + SMEAR_SECONDS - 2) / (SMEAR_SECONDS - 1);
+    let real = start + elapsed;
+    let leap_second = (after as i128) * NANOS_PER_SECOND;
+
+    if real < leap_second {
+        from_unix_nanos(real)
+    } else {
+        // Unix time repeats a second for the leap second
+        let fields = from_unix_nanos(real - NANOS_PER_SECOND)?;
+        if real < leap_second + NANOS_PER_SECOND {
+            Ok(TemporalFields { second: Some(60), ..fields })
+        } else {
+            Ok(fields)
+        }
+    }
+}
+
+/// Smeared Unix nanoseconds for `unix_nanos`, which counts second 60 as the start of the next
+/// minute, in the smear window around the leap second just before `after`.
+fn smear(unix_nanos: i128, after: i64, is_leap_second: bool) -> i128 {
+    let start = ((after - leap_seconds::SMEAR_HALF_WINDOW) as i128) * NANOS_PER_SECOND;
+    let leap_second = (after as i128) * NANOS_PER_SECOND;
+    // nanoseconds since the smear started, in real seconds rather than smeared ones
+    let elapsed = if unix_nanos >= leap_second && !is_leap_second {
+        unix_nanos - start + NANOS_PER_SECOND
+    } else {
+        unix_nanos - start
+    };
+
+    start + elapsed * (SMEAR_SECONDS - 1) / SMEAR_SECONDS
+}
+
+/// Real seconds in a smear window
+const SMEAR_SECONDS: i128 = 2 * (leap_seconds::SMEAR_HALF_WINDOW as i128) + 1;
+
+/// The UTC fields for `unix_nanos` nanoseconds since 1970-01-01T00:00:00Z. Second 60 is never
+/// produced, since Unix time can't distinguish leap seconds.
+pub fn from_unix_nanos(unix_nanos: i128) -> Result<TemporalFields, ConversionError> {
+    let seconds = unix_nanos.div_euclid(NANOS_PER_SECOND);
+    let nanos = unix_nanos.rem_euclid(NANOS_PER_SECOND) as u32;
+
+    let days = seconds.div_euclid(calendar::SECONDS_PER_DAY as i128);
+    let second_of_day = seconds.rem_euclid(calendar::SECONDS_PER_DAY as i128) as u32;
+
+    let first = calendar::days_from_civil(YEAR_MIN as i64, 1, 1) as i128;
+    let last = calendar::days_from_civil(YEAR_MAX as i64, 12, 31) as i128;
+    if days < first || days > last {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    let (year, month, day) = calendar::civil_from_days(days as i64);
+
+    Ok(TemporalFields {
+        year: Some(year as u16),
+        month: Some(month),
+        day: Some(day),
+        hour: Some((second_of_day / 3600) as u8),
+        minute: Some((second_of_day / 60 % 60) as u8),
+        second: Some((second_of_day % 60) as u8),
+        frac_second: FractionalSecond::Nanoseconds(nanos),
+        offset: OffsetValue::UtcOffset(0),
+    })
+}
@@ -0,0 +1,378 @@
+//! ISO 8601 week dates like `2017-W02-7` and ordinal dates like `2017-015`, and text with the
+//! date in those forms.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::*;
+use super::parser::Parser;
+
+/// An ISO 8601 week date. Weeks start on Monday, and week 1 of a week-numbering year is the week
+/// with the year's first Thursday, so the first days of January can be in the last week of the
+/// previous year and the last days of December in week 1 of the next.
+///
+/// Formats and parses as `2017-W02-7`.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let week_date: IsoWeekDate = "2017-W02-7".parse().unwrap();
+/// let date = DateOnly::from_iso_week_date(&week_date).unwrap();
+/// assert_eq!(DateOnly::new(Some(2017), Some(1), Some(15)).unwrap(), date);
+/// assert_eq!("2017-W02-7", date.to_iso_week_date().unwrap().to_string());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct IsoWeekDate {
+    /// The week-numbering year, which is not always the calendar year of the date.
+    pub year: u16,
+    /// The week, in [1, 53]. Only some years have a week 53.
+    pub week: u8,
+    pub weekday: Weekday,
+}
+
+/// An ISO 8601 ordinal date: a year and the day of that year. Formats and parses as `2017-015`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OrdinalDate {
+    pub year: u16,
+    /// The day of the year, in [1, 366].
+    pub day: u16,
+}
+
+/// ISO 8601 text for a date-time value with the date written as a week date or an ordinal date,
+/// like `2017-W02-7T18:45:30.123+02:15`, for use with `format!()` or `to_string()`. Created by
+/// `format_iso_week_date()` and `format_ordinal_date()` on the date-time types.
+///
+/// Trailing missing time fields are left out, as in `2017-015T18:45`. An offset of zero is
+/// written as `Z`, and `OffsetValue::SpecifiedElsewhere` isn't written at all, since ISO 8601 has
+/// no way to express it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct IsoText {
+    date: DateForm,
+    fields: TemporalFields,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum DateForm {
+    Week(IsoWeekDate),
+    Ordinal(OrdinalDate),
+}
+
+impl IsoWeekDate {
+    /// The number of weeks, 52 or 53, in the week-numbering `year`.
+    pub fn weeks_in_year(year: u16) -> u8 {
+        // December 28 is always in the last week
+        week_of(calendar::days_from_civil(year as i64, 12, 28)).1
+    }
+}
+
+impl fmt::Display for IsoWeekDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-W{:02}-{}", self.year, self.week, self.weekday.number_from_monday())
+    }
+}
+
+impl FromStr for IsoWeekDate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<IsoWeekDate, ParseError> {
+        let mut p = Parser::new(s, ParseError::InvalidSyntax);
+        let week_date = week_date(&mut p)?;
+        p.end()?;
+
+        Ok(week_date)
+    }
+}
+
+impl fmt::Display for OrdinalDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:03}", self.year, self.day)
+    }
+}
+
+impl FromStr for OrdinalDate {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<OrdinalDate, ParseError> {
+        let mut p = Parser::new(s, ParseError::InvalidSyntax);
+        let ordinal_date = ordinal_date(&mut p)?;
+        p.end()?;
+
+        Ok(ordinal_date)
+    }
+}
+
+impl fmt::Display for IsoText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.date {
+            DateForm::Week(ref week_date) => write!(f, "{}", week_date)?,
+            DateForm::Ordinal(ref ordinal_date) => write!(f, "{}", ordinal_date)?,
+        }
+
+        let fields = &self.fields;
+        if let Some(hour) = fields.hour {
+            write!(f, "T{:02}", hour)?;
+        }
+        if let Some(minute) = fields.minute {
+            write!(f, ":{:02}", minute)?;
+        }
+        if let Some(second) = fields.second {
+            write!(f, ":{:02}", second)?;
+        }
+
+        match fields.frac_second {
+            FractionalSecond::Milliseconds(ms) => write!(f, ".{:03}", ms)?,
+            FractionalSecond::Microseconds(us) => write!(f, ".{:06}", us)?,
+            FractionalSecond::Nanoseconds(ns) => write!(f, ".{:09}", ns)?,
+            FractionalSecond::None => {}
+        }
+
+        match fields.offset {
+            OffsetValue::UtcOffset(0) => write!(f, "Z"),
+            OffsetValue::UtcOffset(o) => {
+                let sign = if o < 0 { '-' } else { '+' };
+                write!(f, "{}{:02}:{:02}", sign, o.abs() / 60, o.abs() % 60)
+            },
+            OffsetValue::None | OffsetValue::SpecifiedElsewhere => Ok(())
+        }
+    }
+}
+
+/// Days since 1970-01-01 for `week_date`. Returns an error if the week doesn't exist in that
+/// year.
+pub fn week_date_to_days(week_date: &IsoWeekDate) -> Result<i64, ConversionError> {
+    let weeks = IsoWeekDate::weeks_in_year(week_date.year);
+    check_in_range(week_date.week, 1, weeks, ConversionError::InvalidDate)?;
+
+    let jan_4 = calendar::days_from_civil(week_date.year as i64, 1, 4);
+    let week_1_monday = jan_4 - (calendar::weekday(jan_4).number_from_monday() as i64 - 1);
+
+    Ok(week_1_monday + (week_date.week as i64 - 1) * 7
+        + (week_date.weekday.number_from_monday() as i64 - 1))
+}
+
+/// The week date for `days` since 1970-01-01. Returns an error if the week-numbering year is
+/// outside the years Temporenc can represent, as it is for 0000-01-01.
+pub fn days_to_week_date(days: i64) -> Result<IsoWeekDate, ConversionError> {
+    let (year, week) = week_of(days);
+
+    if year < YEAR_MIN as i64 || year > YEAR_MAX as i64 {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    Ok(IsoWeekDate { year: year as u16, week, weekday: calendar::weekday(days) })
+}
+
+/// Days since 1970-01-01 for `ordinal_date`. Returns an error if the day doesn't exist in that
+/// year.
+pub fn ordinal_date_to_days(ordinal_date: &OrdinalDate) -> Result<i64, ConversionError> {
+    let year = ordinal_date.year as i64;
+    let days_in_year = if calendar::is_leap_year(year) { 366 } else { 365 };
+    check_in_range(ordinal_date.day, 1, days_in_year, ConversionError::InvalidDate)?;
+
+    Ok(calendar::days_from_civil(year, 1, 1) + ordinal_date.day as i64 - 1)
+}
+
+/// The ordinal date for `days` since 1970-01-01.
+pub fn days_to_ordinal_date(days: i64) -> Result<OrdinalDate, ConversionError> {
+    let year = date_fields(days)?.year.unwrap_or(0);
+    let jan_1 = calendar::days_from_civil(year as i64, 1, 1);
+
+    Ok(OrdinalDate { year, day: (days - jan_1 + 1) as u16 })
+}
+
+/// ISO 8601 text with the date as a week date or an ordinal date, for the types with a date and
+/// time.
+///
+/// ```
+/// use temporenc::*;
+///
+/// let dt = DateTime::parse_iso_week_date("2017-W02-7T18:45").unwrap();
+/// assert_eq!(Some(15), dt.day());
+/// assert_eq!(None, dt.second());
+/// assert_eq!("2017-015T18:45", dt.format_ordinal_date().unwrap().to_string());
+/// ```
+pub trait IsoDateText: Temporal {
+    /// Parse text with the date as a week date, like `2017-W02-7T18:45:30`. Everything after the
+    /// date is optional, as are the time fields after the hour.
+    fn parse_iso_week_date(text: &str) -> Result<Self, ParseError> {
+        parse(text, true)
+    }
+
+    /// Like `parse_iso_week_date()`, but with the date as an ordinal date, like `2017-015`.
+    fn parse_ordinal_date(text: &str) -> Result<Self, ParseError> {
+        parse(text, false)
+    }
+
+    /// Text with the date as a week date, as described by `IsoText`.
+    ///
+    /// Returns an error if any of year, month, and day are missing, if the date doesn't exist, or
+    /// if a field is present without the coarser ones (like a second without the minute).
+    fn format_iso_week_date(&self) -> Result<IsoText, ConversionError> {
+        format(&self.fields(), true)
+    }
+
+    /// Like `format_iso_week_date()`, but with the date as an ordinal date.
+    fn format_ordinal_date(&self) -> Result<IsoText, ConversionError> {
+        format(&self.fields(), false)
+    }
+}
+
+impl IsoDateText for DateTime {}
+impl IsoDateText for DateTimeOffset {}
+impl IsoDateText for DateTimeSubSecond {}
+impl IsoDateText for DateTimeSubSecondOffset {}
+
+/// Year, month and day for `days` since 1970-01-01, with the other fields absent.
+pub fn date_fields(days: i64) -> Result<TemporalFields, ConversionError> {
+    let (year, month, day) = calendar::civil_from_days(days);
+
+    if year < YEAR_MIN as i64 || year > YEAR_MAX as i64 {
+        return Err(ConversionError::OutOfRange);
+    }
+
+    Ok(TemporalFields {
+        year: Some(year as u16),
+        month: Some(month),
+        day: Some(day),
+        ..TemporalFields::default()
+    })
+}
+
+/// Text for `fields` with the date as a week date, or as an ordinal date if `week` is false.
+///
+/// Returns an error if any of year, month, and day are missing, if the date doesn't exist, or if
+/// a time field is present without the coarser ones, since ISO 8601 can only leave out fields at
+/// the end. The offset needs at least the hour.
+pub fn format(fields: &TemporalFields, week: bool) -> Result<IsoText, ConversionError> {
+    let days = calendar::fields_to_days(fields)?;
+
+    let has_offset = match fields.offset {
+        OffsetValue::UtcOffset(_) => true,
+        OffsetValue::None | OffsetValue::SpecifiedElsewhere => false
+    };
+    if (fields.minute.is_some() && fields.hour.is_none())
+        || (fields.second.is_some() && fields.minute.is_none())
+        || (fields.has_sub_second() && fields.second.is_none())
+        || (has_offset && fields.hour.is_none()) {
+        return Err(ConversionError::MissingField);
+    }
+
+    let date = if week {
+        DateForm::Week(days_to_week_date(days)?)
+    } else {
+        DateForm::Ordinal(days_to_ordinal_date(days)?)
+    };
+
+    Ok(IsoText { date, fields: *fields })
+}
+
+/// Parse text like `2017-W02-7T18:45:30.123+02:15`, or `2017-015T18:45:30.123+02:15` if `week` is
+/// false. Everything after the date is optional, as are the fields after the hour.
+pub fn parse<T: Temporal>(text: &str, week: bool) -> Result<T, ParseError> {
+    let mut p = Parser::new(text, ParseError::InvalidSyntax);
+
+    let days = if week {
+        week_date_to_days(&week_date(&mut p)?)
+    } else {
+        ordinal_date_to_days(&ordinal_date(&mut p)?)
+    };
+    let mut fields = days.and_then(date_fields).map_err(|_| ParseError::InvalidFieldValue)?;
+
+    if p.eat(b'T') {
+        fields.hour = Some(p.digits(2)? as u8);
+        if p.eat(b':') {
+            fields.minute = Some(p.digits(2)? as u8);
+            if p.eat(b':') {
+                fields.second = Some(p.digits(2)? as u8);
+                if p.eat(b'.') {
+                    fields.frac_second = frac_second(&mut p)?;
+                }
+            }
+        }
+
+        if p.eat(b'Z') {
+            fields.offset = OffsetValue::UtcOffset(0);
+        } else {
+            let negative = p.eat(b'-');
+            if negative || p.eat(b'+') {
+                let hours = p.digits(2)? as i16;
+                p.expect(b':')?;
+                let minutes = check_in_range(p.digits(2)?, MINUTE_MIN as u32, MINUTE_MAX as u32,
+                                             ParseError::InvalidFieldValue)?;
+                let minutes = hours * 60 + minutes as i16;
+                fields.offset = OffsetValue::UtcOffset(if negative { -minutes } else { minutes });
+            }
+        }
+    }
+
+    p.end()?;
+
+    T::from_fields(fields).map_err(|_| ParseError::InvalidFieldValue)
+}
+
+/// (week-numbering year, week) for `days` since 1970-01-01
+fn week_of(days: i64) -> (i64, u8) {
+    // a week belongs to the year its Thursday is in
+    let thursday = days + 4 - calendar::weekday(days).number_from_monday() as i64;
+    let (year, _, _) = calendar::civil_from_days(thursday);
+    let week = (thursday - calendar::days_from_civil(year, 1, 1)) / 7 + 1;
+
+    (year, week as u8)
+}
+
+type IsoParser<'a> = Parser<'a, ParseError>;
+
+fn year(p: &mut IsoParser) -> Result<u16, ParseError> {
+    let year = p.digits(4)?;
+
+    check_in_range(year, YEAR_MIN as u32, YEAR_MAX as u32, ParseError::InvalidFieldValue)
+        .map(|y| y as u16)
+}
+
+/// `YYYY-Www-D`, checking that the week exists
+fn week_date(p: &mut IsoParser) -> Result<IsoWeekDate, ParseError> {
+    let year = year(p)?;
+    p.expect(b'-')?;
+    p.expect(b'W')?;
+    let week = p.digits(2)? as u8;
+    p.expect(b'-')?;
+
+    let weekday = match p.digits(1)? {
+        1 => Weekday::Monday,
+        2 => Weekday::Tuesday,
+        3 => Weekday::Wednesday,
+        4 => Weekday::Thursday,
+        5 => Weekday::Friday,
+        6 => Weekday::Saturday,
+        7 => Weekday::Sunday,
+        _ => return Err(ParseError::InvalidFieldValue)
+    };
+
+    let week_date = IsoWeekDate { year, week, weekday };
+    week_date_to_days(&week_date).map_err(|_| ParseError::InvalidFieldValue)?;
+
+    Ok(week_date)
+}
+
+/// `YYYY-DDD`, checking that the day exists
+fn ordinal_date(p: &mut IsoParser) -> Result<OrdinalDate, ParseError> {
+    let year = year(p)?;
+    p.expect(b'-')?;
+
+    let ordinal_date = OrdinalDate { year, day: p.digits(3)? as u16 };
+    ordinal_date_to_days(&ordinal_date).map_err(|_| ParseError::InvalidFieldValue)?;
+
+    Ok(ordinal_date)
+}
+
+/// 3, 6 or 9 digits, for milliseconds, microseconds or nanoseconds
+fn frac_second(p: &mut IsoParser) -> Result<FractionalSecond, ParseError> {
+    let count = p.rest().iter().take_while(|b| b.is_ascii_digit()).count();
+
+    match count {
+        3 => Ok(FractionalSecond::Milliseconds(p.digits(3)? as u16)),
+        6 => Ok(FractionalSecond::Microseconds(p.digits(6)?)),
+        9 => Ok(FractionalSecond::Nanoseconds(p.digits(9)?)),
+        _ => Err(ParseError::InvalidSyntax)
+    }
+}
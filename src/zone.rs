@@ -0,0 +1,196 @@
+use super::*;
+
+/// How to choose an instant for a local time that occurs twice (a "fold", as when clocks are
+/// set back) or not at all (a "gap", as when clocks are set forward).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Disambiguation {
+    /// `Earlier` for folds and `Later` for gaps, which is what most other time libraries do.
+    Compatible,
+    /// In a fold, the first of the two instants. In a gap, the local time is moved backward by
+    /// the length of the gap.
+    Earlier,
+    /// In a fold, the second of the two instants. In a gap, the local time is moved forward by
+    /// the length of the gap.
+    Later,
+    /// Return an error for folds and gaps.
+    Reject,
+}
+
+/// Rules for a UTC offset that varies over time, like a time zone. Implementors need only provide
+/// the offset in effect at a given instant.
+pub trait TimeZoneRules {
+    /// The offset from UTC in seconds (positive east of UTC) in effect at the instant
+    /// `unix_seconds` seconds after 1970-01-01T00:00:00Z, not counting leap seconds.
+    fn offset_at_unix_seconds(&self, unix_seconds: i64) -> i32;
+
+    /// Find the offset in effect at the local time `date_time`, moving the local time if it is in
+    /// a gap as described by `disambiguation`.
+    ///
+    /// Returns an error if any of year, month, day, hour, and minute are missing, if the offset
+    /// is not representable in Temporenc, or if `disambiguation` is `Reject` and the local time is
+    /// in a fold or gap.
+    fn resolve_date_time(&self, date_time: &DateTime, disambiguation: Disambiguation)
+                         -> Result<DateTimeOffset, ConversionError> {
+        let fields = resolve_local(self, date_time.fields(), disambiguation)?;
+
+        DateTimeOffset::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `resolve_date_time()`, but keeping the fractional second.
+    fn resolve_date_time_subsecond(&self, date_time: &DateTimeSubSecond,
+                                   disambiguation: Disambiguation)
+                                   -> Result<DateTimeSubSecondOffset, ConversionError> {
+        let fields = resolve_local(self, date_time.fields(), disambiguation)?;
+
+        DateTimeSubSecondOffset::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `resolve_date_time()`, but storing an offset that Temporenc can't represent (like
+    /// local mean time) as described by `rounding` instead of returning an error.
+    fn resolve_date_time_rounding(&self, date_time: &DateTime, disambiguation: Disambiguation,
+                                  rounding: OffsetRounding)
+                                  -> Result<(DateTimeOffset, OffsetAdjustment), ConversionError> {
+        let (fields, adjustment) =
+            resolve_local_rounding(self, date_time.fields(), disambiguation, rounding)?;
+
+        DateTimeOffset::from_fields(fields)
+            .map(|dto| (dto, adjustment))
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `resolve_date_time_rounding()`, but keeping the fractional second.
+    fn resolve_date_time_subsecond_rounding(&self, date_time: &DateTimeSubSecond,
+                                            disambiguation: Disambiguation,
+                                            rounding: OffsetRounding)
+                                            -> Result<(DateTimeSubSecondOffset, OffsetAdjustment),
+                                                      ConversionError> {
+        let (fields, adjustment) =
+            resolve_local_rounding(self, date_time.fields(), disambiguation, rounding)?;
+
+        DateTimeSubSecondOffset::from_fields(fields)
+            .map(|dtso| (dtso, adjustment))
+            .map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// The same instant as `date_time`, expressed at the offset in effect in this time zone at
+    /// that instant.
+    ///
+    /// Returns an error if the offset of `date_time` isn't a `UtcOffset`, if any of year, month,
+    /// day, hour, and minute are missing, or if the offset in this time zone is not representable
+    /// in Temporenc.
+    fn to_local_date_time(&self, date_time: &DateTimeOffset)
+                          -> Result<DateTimeOffset, ConversionError> {
+        let fields = to_local(self, date_time.fields())?;
+
+        DateTimeOffset::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+
+    /// Like `to_local_date_time()`, but keeping the fractional second.
+    fn to_local_date_time_subsecond(&self, date_time: &DateTimeSubSecondOffset)
+                                    -> Result<DateTimeSubSecondOffset, ConversionError> {
+        let fields = to_local(self, date_time.fields())?;
+
+        DateTimeSubSecondOffset::from_fields(fields).map_err(|_| ConversionError::OutOfRange)
+    }
+}
+
+fn to_local<R: TimeZoneRules + ?Sized>(rules: &R, fields: TemporalFields)
+                                       -> Result<TemporalFields, ConversionError> {
+    let utc = calendar::change_offset(&fields, 0)?;
+    let offset = rules.offset_at_unix_seconds(wall_seconds(&utc)?) as i64;
+
+    calendar::change_offset(&utc, offset_seconds_to_minutes(offset)?)
+}
+
+/// Seconds since the epoch for the wall clock time in `fields`, ignoring the offset. A missing
+/// second is treated as the start of the minute, since no time zone changes offset partway
+/// through a minute.
+fn wall_seconds(fields: &TemporalFields) -> Result<i64, ConversionError> {
+    let days = calendar::fields_to_days(fields)?;
+
+    match (fields.hour, fields.minute) {
+        (Some(h), Some(m)) => Ok(days * calendar::SECONDS_PER_DAY + (h as i64) * 3600
+            + (m as i64) * 60 + fields.second.unwrap_or(0) as i64),
+        _ => Err(ConversionError::MissingField)
+    }
+}
+
+fn resolve_local<R: TimeZoneRules + ?Sized>(rules: &R, fields: TemporalFields,
+                                            disambiguation: Disambiguation)
+                                            -> Result<TemporalFields, ConversionError> {
+    let (offset, shift) = find_offset(rules, &fields, disambiguation)?;
+
+    let offset_minutes = offset_seconds_to_minutes(offset)?;
+    // offsets are whole quarter hours, so the shift is whole minutes
+    let shifted = if shift == 0 {
+        fields
+    } else {
+        calendar::add_minutes(&fields, shift / 60)?
+    };
+
+    Ok(TemporalFields {
+        offset: OffsetValue::UtcOffset(offset_minutes),
+        ..shifted
+    })
+}
+
+fn resolve_local_rounding<R: TimeZoneRules + ?Sized>(rules: &R, fields: TemporalFields,
+                                                     disambiguation: Disambiguation,
+                                                     rounding: OffsetRounding)
+                                                     -> Result<(TemporalFields, OffsetAdjustment),
+                                                               ConversionError> {
+    let (offset, shift) = find_offset(rules, &fields, disambiguation)?;
+
+    offset_rounding::round_offset(&fields, shift, offset, rounding)
+}
+
+/// The offset in seconds in effect at the local time in `fields`, and how many seconds to move
+/// the local time by if it is in a gap.
+fn find_offset<R: TimeZoneRules + ?Sized>(rules: &R, fields: &TemporalFields,
+                                          disambiguation: Disambiguation)
+                                          -> Result<(i64, i64), ConversionError> {
+    let local = wall_seconds(fields)?;
+
+    // Assumes there is at most one transition within a day of the local time, which is true of
+    // every real time zone.
+    let before = rules.offset_at_unix_seconds(local - calendar::SECONDS_PER_DAY) as i64;
+    let after = rules.offset_at_unix_seconds(local + calendar::SECONDS_PER_DAY) as i64;
+    let before_valid = rules.offset_at_unix_seconds(local - before) as i64 == before;
+    let after_valid = before != after
+        && rules.offset_at_unix_seconds(local - after) as i64 == after;
+
+    // (offset, seconds to move the local time by)
+    Ok(match (before_valid, after_valid) {
+        (true, false) => (before, 0),
+        (false, true) => (after, 0),
+        (true, true) => {
+            // the larger offset corresponds to the earlier instant
+            match disambiguation {
+                Disambiguation::Compatible | Disambiguation::Earlier => (before.max(after), 0),
+                Disambiguation::Later => (before.min(after), 0),
+                Disambiguation::Reject => return Err(ConversionError::AmbiguousLocalTime),
+            }
+        },
+        (false, false) => {
+            match disambiguation {
+                Disambiguation::Compatible | Disambiguation::Later => (after, after - before),
+                Disambiguation::Earlier => (before, before - after),
+                Disambiguation::Reject => return Err(ConversionError::NonexistentLocalTime),
+            }
+        }
+    })
+}
+
+/// Returns an error if the offset is not a valid `OffsetValue::UtcOffset`.
+fn offset_seconds_to_minutes(offset_seconds: i64) -> Result<i16, ConversionError> {
+    if offset_seconds % (15 * 60) != 0 {
+        return Err(ConversionError::InvalidOffset);
+    }
+
+    let minutes = offset_seconds / 60;
+    if minutes < OFFSET_MIN as i64 || minutes > OFFSET_MAX as i64 {
+        return Err(ConversionError::InvalidOffset);
+    }
+
+    Ok(minutes as i16)
+}
@@ -0,0 +1,220 @@
+//! Time zones loaded from TZif files, the format of the system zoneinfo database (typically
+//! `/usr/share/zoneinfo`). Requires the `zoneinfo` feature.
+//!
+//! Unlike the rest of the crate, this module allocates, since the number of transitions in a
+//! zone isn't known ahead of time.
+//!
+//! ```no_run
+//! use temporenc::*;
+//! use temporenc::zoneinfo::TimeZone;
+//!
+//! let amsterdam = TimeZone::load("Europe/Amsterdam").unwrap();
+//! let local = DateTime::new(Some(2017), Some(7), Some(1), Some(12), Some(0), Some(0)).unwrap();
+//! let dto = amsterdam.resolve_date_time(&local, Disambiguation::Reject).unwrap();
+//! assert_eq!(OffsetValue::UtcOffset(120), dto.offset());
+//! ```
+
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str;
+
+use super::{PosixTz, TimeZoneRules};
+
+/// Used when loading a `TimeZone`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ZoneInfoError {
+    /// The zone name is not a relative path of the form used by IANA names, like
+    /// `America/New_York`.
+    InvalidName,
+    IoError,
+    /// The data is not valid TZif.
+    InvalidData,
+}
+
+/// The rules for a time zone as described by a TZif file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TimeZone {
+    /// Instants at which the offset changes, in ascending order
+    transitions: Vec<i64>,
+    /// Index into `type_offsets` for the offset that starts at each transition
+    transition_types: Vec<u8>,
+    /// Offset in seconds for each local time type
+    type_offsets: Vec<i32>,
+    /// Rules for instants after the last transition, from the footer of version 2+ files
+    footer: Option<PosixTz>,
+}
+
+impl TimeZone {
+    /// Load a zone by its IANA name, like `Europe/Amsterdam`, from the directory named by the
+    /// `TZDIR` environment variable, or `/usr/share/zoneinfo` if that is not set.
+    pub fn load(name: &str) -> Result<TimeZone, ZoneInfoError> {
+        let dir = env::var_os("TZDIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_ZONEINFO_DIR));
+
+        TimeZone::load_from_dir(dir, name)
+    }
+
+    /// Load a zone by its IANA name, like `Europe/Amsterdam`, from a zoneinfo directory.
+    pub fn load_from_dir<P: AsRef<Path>>(dir: P, name: &str) -> Result<TimeZone, ZoneInfoError> {
+        if !is_valid_name(name) {
+            return Err(ZoneInfoError::InvalidName);
+        }
+
+        let mut bytes = Vec::new();
+        File::open(dir.as_ref().join(name))
+            .and_then(|mut f| f.read_to_end(&mut bytes))
+            .map_err(|_| ZoneInfoError::IoError)?;
+
+        TimeZone::from_tzif(&bytes)
+    }
+
+    /// Parse the contents of a TZif file (RFC 8536), e.g. one embedded in the application.
+    pub fn from_tzif(bytes: &[u8]) -> Result<TimeZone, ZoneInfoError> {
+        let v1_header = Header::parse(bytes)?;
+        let v1_len = HEADER_LEN + v1_header.data_len(4);
+
+        if v1_header.version == 0 {
+            return TimeZone::parse_data(&v1_header, &bytes[HEADER_LEN..], 4);
+        }
+
+        // Version 2+ files repeat the data with 64-bit times after the version 1 data, followed
+        // by a footer with a POSIX TZ string between newlines.
+        let rest = bytes.get(v1_len..).ok_or(ZoneInfoError::InvalidData)?;
+        let v2_header = Header::parse(rest)?;
+        let mut zone = TimeZone::parse_data(&v2_header, &rest[HEADER_LEN..], 8)?;
+
+        let footer = &rest[(HEADER_LEN + v2_header.data_len(8))..];
+        if footer.len() < 2 || footer[0] != b'\n' {
+            return Err(ZoneInfoError::InvalidData);
+        }
+        let tz_end = footer[1..].iter().position(|&b| b == b'\n')
+            .ok_or(ZoneInfoError::InvalidData)? + 1;
+        if tz_end > 1 {
+            let tz = str::from_utf8(&footer[1..tz_end]).map_err(|_| ZoneInfoError::InvalidData)?;
+            zone.footer = Some(PosixTz::parse(tz).map_err(|_| ZoneInfoError::InvalidData)?);
+        }
+
+        Ok(zone)
+    }
+
+    fn parse_data(header: &Header, data: &[u8], time_size: usize)
+                  -> Result<TimeZone, ZoneInfoError> {
+        if data.len() < header.data_len(time_size) || header.type_count == 0 {
+            return Err(ZoneInfoError::InvalidData);
+        }
+
+        let (times, data) = data.split_at(header.transition_count * time_size);
+        let (types, data) = data.split_at(header.transition_count);
+        let type_records = &data[..(header.type_count * 6)];
+
+        let transitions: Vec<i64> = times.chunks(time_size)
+            .map(|t| if time_size == 4 { read_i32(t) as i64 } else { read_i64(t) })
+            .collect();
+
+        if transitions.windows(2).any(|w| w[0] >= w[1])
+            || types.iter().any(|&t| (t as usize) >= header.type_count) {
+            return Err(ZoneInfoError::InvalidData);
+        }
+
+        Ok(TimeZone {
+            transitions,
+            transition_types: types.to_vec(),
+            type_offsets: type_records.chunks(6).map(read_i32).collect(),
+            footer: None,
+        })
+    }
+}
+
+impl TimeZoneRules for TimeZone {
+    fn offset_at_unix_seconds(&self, unix_seconds: i64) -> i32 {
+        // number of transitions at or before the instant
+        let index = match self.transitions.binary_search(&unix_seconds) {
+            Ok(i) => i + 1,
+            Err(i) => i
+        };
+
+        if index == self.transitions.len() {
+            if let Some(ref footer) = self.footer {
+                return footer.offset_at_unix_seconds(unix_seconds);
+            }
+        }
+
+        if index == 0 {
+            // before the first transition, RFC 8536 says to use the first type
+            self.type_offsets[0]
+        } else {
+            self.type_offsets[self.transition_types[index - 1] as usize]
+        }
+    }
+}
+
+struct Header {
+    version: u8,
+    ut_indicator_count: usize,
+    std_indicator_count: usize,
+    leap_count: usize,
+    transition_count: usize,
+    type_count: usize,
+    char_count: usize,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> Result<Header, ZoneInfoError> {
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != b"TZif" {
+            return Err(ZoneInfoError::InvalidData);
+        }
+
+        let version = match bytes[4] {
+            0 => 0,
+            v @ b'2'..=b'9' => v - b'0',
+            _ => return Err(ZoneInfoError::InvalidData)
+        };
+
+        let count = |i: usize| read_i32(&bytes[(20 + i * 4)..]) as u32 as usize;
+
+        Ok(Header {
+            version,
+            ut_indicator_count: count(0),
+            std_indicator_count: count(1),
+            leap_count: count(2),
+            transition_count: count(3),
+            type_count: count(4),
+            char_count: count(5),
+        })
+    }
+
+    /// Length of the data block following the header.
+    fn data_len(&self, time_size: usize) -> usize {
+        self.transition_count * (time_size + 1)
+            + self.type_count * 6
+            + self.char_count
+            + self.leap_count * (time_size + 4)
+            + self.std_indicator_count
+            + self.ut_indicator_count
+    }
+}
+
+fn read_i32(bytes: &[u8]) -> i32 {
+    ((bytes[0] as i32) << 24) | ((bytes[1] as i32) << 16) | ((bytes[2] as i32) << 8)
+        | (bytes[3] as i32)
+}
+
+fn read_i64(bytes: &[u8]) -> i64 {
+    ((read_i32(bytes) as i64) << 32) | (read_i32(&bytes[4..]) as u32 as i64)
+}
+
+/// Names are relative paths made of the characters used in IANA names, with no `.` or `..`
+/// segments, so they can't escape the zoneinfo directory.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('/').all(|segment| {
+            !segment.is_empty() && segment != "." && segment != ".."
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || "_-+.".contains(c))
+        })
+}
+
+const HEADER_LEN: usize = 44;
+const DEFAULT_ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
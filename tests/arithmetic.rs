@@ -0,0 +1,213 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn date(year: u16, month: u8, day: u8) -> DateOnly {
+    DateOnly::new(Some(year), Some(month), Some(day)).unwrap()
+}
+
+fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: Option<u8>) -> DateTime {
+    DateTime::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), second).unwrap()
+}
+
+fn time(hour: Option<u8>, minute: Option<u8>, second: Option<u8>) -> TimeOnly {
+    TimeOnly::new(hour, minute, second).unwrap()
+}
+
+fn dts(second: u8, frac: FractionalSecond) -> DateTimeSubSecond {
+    DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(second), frac)
+        .unwrap()
+}
+
+#[test]
+fn add_exact_durations() {
+    let start = dt(2017, 1, 15, 18, 45, Some(30));
+
+    assert_eq!(Ok(dt(2017, 1, 15, 18, 46, Some(0))),
+               start.checked_add(SignedDuration::from_secs(30)));
+    assert_eq!(Ok(dt(2017, 1, 15, 19, 0, Some(30))),
+               start.checked_add(SignedDuration::from_minutes(15)));
+    assert_eq!(Ok(dt(2017, 1, 16, 2, 45, Some(30))),
+               start.checked_add(SignedDuration::from_hours(8)));
+    assert_eq!(Ok(dt(2017, 3, 1, 18, 45, Some(30))),
+               start.checked_add(SignedDuration::from_days(45)));
+    assert_eq!(Ok(dt(2016, 12, 31, 23, 59, Some(59))),
+               dt(2017, 1, 1, 0, 0, Some(0)).checked_sub(SignedDuration::from_secs(1)));
+    assert_eq!(Ok(dt(2016, 2, 29, 0, 0, Some(0))),
+               dt(2016, 3, 1, 0, 0, Some(0)).checked_sub(SignedDuration::from_days(1)));
+    assert_eq!(Ok(dt(2017, 1, 15, 18, 45, Some(30))),
+               start.checked_add(SignedDuration::default()));
+}
+
+#[test]
+fn add_negative_duration() {
+    let start = dt(2017, 1, 15, 18, 45, Some(30));
+
+    assert_eq!(start.checked_sub(SignedDuration::from_minutes(90)),
+               start.checked_add(SignedDuration::from_minutes(-90)));
+}
+
+#[test]
+fn missing_finer_fields_are_unchanged() {
+    let no_second = dt(2017, 1, 15, 18, 45, None);
+    let no_time = DateTime::new(Some(2017), Some(1), Some(31), None, None, None).unwrap();
+
+    assert_eq!(Ok(dt(2017, 1, 15, 20, 5, None)),
+               no_second.checked_add(SignedDuration::from_minutes(80)));
+    assert_eq!(Ok(DateTime::new(Some(2017), Some(2), Some(1), None, None, None).unwrap()),
+               no_time.checked_add(SignedDuration::from_days(1)));
+}
+
+#[test]
+fn missing_affected_fields() {
+    let no_second = dt(2017, 1, 15, 18, 45, None);
+    let no_year = DateTime::new(None, Some(1), Some(15), Some(18), Some(45), Some(0)).unwrap();
+
+    assert_eq!(Err(ConversionError::MissingField),
+               no_second.checked_add(SignedDuration::from_secs(90)));
+    assert_eq!(Err(ConversionError::MissingField),
+               no_year.checked_add(SignedDuration::from_hours(1)));
+    assert_eq!(Err(ConversionError::MissingField),
+               dts(0, FractionalSecond::None).checked_add(SignedDuration::from_millis(1)));
+}
+
+#[test]
+fn date_only() {
+    assert_eq!(Ok(date(2017, 2, 1)), date(2017, 1, 31).checked_add(SignedDuration::from_days(1)));
+    assert_eq!(Err(ConversionError::PrecisionLoss),
+               date(2017, 1, 31).checked_add(SignedDuration::from_hours(1)));
+}
+
+#[test]
+fn time_only() {
+    let evening = time(Some(23), Some(30), None);
+
+    assert_eq!(Ok(time(Some(23), Some(59), None)),
+               evening.checked_add(SignedDuration::from_minutes(29)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               evening.checked_add(SignedDuration::from_minutes(30)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               evening.checked_sub(SignedDuration::from_days(1)));
+    assert_eq!(Ok(time(Some(11), None, None)),
+               time(Some(9), None, None).checked_add(SignedDuration::from_hours(2)));
+    assert_eq!(Err(ConversionError::MissingField),
+               time(None, Some(30), None).checked_add(SignedDuration::from_minutes(10)));
+}
+
+#[test]
+fn no_subsecond_component() {
+    assert_eq!(Err(ConversionError::PrecisionLoss),
+               dt(2017, 1, 15, 18, 45, Some(30)).checked_add(SignedDuration::from_millis(500)));
+}
+
+#[test]
+fn frac_second_precision() {
+    let millis = dts(30, FractionalSecond::Milliseconds(250));
+
+    assert_eq!(Ok(dts(30, FractionalSecond::Milliseconds(750))),
+               millis.checked_add(SignedDuration::from_millis(500)));
+    assert_eq!(Ok(dts(31, FractionalSecond::Milliseconds(250))),
+               millis.checked_add(SignedDuration::from_secs(1)));
+    assert_eq!(Ok(dts(31, FractionalSecond::Milliseconds(0))),
+               millis.checked_add(SignedDuration::from_millis(750)));
+    assert_eq!(Ok(dts(30, FractionalSecond::Microseconds(250_001))),
+               millis.checked_add(SignedDuration::from_micros(1)));
+    assert_eq!(Ok(dts(29, FractionalSecond::Nanoseconds(999_999_999))),
+               dts(30, FractionalSecond::Microseconds(0))
+                   .checked_sub(SignedDuration::from_nanos(1)));
+    // seconds don't need a fractional second
+    assert_eq!(Ok(dts(31, FractionalSecond::None)),
+               dts(30, FractionalSecond::None).checked_add(SignedDuration::from_secs(1)));
+}
+
+#[test]
+fn offset_unchanged() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(23), Some(45),
+                                            Some(0), FractionalSecond::None,
+                                            OffsetValue::UtcOffset(135)).unwrap();
+
+    assert_eq!(DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(16), Some(0), Some(15),
+                                            Some(0), FractionalSecond::None,
+                                            OffsetValue::UtcOffset(135)).unwrap(),
+               dtso.checked_add(SignedDuration::from_minutes(30)).unwrap());
+}
+
+#[test]
+fn leap_second() {
+    let leap = dt(2016, 12, 31, 23, 59, Some(60));
+
+    assert_eq!(Ok(dt(2017, 1, 1, 23, 59, Some(60))),
+               leap.checked_add(SignedDuration::from_days(1)));
+    assert_eq!(Ok(dt(2017, 1, 1, 0, 0, Some(1))), leap.checked_add(SignedDuration::from_secs(1)));
+}
+
+#[test]
+fn out_of_range() {
+    assert_eq!(Err(ConversionError::OutOfRange),
+               dt(4094, 12, 31, 23, 59, Some(59)).checked_add(SignedDuration::from_secs(1)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               date(0, 1, 1).checked_sub(SignedDuration::from_days(1)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               date(2017, 1, 1).checked_add(SignedDuration::from_days(i64::MAX)));
+
+    let nanos = dts(30, FractionalSecond::Nanoseconds(0));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               nanos.checked_add(SignedDuration::from_nanos(i128::MAX)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               nanos.checked_sub(SignedDuration::from_nanos(i128::MIN)));
+}
+
+#[test]
+fn add_months() {
+    let jan_31 = date(2017, 1, 31);
+
+    assert_eq!(Ok(date(2017, 2, 28)), jan_31.checked_add_months(1, MonthEnd::Clamp));
+    assert_eq!(Ok(date(2016, 2, 29)), date(2016, 1, 31).checked_add_months(1, MonthEnd::Clamp));
+    assert_eq!(Ok(date(2017, 3, 3)), jan_31.checked_add_months(1, MonthEnd::Overflow));
+    assert_eq!(Ok(date(2016, 3, 2)), date(2016, 1, 31).checked_add_months(1, MonthEnd::Overflow));
+    assert_eq!(Err(ConversionError::InvalidDate), jan_31.checked_add_months(1, MonthEnd::Reject));
+    assert_eq!(Ok(date(2017, 3, 31)), jan_31.checked_add_months(2, MonthEnd::Reject));
+    assert_eq!(Ok(date(2016, 11, 30)), jan_31.checked_sub_months(2, MonthEnd::Clamp));
+    assert_eq!(Ok(date(2019, 4, 30)), jan_31.checked_add_months(27, MonthEnd::Clamp));
+}
+
+#[test]
+fn add_months_keeps_time() {
+    assert_eq!(Ok(dt(2017, 2, 28, 18, 45, None)),
+               dt(2017, 1, 31, 18, 45, None).checked_add_months(1, MonthEnd::Clamp));
+}
+
+#[test]
+fn add_months_missing_fields() {
+    let no_day = DateOnly::new(Some(2017), Some(1), None).unwrap();
+    let no_year = DateOnly::new(None, Some(1), Some(15)).unwrap();
+
+    assert_eq!(Ok(DateOnly::new(Some(2016), Some(12), None).unwrap()),
+               no_day.checked_sub_months(1, MonthEnd::Reject));
+    assert_eq!(Err(ConversionError::MissingField), no_year.checked_add_months(1, MonthEnd::Clamp));
+    assert_eq!(Err(ConversionError::MissingField),
+               time(Some(12), None, None).checked_add_months(1, MonthEnd::Clamp));
+}
+
+#[test]
+fn add_years() {
+    let leap_day = date(2016, 2, 29);
+
+    assert_eq!(Ok(date(2017, 2, 28)), leap_day.checked_add_years(1, MonthEnd::Clamp));
+    assert_eq!(Ok(date(2017, 3, 1)), leap_day.checked_add_years(1, MonthEnd::Overflow));
+    assert_eq!(Ok(date(2020, 2, 29)), leap_day.checked_add_years(4, MonthEnd::Reject));
+    assert_eq!(Ok(date(2000, 2, 29)), leap_day.checked_sub_years(16, MonthEnd::Reject));
+}
+
+#[test]
+fn add_months_out_of_range() {
+    assert_eq!(Err(ConversionError::OutOfRange),
+               date(4094, 12, 1).checked_add_months(1, MonthEnd::Clamp));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               date(4094, 12, 31).checked_add_months(0, MonthEnd::Overflow)
+                   .and_then(|d| d.checked_add_years(1, MonthEnd::Clamp)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               date(0, 6, 1).checked_sub_years(1, MonthEnd::Clamp));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               date(2017, 1, 1).checked_add_years(i32::MAX, MonthEnd::Clamp));
+}
@@ -0,0 +1,162 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+    DateTime::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second))
+        .unwrap()
+}
+
+fn partial(year: Option<u16>, month: Option<u8>, day: Option<u8>, hour: Option<u8>,
+           minute: Option<u8>, second: Option<u8>) -> DateTime {
+    DateTime::new(year, month, day, hour, minute, second).unwrap()
+}
+
+fn dts(second: u8, frac: FractionalSecond) -> DateTimeSubSecond {
+    DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(second), frac)
+        .unwrap()
+}
+
+#[test]
+fn truncate_zero() {
+    let value = dt(2017, 5, 15, 18, 45, 30);
+    let truncate = |unit| value.truncate_to_unit(unit, FinerFields::Zero).unwrap();
+
+    assert_eq!(dt(2017, 5, 15, 18, 45, 30), truncate(Unit::Second));
+    assert_eq!(dt(2017, 5, 15, 18, 45, 0), truncate(Unit::Minute));
+    assert_eq!(dt(2017, 5, 15, 18, 0, 0), truncate(Unit::Hour));
+    assert_eq!(dt(2017, 5, 15, 0, 0, 0), truncate(Unit::Day));
+    assert_eq!(dt(2017, 5, 1, 0, 0, 0), truncate(Unit::Month));
+    assert_eq!(dt(2017, 1, 1, 0, 0, 0), truncate(Unit::Year));
+}
+
+#[test]
+fn truncate_missing() {
+    let value = dt(2017, 5, 15, 18, 45, 30);
+    let truncate = |unit| value.truncate_to_unit(unit, FinerFields::Missing).unwrap();
+
+    assert_eq!(partial(Some(2017), Some(5), Some(15), Some(18), Some(45), None),
+               truncate(Unit::Minute));
+    assert_eq!(partial(Some(2017), Some(5), Some(15), None, None, None), truncate(Unit::Day));
+    assert_eq!(partial(Some(2017), Some(5), None, None, None, None), truncate(Unit::Month));
+    assert_eq!(partial(Some(2017), None, None, None, None, None), truncate(Unit::Year));
+}
+
+#[test]
+fn truncate_minute_buckets() {
+    let value = dt(2017, 5, 15, 18, 44, 30);
+    let truncate = |n| value.truncate_to_unit(Unit::Minutes(n), FinerFields::Zero);
+
+    assert_eq!(Ok(dt(2017, 5, 15, 18, 44, 0)), truncate(1));
+    assert_eq!(Ok(dt(2017, 5, 15, 18, 30, 0)), truncate(15));
+    assert_eq!(Ok(dt(2017, 5, 15, 18, 0, 0)), truncate(60));
+    // 90 minute buckets start at 18:00
+    assert_eq!(Ok(dt(2017, 5, 15, 18, 0, 0)), truncate(90));
+    // 7 minute buckets start at midnight: 18:44 is minute 1124, so 1120 = 18:40
+    assert_eq!(Ok(dt(2017, 5, 15, 18, 40, 0)), truncate(7));
+    assert_eq!(Ok(dt(2017, 5, 15, 0, 0, 0)), truncate(1440));
+    assert_eq!(Err(ConversionError::InvalidUnit), truncate(0));
+    assert_eq!(Err(ConversionError::InvalidUnit), truncate(1441));
+}
+
+#[test]
+fn truncate_partial() {
+    let no_year = partial(None, Some(5), Some(15), Some(18), Some(45), Some(30));
+    let no_hour = partial(Some(2017), Some(5), Some(15), None, Some(45), Some(30));
+
+    assert_eq!(Ok(partial(None, Some(5), Some(1), Some(0), Some(0), Some(0))),
+               no_year.truncate_to_unit(Unit::Month, FinerFields::Zero));
+    assert_eq!(Ok(partial(Some(2017), Some(5), Some(15), None, Some(40), None)),
+               no_hour.truncate_to_unit(Unit::Minutes(20), FinerFields::Missing));
+    assert_eq!(Err(ConversionError::MissingField),
+               no_hour.truncate_to_unit(Unit::Minutes(7), FinerFields::Missing));
+}
+
+#[test]
+fn truncate_frac_second() {
+    let value = dts(30, FractionalSecond::Microseconds(123_456));
+
+    assert_eq!(Ok(dts(30, FractionalSecond::Microseconds(0))),
+               value.truncate_to_unit(Unit::Second, FinerFields::Zero));
+    assert_eq!(Ok(dts(30, FractionalSecond::None)),
+               value.truncate_to_unit(Unit::Second, FinerFields::Missing));
+}
+
+#[test]
+fn truncate_keeps_offset() {
+    let value = DateTimeOffset::new(Some(2017), Some(5), Some(15), Some(18), Some(45), Some(30),
+                                    OffsetValue::UtcOffset(-300)).unwrap();
+
+    assert_eq!(DateTimeOffset::new(Some(2017), Some(5), Some(15), Some(0), Some(0), Some(0),
+                                   OffsetValue::UtcOffset(-300)).unwrap(),
+               value.truncate_to_unit(Unit::Day, FinerFields::Zero).unwrap());
+}
+
+#[test]
+fn round() {
+    let value = dt(2017, 5, 15, 18, 45, 30);
+    let round = |unit| value.round_to_unit(unit, FinerFields::Zero).unwrap();
+
+    assert_eq!(dt(2017, 5, 15, 18, 46, 0), round(Unit::Minute));
+    assert_eq!(dt(2017, 5, 15, 19, 0, 0), round(Unit::Hour));
+    assert_eq!(dt(2017, 5, 16, 0, 0, 0), round(Unit::Day));
+    // the middle of May is the 16th at noon
+    assert_eq!(dt(2017, 5, 1, 0, 0, 0), round(Unit::Month));
+    assert_eq!(dt(2017, 1, 1, 0, 0, 0), round(Unit::Year));
+    assert_eq!(dt(2017, 5, 15, 18, 45, 0), round(Unit::Minutes(15)));
+    assert_eq!(dt(2017, 5, 15, 18, 50, 0), round(Unit::Minutes(10)));
+}
+
+#[test]
+fn round_halfway_up() {
+    let round = |value: DateTime, unit| value.round_to_unit(unit, FinerFields::Zero).unwrap();
+
+    assert_eq!(dt(2017, 5, 15, 18, 46, 0), round(dt(2017, 5, 15, 18, 45, 30), Unit::Minute));
+    assert_eq!(dt(2017, 5, 15, 18, 45, 0), round(dt(2017, 5, 15, 18, 45, 29), Unit::Minute));
+    // February 2017 has 28 days, so the middle is the 15th at midnight
+    assert_eq!(dt(2017, 3, 1, 0, 0, 0), round(dt(2017, 2, 15, 0, 0, 0), Unit::Month));
+    assert_eq!(dt(2017, 2, 1, 0, 0, 0), round(dt(2017, 2, 14, 23, 59, 59), Unit::Month));
+    assert_eq!(dt(2017, 6, 1, 0, 0, 0), round(dt(2017, 5, 16, 12, 0, 0), Unit::Month));
+}
+
+#[test]
+fn round_carries() {
+    assert_eq!(Ok(dt(2018, 1, 1, 0, 0, 0)),
+               dt(2017, 12, 31, 23, 59, 30).round_to_unit(Unit::Minute, FinerFields::Zero));
+    assert_eq!(Ok(partial(Some(2018), None, None, None, None, None)),
+               dt(2017, 7, 3, 0, 0, 0).round_to_unit(Unit::Year, FinerFields::Missing));
+    // the last 7 minute bucket of the day is only 5 minutes long
+    assert_eq!(Ok(dt(2017, 5, 16, 0, 0, 0)),
+               dt(2017, 5, 15, 23, 58, 0).round_to_unit(Unit::Minutes(7), FinerFields::Zero));
+    assert_eq!(Ok(dt(2017, 5, 15, 23, 55, 0)),
+               dt(2017, 5, 15, 23, 57, 0).round_to_unit(Unit::Minutes(7), FinerFields::Zero));
+}
+
+#[test]
+fn round_frac_second() {
+    assert_eq!(Ok(dts(31, FractionalSecond::Milliseconds(0))),
+               dts(30, FractionalSecond::Milliseconds(500))
+                   .round_to_unit(Unit::Second, FinerFields::Zero));
+    assert_eq!(Ok(dts(30, FractionalSecond::None)),
+               dts(30, FractionalSecond::Nanoseconds(499_999_999))
+                   .round_to_unit(Unit::Second, FinerFields::Missing));
+}
+
+#[test]
+fn round_missing_fields() {
+    let no_second = partial(Some(2017), Some(5), Some(15), Some(18), Some(45), None);
+    let no_year = partial(None, Some(5), Some(15), Some(18), Some(45), Some(30));
+
+    assert_eq!(Ok(dt(2017, 5, 15, 19, 0, 0)),
+               no_second.round_to_unit(Unit::Hour, FinerFields::Zero));
+    assert_eq!(Err(ConversionError::MissingField),
+               no_second.round_to_unit(Unit::Second, FinerFields::Zero));
+    assert_eq!(Err(ConversionError::MissingField),
+               no_year.round_to_unit(Unit::Hour, FinerFields::Zero));
+}
+
+#[test]
+fn round_out_of_range() {
+    assert_eq!(Err(ConversionError::OutOfRange),
+               dt(4094, 12, 31, 23, 59, 59).round_to_unit(Unit::Day, FinerFields::Zero));
+}
@@ -0,0 +1,103 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn date(year: u16, month: u8, day: u8) -> DateOnly {
+    DateOnly::new(Some(year), Some(month), Some(day)).unwrap()
+}
+
+#[test]
+fn weekday() {
+    assert_eq!(Some(Weekday::Sunday), date(2017, 1, 15).weekday());
+    assert_eq!(Some(Weekday::Thursday), date(1970, 1, 1).weekday());
+    assert_eq!(Some(Weekday::Saturday), date(2000, 1, 1).weekday());
+    assert_eq!(Some(Weekday::Monday), date(2018, 1, 1).weekday());
+    assert_eq!(Some(Weekday::Saturday), date(0, 1, 1).weekday());
+    assert_eq!(Some(Weekday::Friday), date(4094, 12, 31).weekday());
+}
+
+#[test]
+fn weekday_numbers() {
+    assert_eq!(1, Weekday::Monday.number_from_monday());
+    assert_eq!(7, Weekday::Sunday.number_from_monday());
+}
+
+#[test]
+fn weekday_consecutive_days() {
+    // 2016-12-31 was a Saturday
+    let mut expected = Weekday::Saturday.number_from_monday();
+
+    for day in 1..32 {
+        let weekday = date(2017, 1, day).weekday().unwrap();
+        assert_eq!(expected % 7 + 1, weekday.number_from_monday());
+        expected = weekday.number_from_monday();
+    }
+}
+
+#[test]
+fn day_of_year() {
+    assert_eq!(Some(1), date(2017, 1, 1).day_of_year());
+    assert_eq!(Some(60), date(2017, 3, 1).day_of_year());
+    assert_eq!(Some(61), date(2016, 3, 1).day_of_year());
+    assert_eq!(Some(365), date(2017, 12, 31).day_of_year());
+    assert_eq!(Some(366), date(2016, 12, 31).day_of_year());
+}
+
+#[test]
+fn leap_year_and_days_in_month() {
+    assert_eq!(Some(true), date(2000, 1, 1).is_leap_year());
+    assert_eq!(Some(false), date(1900, 1, 1).is_leap_year());
+    assert_eq!(Some(29), date(2016, 2, 1).days_in_month());
+    assert_eq!(Some(28), date(2017, 2, 1).days_in_month());
+    assert_eq!(Some(30), date(2017, 4, 1).days_in_month());
+    assert_eq!(Some(31), date(2017, 12, 1).days_in_month());
+}
+
+#[test]
+fn julian_day() {
+    assert_eq!(Some(2_451_545), date(2000, 1, 1).julian_day_number());
+    assert_eq!(Some(2_440_588), date(1970, 1, 1).julian_day_number());
+    assert_eq!(Some(1_721_060), date(0, 1, 1).julian_day_number());
+    assert_eq!(Some(0), date(1858, 11, 17).modified_julian_date());
+    assert_eq!(Some(51_544), date(2000, 1, 1).modified_julian_date());
+    assert_eq!(Some(57_768), date(2017, 1, 15).modified_julian_date());
+}
+
+#[test]
+fn missing_fields() {
+    let no_year = DateOnly::new(None, Some(4), Some(15)).unwrap();
+    let no_year_feb = DateOnly::new(None, Some(2), Some(15)).unwrap();
+    let no_day = DateOnly::new(Some(2016), Some(2), None).unwrap();
+
+    assert_eq!(None, no_year.weekday());
+    assert_eq!(None, no_year.day_of_year());
+    assert_eq!(None, no_year.is_leap_year());
+    assert_eq!(None, no_year.julian_day_number());
+    assert_eq!(Some(30), no_year.days_in_month());
+    assert_eq!(None, no_year_feb.days_in_month());
+    assert_eq!(None, no_day.weekday());
+    assert_eq!(None, no_day.modified_julian_date());
+    assert_eq!(Some(true), no_day.is_leap_year());
+    assert_eq!(Some(29), no_day.days_in_month());
+}
+
+#[test]
+fn nonexistent_date() {
+    let feb_30 = date(2017, 2, 30);
+
+    assert_eq!(None, feb_30.weekday());
+    assert_eq!(None, feb_30.day_of_year());
+    assert_eq!(Some(28), feb_30.days_in_month());
+}
+
+#[test]
+fn other_types() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(23), Some(0),
+                                            Some(0), FractionalSecond::None,
+                                            OffsetValue::UtcOffset(-300)).unwrap();
+    let dt = DateTime::new(Some(2017), Some(1), Some(15), None, None, None).unwrap();
+
+    // the local date, regardless of the offset
+    assert_eq!(Some(Weekday::Sunday), dtso.weekday());
+    assert_eq!(Some(15), dt.day_of_year());
+}
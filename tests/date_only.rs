@@ -71,3 +71,11 @@ fn date_roundtrip_struct() {
         };
     }
 }
+
+#[test]
+fn copy_and_debug_use_fields() {
+    let d = DateOnly::new(Some(2017), Some(1), None).unwrap();
+    let copy = d;
+    assert_eq!(d, copy);
+    assert_eq!("DateOnly { year: Some(2017), month: Some(1), day: None }", format!("{:?}", d));
+}
@@ -95,6 +95,39 @@ fn roundtrip_components_dto_all_random() {
     }
 }
 
+#[test]
+fn packed_matches_serialized_order_random() {
+    let mut random_fields = RandomFieldSource::new(rand::weak_rng());
+    let mut random = || DateTimeOffset::new(random_fields.year(), random_fields.month(),
+                                            random_fields.day(), random_fields.hour(),
+                                            random_fields.minute(), random_fields.second(),
+                                            random_fields.offset()).unwrap();
+
+    for _ in 0..10_000 {
+        let (a, b) = (random(), random());
+        let (mut a_bytes, mut b_bytes) = (Vec::new(), Vec::new());
+        a.serialize(&mut a_bytes).unwrap();
+        b.serialize(&mut b_bytes).unwrap();
+
+        assert_eq!(a_bytes.cmp(&b_bytes), a.to_packed_u64().cmp(&b.to_packed_u64()));
+        assert_eq!(a_bytes == b_bytes, a == b);
+    }
+}
+
+#[test]
+fn encoded_order_is_local_time_not_instant() {
+    let at = |hour, offset| DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(hour),
+                                                Some(0), Some(0), OffsetValue::UtcOffset(offset))
+        .unwrap();
+    // 08:00 UTC and 09:00 UTC
+    let (earlier, later) = (at(10, 120), at(9, 0));
+
+    assert!(earlier.to_packed_u64() > later.to_packed_u64());
+    assert!(earlier.to_utc().unwrap().to_packed_u64() < later.to_utc().unwrap().to_packed_u64());
+    // with the same local time, the offset decides
+    assert!(at(9, -60).to_packed_u64() < at(9, 0).to_packed_u64());
+}
+
 #[test]
 fn roundtrip_struct_dto_all_random() {
     let mut vec = Vec::new();
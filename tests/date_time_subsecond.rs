@@ -63,6 +63,19 @@ fn deser_dts_all_ms() {
     assert_eq!(bytes, serialized);
 }
 
+#[test]
+fn deser_dts_ms_ignores_padding() {
+    let bytes = vec!(0x47, 0xBF, 0x07, 0x49, 0x93, 0x07, 0xB0);
+    let padded = vec!(0x47, 0xBF, 0x07, 0x49, 0x93, 0x07, 0xBF);
+    let d = DateTimeSubSecond::deserialize(&mut Cursor::new(bytes.as_slice())).unwrap();
+    let p = DateTimeSubSecond::deserialize(&mut Cursor::new(padded.as_slice())).unwrap();
+    assert_eq!(d, p);
+
+    let mut serialized = Vec::new();
+    p.serialize(&mut serialized).unwrap();
+    assert_eq!(bytes, serialized);
+}
+
 #[test]
 fn deser_dts_all_us() {
     let bytes = vec!(0x57, 0xBF, 0x07, 0x49, 0x93, 0x07, 0x89, 0x00);
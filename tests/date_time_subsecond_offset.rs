@@ -47,6 +47,19 @@ fn deser_dtso_all_no_subsec() {
     assert_eq!(bytes, serialized);
 }
 
+#[test]
+fn deser_dtso_no_subsec_ignores_padding() {
+    let bytes = vec!(0xFB, 0xDF, 0x83, 0xA4, 0xC9, 0x91, 0x00);
+    let padded = vec!(0xFB, 0xDF, 0x83, 0xA4, 0xC9, 0x91, 0x3F);
+    let d = DateTimeSubSecondOffset::deserialize(&mut Cursor::new(bytes.as_slice())).unwrap();
+    let p = DateTimeSubSecondOffset::deserialize(&mut Cursor::new(padded.as_slice())).unwrap();
+    assert_eq!(d, p);
+
+    let mut serialized = Vec::new();
+    p.serialize(&mut serialized).unwrap();
+    assert_eq!(bytes, serialized);
+}
+
 #[test]
 fn deser_dtso_all_ms() {
     let bytes = vec!(0xE3, 0xDF, 0x83, 0xA4, 0xC9, 0x83, 0xDC, 0x40);
@@ -0,0 +1,104 @@
+extern crate temporenc;
+extern crate rand;
+
+mod common;
+
+use std::fmt::Debug;
+use rand::Rng;
+use temporenc::*;
+use common::RandomFieldSource;
+
+fn assert_same_as_reader<T: Deserializable + PartialEq + Debug>(bytes: &[u8]) {
+    let mut rest = bytes;
+    let expected = T::deserialize(&mut rest).map(|value| (value, bytes.len() - rest.len()));
+
+    assert_eq!(expected, T::deserialize_from_slice(bytes), "{:?}", bytes);
+}
+
+fn assert_all_types_same_as_reader(bytes: &[u8]) {
+    assert_same_as_reader::<DateOnly>(bytes);
+    assert_same_as_reader::<TimeOnly>(bytes);
+    assert_same_as_reader::<DateTime>(bytes);
+    assert_same_as_reader::<DateTimeOffset>(bytes);
+    assert_same_as_reader::<DateTimeSubSecond>(bytes);
+    assert_same_as_reader::<DateTimeSubSecondOffset>(bytes);
+}
+
+#[test]
+fn deser_slice_random_values_followed_by_junk() {
+    let mut s = RandomFieldSource::new(rand::weak_rng());
+    let mut rng = rand::weak_rng();
+
+    for _ in 0..10_000 {
+        let dtso = DateTimeSubSecondOffset::new(s.year(), s.month(), s.day(), s.hour(),
+                                                s.minute(), s.second(), s.fractional_second(),
+                                                s.offset()).unwrap();
+        let mut bytes = Vec::new();
+        dtso.serialize(&mut bytes).unwrap();
+        // whatever follows must not leak into the value
+        bytes.extend((0..16).map(|_| rng.gen::<u8>()));
+
+        assert_eq!((dtso, dtso.serialized_size()),
+                   DateTimeSubSecondOffset::deserialize_from_slice(&bytes).unwrap());
+
+        let dts = dtso.truncate_to::<DateTimeSubSecond>();
+        bytes.clear();
+        dts.serialize(&mut bytes).unwrap();
+        bytes.extend((0..16).map(|_| rng.gen::<u8>()));
+
+        assert_eq!((dts, dts.serialized_size()),
+                   DateTimeSubSecond::deserialize_from_slice(&bytes).unwrap());
+    }
+}
+
+#[test]
+fn deser_slice_random_bytes_same_as_reader() {
+    // tags and tag masks, so that most inputs get past the tag check to the field checks
+    let tags = [(0x80, 0xE0), (0xA0, 0xFE), (0x00, 0xC0), (0xC0, 0xE0), (0x40, 0xC0),
+                (0xE0, 0xE0)];
+    let mut rng = rand::weak_rng();
+    let mut bytes = [0; 20];
+
+    for _ in 0..10_000 {
+        rng.fill_bytes(&mut bytes);
+
+        for &(tag, mask) in tags.iter() {
+            bytes[0] = (bytes[0] & !mask) | tag;
+            assert_all_types_same_as_reader(&bytes);
+        }
+    }
+}
+
+#[test]
+fn deser_slice_short_slices_same_as_reader() {
+    let value = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                             Some(30), FractionalSecond::Nanoseconds(123_456_789),
+                                             OffsetValue::UtcOffset(120)).unwrap();
+    let mut bytes = Vec::new();
+    value.serialize(&mut bytes).unwrap();
+    bytes.extend_from_slice(&[0xFF; 10]);
+
+    for len in 0..bytes.len() {
+        assert_all_types_same_as_reader(&bytes[..len]);
+    }
+
+    assert_eq!(DeserializationError::IoError,
+               DateTimeSubSecondOffset::deserialize_from_slice(&bytes[..9]).unwrap_err());
+}
+
+#[test]
+fn deser_slice_checks_fields() {
+    let mut bytes = Vec::new();
+    DateTime::new(None, None, None, None, None, None).unwrap().serialize(&mut bytes).unwrap();
+    bytes.extend_from_slice(&[0; 8]);
+
+    let (value, len) = DateTime::deserialize_from_slice(&bytes).unwrap();
+    assert_eq!(5, len);
+    assert_eq!(None, value.year());
+    assert_eq!(None, value.second());
+
+    // hour 24 rather than all ones, keeping the high bit in the previous byte
+    bytes[3] = 0b1000_0000 | (bytes[3] & 0x0F);
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateTime::deserialize_from_slice(&bytes).unwrap_err());
+}
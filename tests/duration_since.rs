@@ -0,0 +1,91 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dt(day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+    DateTime::new(Some(2017), Some(1), Some(day), Some(hour), Some(minute), Some(second)).unwrap()
+}
+
+fn dto(day: u8, hour: u8, minute: u8, offset: OffsetValue) -> DateTimeOffset {
+    DateTimeOffset::new(Some(2017), Some(1), Some(day), Some(hour), Some(minute), Some(0), offset)
+        .unwrap()
+}
+
+fn dtso(second: u8, frac: FractionalSecond, offset: i16) -> DateTimeSubSecondOffset {
+    DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(second),
+                                 frac, OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+#[test]
+fn date_time() {
+    let start = dt(15, 18, 45, 0);
+
+    assert_eq!(Ok(SignedDuration::from_secs(90)), dt(15, 18, 46, 30).duration_since(&start));
+    assert_eq!(Ok(SignedDuration::from_secs(-90)), start.duration_since(&dt(15, 18, 46, 30)));
+    assert_eq!(Ok(SignedDuration::from_days(16)), dt(31, 18, 45, 0).duration_since(&start));
+    assert_eq!(Ok(SignedDuration::default()), start.duration_since(&dt(15, 18, 45, 0)));
+}
+
+#[test]
+fn date_time_across_years() {
+    let new_year = DateTime::new(Some(2000), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap();
+    let earliest = DateTime::new(Some(0), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap();
+
+    assert_eq!(Ok(SignedDuration::from_days(730_485)), new_year.duration_since(&earliest));
+}
+
+#[test]
+fn date_time_subsecond() {
+    let a = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(1),
+                                   FractionalSecond::Microseconds(250)).unwrap();
+    let b = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(0),
+                                   FractionalSecond::Nanoseconds(999_999_999)).unwrap();
+    let no_frac = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                         Some(0), FractionalSecond::None).unwrap();
+
+    assert_eq!(Ok(SignedDuration::from_nanos(250_001)), a.duration_since(&b));
+    assert_eq!(Ok(SignedDuration::from_nanos(-999_999_999)), no_frac.duration_since(&b));
+}
+
+#[test]
+fn offsets() {
+    let utc = dto(15, 12, 0, OffsetValue::UtcOffset(0));
+    let cet = dto(15, 13, 0, OffsetValue::UtcOffset(60));
+    let est = dto(15, 7, 30, OffsetValue::UtcOffset(-300));
+
+    assert_eq!(Ok(SignedDuration::default()), cet.duration_since(&utc));
+    assert_eq!(Ok(SignedDuration::from_minutes(30)), est.duration_since(&cet));
+    assert_eq!(Ok(SignedDuration::from_minutes(-30)), cet.duration_since(&est));
+}
+
+#[test]
+fn subsecond_offsets() {
+    let sent = dtso(30, FractionalSecond::Milliseconds(125), 60);
+    let received = dtso(30, FractionalSecond::Nanoseconds(125_004_321), 60);
+    let received_utc = received.to_utc().unwrap();
+
+    assert_eq!(Ok(SignedDuration::from_nanos(4_321)), received.duration_since(&sent));
+    assert_eq!(Ok(SignedDuration::from_nanos(4_321)), received_utc.duration_since(&sent));
+    assert_eq!(Ok(SignedDuration::from_nanos(-4_321)), sent.duration_since(&received_utc));
+}
+
+#[test]
+fn unknown_offset() {
+    let utc = dto(15, 12, 0, OffsetValue::UtcOffset(0));
+    let elsewhere = dto(15, 12, 0, OffsetValue::SpecifiedElsewhere);
+    let none = dto(15, 12, 0, OffsetValue::None);
+
+    assert_eq!(Err(ConversionError::UnknownOffset), utc.duration_since(&elsewhere));
+    assert_eq!(Err(ConversionError::UnknownOffset), elsewhere.duration_since(&utc));
+    assert_eq!(Err(ConversionError::UnknownOffset), none.duration_since(&utc));
+}
+
+#[test]
+fn missing_field() {
+    let no_second = DateTime::new(Some(2017), Some(1), Some(15), Some(18), Some(45), None)
+        .unwrap();
+    let no_month = DateTime::new(Some(2017), None, Some(15), Some(18), Some(45), Some(0)).unwrap();
+
+    assert_eq!(Err(ConversionError::MissingField), no_second.duration_since(&dt(15, 0, 0, 0)));
+    assert_eq!(Err(ConversionError::MissingField), dt(15, 0, 0, 0).duration_since(&no_month));
+}
@@ -0,0 +1,45 @@
+extern crate temporenc;
+
+use std::io::Cursor;
+use temporenc::*;
+
+#[test]
+fn serialize_mixed_boxed_values() {
+    let values: Vec<Box<dyn DynSerializable>> = vec!(
+        Box::new(DateOnly::new(Some(1983), Some(1), Some(15)).unwrap()),
+        Box::new(TimeOnly::new(Some(18), Some(25), Some(12)).unwrap()),
+        Box::new(DateTimeSubSecondOffset::new(Some(1983), Some(1), Some(15), Some(18), Some(25),
+                                              Some(12), FractionalSecond::Milliseconds(123),
+                                              OffsetValue::UtcOffset(60)).unwrap()),
+    );
+
+    let mut vec = Vec::new();
+    let mut total = 0;
+    for v in values.iter() {
+        let written = v.serialize_dyn(&mut vec).unwrap();
+        assert_eq!(v.encoded_len(), written);
+        total += written;
+    }
+
+    assert_eq!(3 + 3 + 8, total);
+    assert_eq!(total, vec.len());
+
+    let mut cursor = Cursor::new(vec.as_slice());
+    assert_eq!(Some(1983), DateOnly::deserialize(&mut cursor).unwrap().year());
+    assert_eq!(Some(18), TimeOnly::deserialize(&mut cursor).unwrap().hour());
+    assert_eq!(FractionalSecond::Milliseconds(123),
+               DateTimeSubSecondOffset::deserialize(&mut cursor).unwrap().fractional_second());
+}
+
+#[test]
+fn serialize_dyn_matches_serialize() {
+    let dts = DateTimeSubSecond::new(Some(1983), Some(1), Some(15), Some(18), Some(25), Some(12),
+                                     FractionalSecond::Nanoseconds(123456789)).unwrap();
+    let mut expected = Vec::new();
+    dts.serialize(&mut expected).unwrap();
+
+    let as_dyn: &dyn DynSerializable = &dts;
+    let mut actual = Vec::new();
+    assert_eq!(dts.serialized_size(), as_dyn.serialize_dyn(&mut actual).unwrap());
+    assert_eq!(expected, actual);
+}
@@ -0,0 +1,143 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn date(year: u16, month: Option<u8>, day: Option<u8>) -> DateOnly {
+    DateOnly::new(Some(year), month, day).unwrap()
+}
+
+fn dt(year: u16, month: u8, day: u8, hour: Option<u8>, minute: Option<u8>, second: Option<u8>)
+      -> DateTime {
+    DateTime::new(Some(year), Some(month), Some(day), hour, minute, second).unwrap()
+}
+
+fn dts(frac_second: FractionalSecond) -> DateTimeSubSecond {
+    DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                           frac_second).unwrap()
+}
+
+fn dto(day: u8, hour: Option<u8>, minute: Option<u8>, offset: OffsetValue) -> DateTimeOffset {
+    DateTimeOffset::new(Some(2017), Some(3), Some(day), hour, minute, minute.map(|_| 0), offset)
+        .unwrap()
+}
+
+#[test]
+fn month_bounds() {
+    let feb_2016 = date(2016, Some(2), None);
+
+    assert_eq!(date(2016, Some(2), Some(1)), feb_2016.earliest().unwrap());
+    assert_eq!(date(2016, Some(2), Some(29)), feb_2016.latest().unwrap());
+    assert_eq!(date(2017, Some(12), Some(31)), date(2017, None, None).latest().unwrap());
+}
+
+#[test]
+fn time_bounds() {
+    let hour = dt(2017, 1, 15, Some(18), None, None);
+
+    assert_eq!(dt(2017, 1, 15, Some(18), Some(0), Some(0)), hour.earliest().unwrap());
+    assert_eq!(dt(2017, 1, 15, Some(18), Some(59), Some(59)), hour.latest().unwrap());
+
+    let day = dt(2017, 1, 15, None, None, None);
+    assert_eq!(dt(2017, 1, 15, Some(0), Some(0), Some(0)), day.earliest().unwrap());
+    assert_eq!(dt(2017, 1, 15, Some(23), Some(59), Some(59)), day.latest().unwrap());
+}
+
+#[test]
+fn fractional_second_bounds() {
+    let millis = dts(FractionalSecond::Milliseconds(123));
+    assert_eq!(dts(FractionalSecond::Nanoseconds(123_000_000)), millis.earliest().unwrap());
+    assert_eq!(dts(FractionalSecond::Nanoseconds(123_999_999)), millis.latest().unwrap());
+
+    let micros = dts(FractionalSecond::Microseconds(123_456));
+    assert_eq!(dts(FractionalSecond::Nanoseconds(123_456_999)), micros.latest().unwrap());
+
+    let nanos = dts(FractionalSecond::Nanoseconds(5));
+    assert_eq!(dts(FractionalSecond::Nanoseconds(5)), nanos.earliest().unwrap());
+    assert_eq!(dts(FractionalSecond::Nanoseconds(5)), nanos.latest().unwrap());
+
+    let whole_second = dts(FractionalSecond::None);
+    assert_eq!(dts(FractionalSecond::Nanoseconds(0)), whole_second.earliest().unwrap());
+    assert_eq!(dts(FractionalSecond::Nanoseconds(999_999_999)), whole_second.latest().unwrap());
+}
+
+#[test]
+fn offset_is_kept() {
+    let value = dto(15, Some(18), None, OffsetValue::UtcOffset(60));
+
+    assert_eq!(DateTimeOffset::new(Some(2017), Some(3), Some(15), Some(18), Some(59), Some(59),
+                                   OffsetValue::UtcOffset(60)).unwrap(),
+               value.latest().unwrap());
+}
+
+#[test]
+fn bounds_errors() {
+    let no_year = DateOnly::new(None, Some(3), None).unwrap();
+    let gap = DateOnly::new(Some(2017), None, Some(15)).unwrap();
+    let minute_without_hour = DateTime::new(Some(2017), Some(3), Some(15), None, Some(30), None)
+        .unwrap();
+    let frac_without_second = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18),
+                                                     Some(45), None,
+                                                     FractionalSecond::Milliseconds(1)).unwrap();
+
+    assert_eq!(ConversionError::MissingField, no_year.earliest().unwrap_err());
+    assert_eq!(ConversionError::MissingField, no_year.latest().unwrap_err());
+    assert_eq!(ConversionError::MissingField, gap.latest().unwrap_err());
+    assert_eq!(ConversionError::MissingField, minute_without_hour.earliest().unwrap_err());
+    assert_eq!(ConversionError::MissingField, frac_without_second.earliest().unwrap_err());
+    assert_eq!(ConversionError::InvalidDate, date(2017, Some(2), Some(30)).earliest().unwrap_err());
+}
+
+#[test]
+fn contains() {
+    let march = date(2017, Some(3), None);
+
+    assert!(march.contains(&date(2017, Some(3), Some(1))).unwrap());
+    assert!(march.contains(&dt(2017, 3, 31, Some(23), Some(59), Some(59))).unwrap());
+    assert!(march.contains(&march).unwrap());
+    assert!(!march.contains(&dt(2017, 4, 1, Some(0), Some(0), Some(0))).unwrap());
+    assert!(!march.contains(&date(2017, None, None)).unwrap());
+    assert!(date(2017, None, None).contains(&march).unwrap());
+
+    let millis = dts(FractionalSecond::Milliseconds(123));
+    assert!(millis.contains(&dts(FractionalSecond::Nanoseconds(123_999_999))).unwrap());
+    assert!(!millis.contains(&dts(FractionalSecond::Nanoseconds(124_000_000))).unwrap());
+    assert!(dt(2017, 1, 15, Some(18), Some(45), Some(30)).contains(&millis).unwrap());
+}
+
+#[test]
+fn overlaps() {
+    let march = date(2017, Some(3), None);
+    let march_31 = dt(2017, 3, 31, None, None, None);
+
+    assert!(march.overlaps(&march_31).unwrap());
+    assert!(march_31.overlaps(&march).unwrap());
+    assert!(!march.overlaps(&date(2017, Some(4), Some(1))).unwrap());
+    assert!(date(2017, None, None).overlaps(&march).unwrap());
+
+    let a = dts(FractionalSecond::Milliseconds(123));
+    assert!(a.overlaps(&dts(FractionalSecond::Microseconds(123_999))).unwrap());
+    assert!(!a.overlaps(&dts(FractionalSecond::Microseconds(124_000))).unwrap());
+}
+
+#[test]
+fn offsets_compared_as_instants() {
+    let march_15_utc = dto(15, None, None, OffsetValue::UtcOffset(0));
+    let before_midnight_utc = dto(16, Some(0), Some(30), OffsetValue::UtcOffset(60));
+    let after_midnight_utc = dto(16, Some(1), Some(0), OffsetValue::UtcOffset(60));
+
+    assert!(march_15_utc.contains(&before_midnight_utc).unwrap());
+    assert!(!march_15_utc.overlaps(&after_midnight_utc).unwrap());
+}
+
+#[test]
+fn offset_mismatch() {
+    let march = date(2017, Some(3), None);
+    let with_offset = dto(15, Some(18), Some(0), OffsetValue::UtcOffset(60));
+    let elsewhere = dto(15, Some(18), Some(0), OffsetValue::SpecifiedElsewhere);
+    let no_offset = dto(15, Some(18), Some(0), OffsetValue::None);
+
+    assert_eq!(ConversionError::UnknownOffset, march.contains(&with_offset).unwrap_err());
+    assert_eq!(ConversionError::UnknownOffset, elsewhere.overlaps(&elsewhere).unwrap_err());
+    // no offset is compared as local time
+    assert!(march.contains(&no_offset).unwrap());
+}
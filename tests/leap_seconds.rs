@@ -0,0 +1,69 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dto(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, offset: i16)
+       -> DateTimeOffset {
+    DateTimeOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second),
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+#[test]
+fn real_leap_seconds_accepted() {
+    assert_eq!(Ok(()), dto(1972, 6, 30, 23, 59, 60, 0).check_leap_second());
+    assert_eq!(Ok(()), dto(2008, 12, 31, 23, 59, 60, 0).check_leap_second());
+    assert_eq!(Ok(()), dto(2016, 12, 31, 23, 59, 60, 0).check_leap_second());
+    // the same leap second in other offsets
+    assert_eq!(Ok(()), dto(2017, 1, 1, 0, 59, 60, 60).check_leap_second());
+    assert_eq!(Ok(()), dto(2015, 6, 30, 19, 59, 60, -240).check_leap_second());
+}
+
+#[test]
+fn bogus_leap_seconds_rejected() {
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               dto(2017, 3, 14, 3, 17, 60, 0).check_leap_second());
+    // right time of day, but no leap second that year
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               dto(2017, 12, 31, 23, 59, 60, 0).check_leap_second());
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               dto(1971, 12, 31, 23, 59, 60, 0).check_leap_second());
+    // 23:59:60 local, but not UTC
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               dto(2016, 12, 31, 23, 59, 60, 60).check_leap_second());
+    // a leap second month, but not the last day
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               dto(2016, 12, 30, 23, 59, 60, 0).check_leap_second());
+}
+
+#[test]
+fn other_seconds_accepted() {
+    assert_eq!(Ok(()), dto(2017, 3, 14, 3, 17, 59, 0).check_leap_second());
+
+    let partial = DateTimeOffset::new(None, Some(3), None, Some(3), Some(17), Some(0),
+                                      OffsetValue::None).unwrap();
+    assert_eq!(Ok(()), partial.check_leap_second());
+}
+
+#[test]
+fn unverifiable_leap_seconds_rejected() {
+    let no_offset = DateTimeOffset::new(Some(2016), Some(12), Some(31), Some(23), Some(59),
+                                        Some(60), OffsetValue::SpecifiedElsewhere).unwrap();
+    let no_year = DateTimeOffset::new(None, Some(12), Some(31), Some(23), Some(59), Some(60),
+                                      OffsetValue::UtcOffset(0)).unwrap();
+
+    assert_eq!(Err(ConversionError::UnknownOffset), no_offset.check_leap_second());
+    assert_eq!(Err(ConversionError::MissingField), no_year.check_leap_second());
+}
+
+#[test]
+fn subsecond() {
+    let real = DateTimeSubSecondOffset::new(Some(2016), Some(12), Some(31), Some(23), Some(59),
+                                            Some(60), FractionalSecond::Milliseconds(500),
+                                            OffsetValue::UtcOffset(0)).unwrap();
+    let bogus = DateTimeSubSecondOffset::new(Some(2016), Some(12), Some(30), Some(23), Some(59),
+                                             Some(60), FractionalSecond::Milliseconds(500),
+                                             OffsetValue::UtcOffset(0)).unwrap();
+
+    assert_eq!(Ok(()), real.check_leap_second());
+    assert_eq!(Err(ConversionError::InvalidLeapSecond), bogus.check_leap_second());
+}
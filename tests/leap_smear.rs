@@ -0,0 +1,99 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+const NANOS: i128 = 1_000_000_000;
+/// 2017-01-01T00:00:00Z, just after the leap second
+const AFTER_LEAP: i128 = 1_483_228_800 * NANOS;
+/// 2016-12-31T12:00:00Z
+const SMEAR_START: i128 = AFTER_LEAP - 12 * 3600 * NANOS;
+
+fn utc(day: u8, hour: u8, minute: u8, second: u8, nanos: u32) -> DateTimeSubSecondOffset {
+    let (year, month) = if day == 31 { (2016, 12) } else { (2017, 1) };
+
+    DateTimeSubSecondOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute),
+                                 Some(second), FractionalSecond::Nanoseconds(nanos),
+                                 OffsetValue::UtcOffset(0)).unwrap()
+}
+
+/// The published formula for a 24 hour linear smear: smeared time advances 86400 seconds over
+/// the 86401 real seconds from noon to noon.
+fn smeared(real_seconds_since_start: i128) -> i128 {
+    SMEAR_START + real_seconds_since_start * 86_400 / 86_401
+}
+
+fn smear(value: &DateTimeSubSecondOffset) -> i128 {
+    value.to_unix_nanos(LeapSecondMode::Smear).unwrap()
+}
+
+#[test]
+fn outside_window_unchanged() {
+    for value in [utc(31, 11, 59, 59, 999_999_999), utc(1, 12, 0, 0, 0)].iter() {
+        assert_eq!(value.to_unix_nanos(LeapSecondMode::Strict).unwrap(), smear(value));
+        assert_eq!(Ok(value), DateTimeSubSecondOffset::from_smeared_unix_nanos(smear(value))
+            .as_ref());
+    }
+}
+
+#[test]
+fn matches_formula() {
+    assert_eq!(SMEAR_START, smear(&utc(31, 12, 0, 0, 0)));
+    assert_eq!(smeared(6 * 3600 * NANOS), smear(&utc(31, 18, 0, 0, 0)));
+    assert_eq!(smeared(12 * 3600 * NANOS), smear(&utc(31, 23, 59, 60, 0)));
+    assert_eq!(smeared(12 * 3600 * NANOS + NANOS / 2), smear(&utc(31, 23, 59, 60, 500_000_000)));
+    assert_eq!(smeared(12 * 3600 * NANOS + NANOS), smear(&utc(1, 0, 0, 0, 0)));
+    assert_eq!(smeared(18 * 3600 * NANOS + NANOS), smear(&utc(1, 6, 0, 0, 0)));
+    assert_eq!(smeared(86_401 * NANOS - 1), smear(&utc(1, 11, 59, 59, 999_999_999)));
+}
+
+#[test]
+fn half_a_second_behind_at_the_leap_second() {
+    // the smear is halfway done when the leap second starts
+    let lag = AFTER_LEAP - smear(&utc(31, 23, 59, 60, 0));
+
+    // 43200/86401 seconds, rounded up since smeared times are rounded down
+    assert_eq!(499_994_214, lag);
+}
+
+#[test]
+fn monotonic() {
+    let values = [utc(31, 23, 59, 59, 0), utc(31, 23, 59, 59, 999_999_999),
+                  utc(31, 23, 59, 60, 0), utc(31, 23, 59, 60, 999_999_999), utc(1, 0, 0, 0, 0)];
+
+    assert!(values.windows(2).all(|w| smear(&w[0]) < smear(&w[1])));
+}
+
+#[test]
+fn from_smeared() {
+    for &real in [0, 1, 43_199 * NANOS, 43_200 * NANOS, 43_200 * NANOS + NANOS / 2,
+                  43_201 * NANOS, 86_400 * NANOS, 86_401 * NANOS - 1].iter() {
+        let smeared = smeared(real);
+        let value = DateTimeSubSecondOffset::from_smeared_unix_nanos(smeared).unwrap();
+
+        assert_eq!(smeared, smear(&value));
+    }
+
+    assert_eq!(Ok(utc(31, 23, 59, 60, 0)),
+               DateTimeSubSecondOffset::from_smeared_unix_nanos(smeared(43_200 * NANOS)));
+    assert_eq!(Ok(utc(1, 0, 0, 0, 0)),
+               DateTimeSubSecondOffset::from_smeared_unix_nanos(smeared(43_201 * NANOS)));
+}
+
+#[test]
+fn bogus_leap_second_rejected() {
+    let bogus = DateTimeSubSecondOffset::new(Some(2017), Some(3), Some(14), Some(3), Some(17),
+                                             Some(60), FractionalSecond::None,
+                                             OffsetValue::UtcOffset(0)).unwrap();
+
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               bogus.to_unix_nanos(LeapSecondMode::Smear));
+}
+
+#[test]
+fn seconds() {
+    let leap = DateTimeOffset::new(Some(2016), Some(12), Some(31), Some(23), Some(59), Some(60),
+                                   OffsetValue::UtcOffset(0)).unwrap();
+
+    // the leap second is smeared to about 0.5 seconds before midnight
+    assert_eq!(Ok(1_483_228_799), leap.to_unix_seconds(LeapSecondMode::Smear));
+}
@@ -0,0 +1,114 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+    DateTime::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second))
+        .unwrap()
+}
+
+fn dtso(frac_second: FractionalSecond, offset: OffsetValue) -> DateTimeSubSecondOffset {
+    DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                                 frac_second, offset).unwrap()
+}
+
+#[test]
+fn date_without_year() {
+    let every_jan_15 = DateOnly::new(None, Some(1), Some(15)).unwrap();
+
+    assert!(every_jan_15.matches(&DateOnly::new(Some(2017), Some(1), Some(15)).unwrap()));
+    assert!(every_jan_15.matches(&DateOnly::new(Some(1983), Some(1), Some(15)).unwrap()));
+    assert!(!every_jan_15.matches(&DateOnly::new(Some(2017), Some(1), Some(16)).unwrap()));
+    assert!(!every_jan_15.matches(&DateOnly::new(Some(2017), Some(2), Some(15)).unwrap()));
+}
+
+#[test]
+fn time_without_second() {
+    let any_second = TimeOnly::new(Some(18), Some(45), None).unwrap();
+
+    assert!(any_second.matches(&TimeOnly::new(Some(18), Some(45), Some(0)).unwrap()));
+    assert!(any_second.matches(&TimeOnly::new(Some(18), Some(45), Some(60)).unwrap()));
+    assert!(!any_second.matches(&TimeOnly::new(Some(18), Some(46), Some(0)).unwrap()));
+}
+
+#[test]
+fn wider_type() {
+    let every_jan_15 = DateOnly::new(None, Some(1), Some(15)).unwrap();
+    let any_second = TimeOnly::new(Some(18), Some(45), None).unwrap();
+    let concrete = dtso(FractionalSecond::Nanoseconds(5), OffsetValue::UtcOffset(60));
+
+    assert!(every_jan_15.matches(&dt(2017, 1, 15, 18, 45, 30)));
+    assert!(every_jan_15.matches(&concrete));
+    assert!(any_second.matches(&concrete));
+    assert!(!any_second.matches(&dt(2017, 1, 15, 19, 45, 30)));
+}
+
+#[test]
+fn narrower_type_never_matches() {
+    let all_missing = DateTime::new(None, None, None, None, None, None).unwrap();
+
+    assert!(all_missing.matches(&dt(2017, 1, 15, 18, 45, 30)));
+    assert!(!all_missing.matches(&DateOnly::new(Some(2017), Some(1), Some(15)).unwrap()));
+    assert!(!all_missing.matches(&TimeOnly::new(Some(18), Some(45), Some(30)).unwrap()));
+}
+
+#[test]
+fn missing_in_concrete() {
+    let pattern = DateOnly::new(None, Some(1), Some(15)).unwrap();
+
+    // the pattern needs a day, and the concrete value doesn't have one
+    assert!(!pattern.matches(&DateOnly::new(Some(2017), Some(1), None).unwrap()));
+}
+
+#[test]
+fn fractional_second_precision() {
+    let millis = dtso(FractionalSecond::Milliseconds(123), OffsetValue::UtcOffset(60));
+
+    assert!(millis.matches(&dtso(FractionalSecond::Milliseconds(123), OffsetValue::UtcOffset(60))));
+    assert!(millis.matches(&dtso(FractionalSecond::Microseconds(123_999),
+                                 OffsetValue::UtcOffset(60))));
+    assert!(millis.matches(&dtso(FractionalSecond::Nanoseconds(123_000_001),
+                                 OffsetValue::UtcOffset(60))));
+    assert!(!millis.matches(&dtso(FractionalSecond::Milliseconds(124),
+                                  OffsetValue::UtcOffset(60))));
+    assert!(!millis.matches(&dtso(FractionalSecond::None, OffsetValue::UtcOffset(60))));
+
+    let nanos = dtso(FractionalSecond::Nanoseconds(123_000_000), OffsetValue::UtcOffset(60));
+    assert!(nanos.matches(&dtso(FractionalSecond::Milliseconds(123), OffsetValue::UtcOffset(60))));
+    assert!(!dtso(FractionalSecond::Nanoseconds(123_000_001), OffsetValue::UtcOffset(60))
+        .matches(&dtso(FractionalSecond::Milliseconds(123), OffsetValue::UtcOffset(60))));
+}
+
+#[test]
+fn offsets() {
+    let pattern = DateTimeOffset::new(None, Some(1), Some(15), Some(18), None, None,
+                                      OffsetValue::UtcOffset(60)).unwrap();
+    let no_offset = DateTimeOffset::new(None, Some(1), Some(15), Some(18), None, None,
+                                        OffsetValue::None).unwrap();
+    let concrete = dtso(FractionalSecond::None, OffsetValue::UtcOffset(60));
+    // the same instant, but a different offset
+    let utc = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(17), Some(45),
+                                           Some(30), FractionalSecond::None,
+                                           OffsetValue::UtcOffset(0)).unwrap();
+
+    assert!(pattern.matches(&concrete));
+    assert!(!pattern.matches(&utc));
+    assert!(no_offset.matches(&concrete));
+    assert!(!pattern.matches(&dtso(FractionalSecond::None, OffsetValue::SpecifiedElsewhere)));
+}
+
+#[test]
+fn is_complete() {
+    assert!(DateOnly::new(Some(2017), Some(1), Some(15)).unwrap().is_complete());
+    assert!(!DateOnly::new(None, Some(1), Some(15)).unwrap().is_complete());
+    assert!(TimeOnly::new(Some(18), Some(45), Some(0)).unwrap().is_complete());
+    assert!(!TimeOnly::new(Some(18), Some(45), None).unwrap().is_complete());
+    assert!(dt(2017, 1, 15, 18, 45, 30).is_complete());
+    assert!(!DateTime::new(Some(2017), Some(1), Some(15), Some(18), None, Some(30)).unwrap()
+        .is_complete());
+
+    assert!(dtso(FractionalSecond::Milliseconds(0), OffsetValue::SpecifiedElsewhere)
+        .is_complete());
+    assert!(!dtso(FractionalSecond::None, OffsetValue::UtcOffset(60)).is_complete());
+    assert!(!dtso(FractionalSecond::Milliseconds(0), OffsetValue::None).is_complete());
+}
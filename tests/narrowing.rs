@@ -0,0 +1,86 @@
+extern crate temporenc;
+
+use std::convert::TryFrom;
+use temporenc::*;
+
+#[test]
+fn try_from_dtso_to_dt_when_nothing_lost() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), FractionalSecond::None, OffsetValue::None)
+        .unwrap();
+    let dt = DateTime::try_from(dtso).unwrap();
+
+    assert_eq!(DateTime::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30)).unwrap(),
+               dt);
+}
+
+#[test]
+fn try_from_dtso_to_dt_rejects_frac_second() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), FractionalSecond::Milliseconds(0),
+                                            OffsetValue::None).unwrap();
+
+    assert_eq!(NarrowingError::FractionalSecond, DateTime::try_from(dtso).unwrap_err());
+}
+
+#[test]
+fn try_from_dtso_to_dt_rejects_offset() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), FractionalSecond::None,
+                                            OffsetValue::UtcOffset(0)).unwrap();
+
+    assert_eq!(NarrowingError::Offset, DateTime::try_from(dtso).unwrap_err());
+}
+
+#[test]
+fn try_from_dtso_to_dto_keeps_offset() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), FractionalSecond::None,
+                                            OffsetValue::UtcOffset(135)).unwrap();
+    let dto = DateTimeOffset::try_from(dtso).unwrap();
+
+    assert_eq!(DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                                   OffsetValue::UtcOffset(135)).unwrap(),
+               dto);
+}
+
+#[test]
+fn try_from_dtso_to_dto_rejects_frac_second() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), FractionalSecond::Nanoseconds(1),
+                                            OffsetValue::UtcOffset(135)).unwrap();
+
+    assert_eq!(NarrowingError::FractionalSecond, DateTimeOffset::try_from(dtso).unwrap_err());
+}
+
+#[test]
+fn try_from_dto_to_dt_rejects_specified_elsewhere() {
+    let dto = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                                  OffsetValue::SpecifiedElsewhere).unwrap();
+
+    assert_eq!(NarrowingError::Offset, DateTime::try_from(dto).unwrap_err());
+}
+
+#[test]
+fn try_from_dts_to_dt_rejects_frac_second() {
+    let dts = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                                     FractionalSecond::Microseconds(123456)).unwrap();
+
+    assert_eq!(NarrowingError::FractionalSecond, DateTime::try_from(dts).unwrap_err());
+}
+
+#[test]
+fn truncate_dtso_discards_frac_second_and_offset() {
+    let dtso = DateTimeSubSecondOffset::new(None, Some(1), Some(15), Some(18), None, Some(30),
+                                            FractionalSecond::Microseconds(123456),
+                                            OffsetValue::UtcOffset(-60)).unwrap();
+
+    assert_eq!(DateTime::new(None, Some(1), Some(15), Some(18), None, Some(30)).unwrap(),
+               dtso.truncate_to::<DateTime>());
+    assert_eq!(DateTimeOffset::new(None, Some(1), Some(15), Some(18), None, Some(30),
+                                   OffsetValue::UtcOffset(-60)).unwrap(),
+               dtso.truncate_to::<DateTimeOffset>());
+    assert_eq!(DateTimeSubSecond::new(None, Some(1), Some(15), Some(18), None, Some(30),
+                                      FractionalSecond::Microseconds(123456)).unwrap(),
+               dtso.truncate_to::<DateTimeSubSecond>());
+}
@@ -0,0 +1,119 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+/// Amsterdam local mean time
+const AMSTERDAM_LMT: i32 = 19 * 60 + 32;
+
+fn local(hour: u8, minute: u8, second: Option<u8>) -> DateTime {
+    DateTime::new(Some(1900), Some(1), Some(1), Some(hour), Some(minute), second).unwrap()
+}
+
+fn dto(hour: u8, minute: u8, second: Option<u8>, offset: i16) -> DateTimeOffset {
+    DateTimeOffset::new(Some(1900), Some(1), Some(1), Some(hour), Some(minute), second,
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+#[test]
+fn nearest_quarter_hour() {
+    let (value, adjustment) = DateTimeOffset::from_local(&local(12, 0, Some(0)), AMSTERDAM_LMT,
+                                                         OffsetRounding::NearestQuarterHour)
+        .unwrap();
+
+    assert_eq!(dto(11, 55, Some(28), 15), value);
+    assert_eq!(OffsetAdjustment { offset_seconds: 272, instant_seconds: 0 }, adjustment);
+    assert!(!adjustment.is_exact());
+}
+
+#[test]
+fn nearest_quarter_hour_rounds_up() {
+    // +00:20 rounds to +00:15, but +00:23 rounds to +00:30
+    let (value, adjustment) = DateTimeOffset::from_local(&local(12, 0, Some(0)), 23 * 60,
+                                                         OffsetRounding::NearestQuarterHour)
+        .unwrap();
+
+    assert_eq!(dto(12, 7, Some(0), 30), value);
+    assert_eq!(OffsetAdjustment { offset_seconds: -7 * 60, instant_seconds: 0 }, adjustment);
+
+    // west of UTC
+    let (value, _) = DateTimeOffset::from_local(&local(12, 0, Some(0)), -(4 * 3600 + 56 * 60 + 2),
+                                                OffsetRounding::NearestQuarterHour).unwrap();
+    assert_eq!(dto(11, 56, Some(2), -300), value);
+}
+
+#[test]
+fn utc() {
+    let (value, adjustment) = DateTimeOffset::from_local(&local(12, 0, Some(0)), AMSTERDAM_LMT,
+                                                         OffsetRounding::Utc).unwrap();
+
+    assert_eq!(dto(11, 40, Some(28), 0), value);
+    assert_eq!(OffsetAdjustment { offset_seconds: AMSTERDAM_LMT, instant_seconds: 0 }, adjustment);
+}
+
+#[test]
+fn utc_out_of_range_offset() {
+    let offset = 16 * 3600;
+
+    assert_eq!(ConversionError::InvalidOffset,
+               DateTimeOffset::from_local(&local(20, 0, Some(0)), offset,
+                                          OffsetRounding::NearestQuarterHour).unwrap_err());
+    assert_eq!(dto(4, 0, Some(0), 0),
+               DateTimeOffset::from_local(&local(20, 0, Some(0)), offset, OffsetRounding::Utc)
+                   .unwrap().0);
+}
+
+#[test]
+fn missing_second_loses_instant_precision() {
+    let (value, adjustment) = DateTimeOffset::from_local(&local(12, 0, None), AMSTERDAM_LMT,
+                                                         OffsetRounding::NearestQuarterHour)
+        .unwrap();
+
+    // the wall clock should move back 4:32, but can only move back 5:00
+    assert_eq!(dto(11, 55, None, 15), value);
+    assert_eq!(OffsetAdjustment { offset_seconds: 272, instant_seconds: -28 }, adjustment);
+}
+
+#[test]
+fn representable_offset_unchanged() {
+    for &rounding in [OffsetRounding::NearestQuarterHour, OffsetRounding::Utc].iter() {
+        let partial = DateTime::new(Some(1900), None, Some(1), None, Some(0), None).unwrap();
+        let (value, adjustment) = DateTimeOffset::from_local(&partial, 3600, rounding).unwrap();
+
+        assert_eq!(DateTimeOffset::new(Some(1900), None, Some(1), None, Some(0), None,
+                                       OffsetValue::UtcOffset(60)).unwrap(),
+                   value);
+        assert!(adjustment.is_exact());
+    }
+}
+
+#[test]
+fn missing_field() {
+    let partial = DateTime::new(Some(1900), Some(1), Some(1), Some(12), None, Some(0)).unwrap();
+
+    assert_eq!(ConversionError::MissingField,
+               DateTimeOffset::from_local(&partial, AMSTERDAM_LMT, OffsetRounding::Utc)
+                   .unwrap_err());
+}
+
+#[test]
+fn out_of_range() {
+    let earliest = DateTime::new(Some(0), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap();
+
+    assert_eq!(ConversionError::OutOfRange,
+               DateTimeOffset::from_local(&earliest, AMSTERDAM_LMT, OffsetRounding::Utc)
+                   .unwrap_err());
+}
+
+#[test]
+fn subsecond_carries_across_midnight() {
+    let local = DateTimeSubSecond::new(Some(1899), Some(12), Some(31), Some(23), Some(59),
+                                       Some(50), FractionalSecond::Milliseconds(250)).unwrap();
+    let (value, adjustment) = DateTimeSubSecondOffset::from_local(&local, -AMSTERDAM_LMT,
+                                                                  OffsetRounding::Utc).unwrap();
+
+    assert_eq!(DateTimeSubSecondOffset::new(Some(1900), Some(1), Some(1), Some(0), Some(19),
+                                            Some(22), FractionalSecond::Milliseconds(250),
+                                            OffsetValue::UtcOffset(0)).unwrap(),
+               value);
+    assert_eq!(-AMSTERDAM_LMT, adjustment.offset_seconds);
+}
@@ -0,0 +1,237 @@
+extern crate temporenc;
+extern crate rand;
+
+mod common;
+
+use std::cmp::Ordering;
+use temporenc::*;
+use common::RandomFieldSource;
+
+fn key<T: OrderedKey>(value: &T, missing: MissingFields) -> Vec<u8> {
+    let mut vec = Vec::new();
+    assert_eq!(T::key_size(), value.serialize_key(missing, &mut vec).unwrap());
+    assert_eq!(T::key_size(), vec.len());
+    vec
+}
+
+fn encoded<T: Serializable>(value: &T) -> Vec<u8> {
+    let mut vec = Vec::new();
+    value.serialize(&mut vec).unwrap();
+    vec
+}
+
+fn dts(second: Option<u8>, frac_second: FractionalSecond) -> DateTimeSubSecond {
+    DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45), second, frac_second)
+        .unwrap()
+}
+
+fn date(month: Option<u8>, day: Option<u8>) -> DateOnly {
+    DateOnly::new(Some(2017), month, day).unwrap()
+}
+
+/// The order keys should have, computed from the fields
+fn expected_order(fields: &TemporalFields, missing: MissingFields) -> [u64; 9] {
+    let k = |v: Option<u32>| match (v, missing) {
+        (Some(v), _) => v as u64 + 1,
+        (None, MissingFields::First) => 0,
+        (None, MissingFields::Last) => std::u64::MAX,
+    };
+    let (frac, precision) = match fields.frac_second {
+        FractionalSecond::Milliseconds(ms) => (Some(ms as u32 * 1_000_000), 0),
+        FractionalSecond::Microseconds(us) => (Some(us * 1_000), 1),
+        FractionalSecond::Nanoseconds(ns) => (Some(ns), 2),
+        FractionalSecond::None => (None, 0),
+    };
+
+    [k(fields.year.map(u32::from)), k(fields.month.map(u32::from)), k(fields.day.map(u32::from)),
+     k(fields.hour.map(u32::from)), k(fields.minute.map(u32::from)),
+     k(fields.second.map(u32::from)), k(frac), precision, 0]
+}
+
+#[test]
+fn key_sizes() {
+    assert_eq!(3, DateOnly::key_size());
+    assert_eq!(3, TimeOnly::key_size());
+    assert_eq!(5, DateTime::key_size());
+    assert_eq!(6, DateTimeOffset::key_size());
+    assert_eq!(9, DateTimeSubSecond::key_size());
+    assert_eq!(10, DateTimeSubSecondOffset::key_size());
+}
+
+#[test]
+fn order_across_precisions() {
+    let in_order = [
+        dts(Some(30), FractionalSecond::Milliseconds(0)),
+        dts(Some(30), FractionalSecond::Microseconds(0)),
+        dts(Some(30), FractionalSecond::Nanoseconds(0)),
+        dts(Some(30), FractionalSecond::Nanoseconds(1)),
+        dts(Some(30), FractionalSecond::Microseconds(1)),
+        dts(Some(30), FractionalSecond::Microseconds(122_999)),
+        dts(Some(30), FractionalSecond::Milliseconds(123)),
+        dts(Some(30), FractionalSecond::Nanoseconds(123_000_001)),
+        dts(Some(30), FractionalSecond::Microseconds(123_001)),
+        dts(Some(30), FractionalSecond::Nanoseconds(999_999_999)),
+        dts(Some(31), FractionalSecond::Milliseconds(0)),
+    ];
+
+    for missing in [MissingFields::First, MissingFields::Last].iter() {
+        for pair in in_order.windows(2) {
+            assert!(key(&pair[0], *missing) < key(&pair[1], *missing));
+            assert_eq!(Ordering::Less, compare_encoded(&encoded(&pair[0]), &encoded(&pair[1]))
+                .unwrap());
+        }
+    }
+}
+
+#[test]
+fn missing_placement() {
+    let march = date(Some(3), None);
+    let march_1 = date(Some(3), Some(1));
+    let march_31 = date(Some(3), Some(31));
+    let april_1 = date(Some(4), Some(1));
+
+    assert!(key(&march, MissingFields::First) < key(&march_1, MissingFields::First));
+    assert!(key(&march_31, MissingFields::Last) < key(&march, MissingFields::Last));
+    assert!(key(&march, MissingFields::Last) < key(&april_1, MissingFields::Last));
+
+    let whole_second = dts(Some(30), FractionalSecond::None);
+    let start = dts(Some(30), FractionalSecond::Milliseconds(0));
+    let end = dts(Some(30), FractionalSecond::Nanoseconds(999_999_999));
+    assert!(key(&whole_second, MissingFields::First) < key(&start, MissingFields::First));
+    assert!(key(&end, MissingFields::Last) < key(&whole_second, MissingFields::Last));
+    assert!(key(&whole_second, MissingFields::Last)
+        < key(&dts(Some(31), FractionalSecond::None), MissingFields::Last));
+}
+
+#[test]
+fn offset_placement() {
+    let dto = |offset| DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                           Some(30), offset).unwrap();
+    let (none, elsewhere) = (dto(OffsetValue::None), dto(OffsetValue::SpecifiedElsewhere));
+    let (west, east) = (dto(OffsetValue::UtcOffset(OFFSET_MIN)),
+                        dto(OffsetValue::UtcOffset(OFFSET_MAX)));
+
+    let first = MissingFields::First;
+    assert!(key(&none, first) < key(&elsewhere, first));
+    assert!(key(&elsewhere, first) < key(&west, first));
+    assert!(key(&west, first) < key(&east, first));
+
+    let last = MissingFields::Last;
+    assert!(key(&west, last) < key(&east, last));
+    assert!(key(&east, last) < key(&elsewhere, last));
+    assert!(key(&elsewhere, last) < key(&none, last));
+}
+
+#[test]
+fn key_order_matches_fields_random() {
+    let mut random_fields = RandomFieldSource::new(rand::weak_rng());
+    let mut random = || {
+        DateTimeSubSecond::new(random_fields.year(), random_fields.month(), random_fields.day(),
+                               random_fields.hour(), random_fields.minute(),
+                               random_fields.second(), random_fields.fractional_second())
+            .unwrap()
+    };
+
+    for _ in 0..10_000 {
+        let (a, b) = (random(), random());
+        // share a prefix some of the time so that the finer fields are compared
+        let b = DateTimeSubSecond::new(a.year(), a.month(), b.day(), b.hour(), b.minute(),
+                                       b.second(), b.fractional_second()).unwrap();
+
+        for missing in [MissingFields::First, MissingFields::Last].iter() {
+            let expected = expected_order(&a.fields(), *missing)
+                .cmp(&expected_order(&b.fields(), *missing));
+            assert_eq!(expected, key(&a, *missing).cmp(&key(&b, *missing)));
+        }
+
+        let expected = expected_order(&a.fields(), MissingFields::Last)
+            .cmp(&expected_order(&b.fields(), MissingFields::Last));
+        assert_eq!(expected, compare_encoded(&encoded(&a), &encoded(&b)).unwrap());
+    }
+}
+
+#[test]
+fn roundtrip_random() {
+    let mut random_fields = RandomFieldSource::new(rand::weak_rng());
+
+    for _ in 0..10_000 {
+        let dtso = DateTimeSubSecondOffset::new(random_fields.year(), random_fields.month(),
+                                                random_fields.day(), random_fields.hour(),
+                                                random_fields.minute(), random_fields.second(),
+                                                random_fields.fractional_second(),
+                                                random_fields.offset()).unwrap();
+        let dto = DateTimeOffset::truncate_from(&dtso);
+        let date = DateOnly::new(dtso.year(), dtso.month(), dtso.day()).unwrap();
+        let time = TimeOnly::new(dtso.hour(), dtso.minute(), dtso.second()).unwrap();
+
+        for missing in [MissingFields::First, MissingFields::Last].iter() {
+            let k = key(&dtso, *missing);
+            assert_eq!(dtso, DateTimeSubSecondOffset::deserialize_key(&mut k.as_slice(), *missing)
+                .unwrap());
+            let k = key(&dto, *missing);
+            assert_eq!(dto, DateTimeOffset::deserialize_key(&mut k.as_slice(), *missing).unwrap());
+            let k = key(&date, *missing);
+            assert_eq!(date, DateOnly::deserialize_key(&mut k.as_slice(), *missing).unwrap());
+            let k = key(&time, *missing);
+            assert_eq!(time, TimeOnly::deserialize_key(&mut k.as_slice(), *missing).unwrap());
+        }
+    }
+}
+
+#[test]
+fn fixed_size_keys_sort_like_encoding() {
+    let mut random_fields = RandomFieldSource::new(rand::weak_rng());
+    let mut random = || {
+        DateTimeOffset::new(random_fields.year(), random_fields.month(), random_fields.day(),
+                            random_fields.hour(), random_fields.minute(), random_fields.second(),
+                            random_fields.offset()).unwrap()
+    };
+
+    for _ in 0..10_000 {
+        let (a, b) = (random(), random());
+
+        assert_eq!(encoded(&a).cmp(&encoded(&b)),
+                   key(&a, MissingFields::Last).cmp(&key(&b, MissingFields::Last)));
+        assert_eq!(encoded(&a).cmp(&encoded(&b)),
+                   compare_encoded(&encoded(&a), &encoded(&b)).unwrap());
+    }
+}
+
+#[test]
+fn deserialize_invalid_key() {
+    // the low 3 bits are padding
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateOnly::deserialize_key(&mut &[0x3F, 0x00, 0x01][..], MissingFields::Last)
+                   .unwrap_err());
+    // month 13
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateOnly::deserialize_key(&mut &[0x3F, 0x0C, 0x00][..], MissingFields::Last)
+                   .unwrap_err());
+    assert_eq!(DeserializationError::IoError,
+               DateTime::deserialize_key(&mut &[0x3F, 0x00][..], MissingFields::Last)
+                   .unwrap_err());
+}
+
+#[test]
+fn compare_encoded_trailing_bytes() {
+    let mut a = encoded(&date(Some(3), Some(1)));
+    let mut b = encoded(&date(Some(3), Some(1)));
+    assert_eq!(Ordering::Equal, compare_encoded(&a, &b).unwrap());
+
+    a.push(2);
+    b.push(1);
+    assert_eq!(Ordering::Greater, compare_encoded(&a, &b).unwrap());
+    assert_eq!(Ordering::Less, compare_encoded(&encoded(&date(Some(2), Some(28))), &b).unwrap());
+}
+
+#[test]
+fn compare_encoded_errors() {
+    let date = encoded(&date(Some(3), Some(1)));
+    let time = encoded(&TimeOnly::new(Some(18), None, None).unwrap());
+
+    assert_eq!(DeserializationError::IncorrectTypeTag, compare_encoded(&date, &time).unwrap_err());
+    assert_eq!(DeserializationError::IncorrectTypeTag,
+               compare_encoded(&[0b1011_0000, 0, 0], &date).unwrap_err());
+    assert_eq!(DeserializationError::IoError, compare_encoded(&date[..2], &date).unwrap_err());
+    assert_eq!(DeserializationError::IoError, compare_encoded(&[], &date).unwrap_err());
+}
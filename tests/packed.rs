@@ -0,0 +1,166 @@
+extern crate temporenc;
+extern crate rand;
+
+mod common;
+
+use temporenc::*;
+use common::RandomFieldSource;
+
+fn key<T: OrderedKey>(value: &T) -> Vec<u8> {
+    let mut vec = Vec::new();
+    value.serialize_key(MissingFields::Last, &mut vec).unwrap();
+    vec
+}
+
+#[test]
+fn packed_round_trips_random_values() {
+    let mut s = RandomFieldSource::new(rand::weak_rng());
+
+    for _ in 0..10_000 {
+        let date = DateOnly::new(s.year(), s.month(), s.day()).unwrap();
+        assert_eq!(date, DateOnly::from_packed_u64(date.to_packed_u64()).unwrap());
+        assert!(date.to_packed_u64() < 1 << 21);
+
+        let time = TimeOnly::new(s.hour(), s.minute(), s.second()).unwrap();
+        assert_eq!(time, TimeOnly::from_packed_u64(time.to_packed_u64()).unwrap());
+        assert!(time.to_packed_u64() < 1 << 17);
+
+        let dt = DateTime::new(s.year(), s.month(), s.day(), s.hour(), s.minute(), s.second())
+            .unwrap();
+        assert_eq!(dt, DateTime::from_packed_u64(dt.to_packed_u64()).unwrap());
+        assert!(dt.to_packed_u64() < 1 << 38);
+
+        let dto = DateTimeOffset::new(s.year(), s.month(), s.day(), s.hour(), s.minute(),
+                                      s.second(), s.offset()).unwrap();
+        assert_eq!(dto, DateTimeOffset::from_packed_u64(dto.to_packed_u64()).unwrap());
+        assert!(dto.to_packed_u64() < 1 << 45);
+
+        let dts = DateTimeSubSecond::new(s.year(), s.month(), s.day(), s.hour(), s.minute(),
+                                         s.second(), s.fractional_second()).unwrap();
+        assert_eq!(dts, DateTimeSubSecond::from_packed_u128(dts.to_packed_u128()).unwrap());
+        assert!(dts.to_packed_u128() < 1 << 70);
+
+        let dtso = DateTimeSubSecondOffset::new(s.year(), s.month(), s.day(), s.hour(),
+                                                s.minute(), s.second(), s.fractional_second(),
+                                                s.offset()).unwrap();
+        assert_eq!(dtso,
+                   DateTimeSubSecondOffset::from_packed_u128(dtso.to_packed_u128()).unwrap());
+        assert!(dtso.to_packed_u128() < 1 << 77);
+    }
+}
+
+#[test]
+fn packed_order_matches_key_order() {
+    let mut s = RandomFieldSource::new(rand::weak_rng());
+
+    for _ in 0..10_000 {
+        let mut random = || DateTimeSubSecondOffset::new(s.year(), s.month(), s.day(), s.hour(),
+                                                         s.minute(), s.second(),
+                                                         s.fractional_second(), s.offset())
+            .unwrap();
+        let (a, b) = (random(), random());
+
+        assert_eq!(key(&a).cmp(&key(&b)), a.to_packed_u128().cmp(&b.to_packed_u128()));
+    }
+}
+
+#[test]
+fn packed_is_chronological() {
+    let times = [
+        DateTime::new(Some(2016), Some(12), Some(31), Some(23), Some(59), Some(60)).unwrap(),
+        DateTime::new(Some(2017), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap(),
+        DateTime::new(Some(2017), Some(1), Some(1), Some(0), Some(0), Some(1)).unwrap(),
+        DateTime::new(Some(2017), Some(1), Some(2), Some(0), Some(0), Some(0)).unwrap(),
+        DateTime::new(Some(2017), Some(12), Some(31), Some(23), Some(59), Some(59)).unwrap(),
+        DateTime::new(Some(4094), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap(),
+        // missing fields sort last
+        DateTime::new(None, None, None, None, None, None).unwrap(),
+    ];
+
+    for pair in times.windows(2) {
+        assert!(pair[0].to_packed_u64() < pair[1].to_packed_u64(), "{:?}", pair);
+    }
+}
+
+#[test]
+fn packed_sub_second_keeps_precision() {
+    let dts = |frac| DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), frac).unwrap();
+    let millis = dts(FractionalSecond::Milliseconds(123));
+    let micros = dts(FractionalSecond::Microseconds(123_000));
+    let nanos = dts(FractionalSecond::Nanoseconds(123_000_001));
+
+    assert!(millis.to_packed_u128() < micros.to_packed_u128());
+    assert!(micros.to_packed_u128() < nanos.to_packed_u128());
+    assert_eq!(micros, DateTimeSubSecond::from_packed_u128(micros.to_packed_u128()).unwrap());
+}
+
+#[test]
+fn from_packed_rejects_unused_bits() {
+    let date = DateOnly::new(Some(2017), Some(1), Some(15)).unwrap();
+
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateOnly::from_packed_u64(date.to_packed_u64() | 1 << 21).unwrap_err());
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateTimeSubSecondOffset::from_packed_u128(1 << 77).unwrap_err());
+}
+
+#[test]
+fn from_packed_rejects_out_of_range_fields() {
+    // hour 24, in the second 5-bit field
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               TimeOnly::from_packed_u64(24 << 12).unwrap_err());
+    // offset raw value 126 is SpecifiedElsewhere, and 125 the largest offset; all ones is None
+    let dto = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                                  OffsetValue::SpecifiedElsewhere).unwrap();
+    assert_eq!(dto.to_packed_u64() & 0x7F, 126);
+    // month 13
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateOnly::from_packed_u64((2017 << 9) | (12 << 5)).unwrap_err());
+}
+
+#[test]
+fn packed_u64_sub_second_matches_u128_order() {
+    let mut s = RandomFieldSource::new(rand::weak_rng());
+
+    for _ in 0..10_000 {
+        let mut random = || loop {
+            let frac = s.fractional_second();
+            if let FractionalSecond::Nanoseconds(_) = frac {
+                continue;
+            }
+            return DateTimeSubSecond::new(s.year(), s.month(), s.day(), s.hour(), s.minute(),
+                                          s.second(), frac).unwrap();
+        };
+        let (a, b) = (random(), random());
+        let packed = a.to_packed_u64().unwrap();
+
+        assert_eq!(a, DateTimeSubSecond::from_packed_u64(packed).unwrap());
+        assert!(packed < 1 << 59);
+        assert_eq!(a.to_packed_u128().cmp(&b.to_packed_u128()),
+                   packed.cmp(&b.to_packed_u64().unwrap()));
+    }
+}
+
+#[test]
+fn packed_u64_sub_second_errors() {
+    let dts = |frac| DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), frac).unwrap();
+
+    assert_eq!(ConversionError::PrecisionLoss,
+               dts(FractionalSecond::Nanoseconds(123_000_000)).to_packed_u64().unwrap_err());
+
+    let millis = dts(FractionalSecond::Milliseconds(123)).to_packed_u64().unwrap();
+    let frac_mask = (1 << 21) - 1;
+    // 123001 microseconds tagged as milliseconds
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateTimeSubSecond::from_packed_u64((millis & !frac_mask) | (123_002 << 1))
+                   .unwrap_err());
+    // zero is never used, and microseconds past the largest value
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateTimeSubSecond::from_packed_u64(millis & !frac_mask).unwrap_err());
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateTimeSubSecond::from_packed_u64(millis | (frac_mask - 1)).unwrap_err());
+    assert_eq!(DeserializationError::InvalidFieldValue,
+               DateTimeSubSecond::from_packed_u64(millis | 1 << 59).unwrap_err());
+}
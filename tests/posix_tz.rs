@@ -0,0 +1,156 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+    DateTime::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(0)).unwrap()
+}
+
+fn dto(year: u16, month: u8, day: u8, hour: u8, minute: u8, offset: i16) -> DateTimeOffset {
+    DateTimeOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(0),
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+fn offset_at(tz: &PosixTz, local: &DateTime) -> OffsetValue {
+    tz.resolve_date_time(local, Disambiguation::Reject).unwrap().offset()
+}
+
+#[test]
+fn parse_fixed_offset() {
+    let utc = PosixTz::parse("UTC0").unwrap();
+    let ist = PosixTz::parse("IST-5:30").unwrap();
+    let quoted = PosixTz::parse("<-03>3").unwrap();
+
+    assert_eq!(0, utc.offset_at_unix_seconds(0));
+    assert_eq!(5 * 3600 + 30 * 60, ist.offset_at_unix_seconds(0));
+    assert_eq!(-3 * 3600, quoted.offset_at_unix_seconds(1_500_000_000));
+    assert_eq!(OffsetValue::UtcOffset(330), offset_at(&ist, &dt(2017, 1, 15, 12, 0)));
+}
+
+#[test]
+fn parse_invalid() {
+    assert_eq!(PosixTzError::InvalidSyntax, PosixTz::parse("").unwrap_err());
+    assert_eq!(PosixTzError::InvalidSyntax, PosixTz::parse("CET").unwrap_err());
+    assert_eq!(PosixTzError::InvalidSyntax, PosixTz::parse("CE-1").unwrap_err());
+    assert_eq!(PosixTzError::InvalidSyntax, PosixTz::parse("<+01-1").unwrap_err());
+    assert_eq!(PosixTzError::InvalidSyntax, PosixTz::parse("CET-1CEST,M3.5.0").unwrap_err());
+    assert_eq!(PosixTzError::InvalidSyntax, PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3x")
+        .unwrap_err());
+    assert_eq!(PosixTzError::InvalidValue, PosixTz::parse("CET-25").unwrap_err());
+    assert_eq!(PosixTzError::InvalidValue,
+               PosixTz::parse("CET-1CEST,M13.5.0,M10.5.0").unwrap_err());
+    assert_eq!(PosixTzError::InvalidValue, PosixTz::parse("CET-1CEST,M3.6.0,M10.5.0").unwrap_err());
+    assert_eq!(PosixTzError::InvalidValue, PosixTz::parse("CET-1CEST,J0,J300").unwrap_err());
+}
+
+#[test]
+fn central_europe() {
+    let tz = PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&tz, &dt(2017, 1, 15, 12, 0)));
+    assert_eq!(OffsetValue::UtcOffset(120), offset_at(&tz, &dt(2017, 7, 1, 12, 0)));
+    // last Sunday of March and October
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&tz, &dt(2017, 3, 26, 1, 59)));
+    assert_eq!(OffsetValue::UtcOffset(120), offset_at(&tz, &dt(2017, 3, 26, 3, 0)));
+    assert_eq!(OffsetValue::UtcOffset(120), offset_at(&tz, &dt(2017, 10, 29, 1, 59)));
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&tz, &dt(2017, 10, 29, 3, 0)));
+    assert_eq!(ConversionError::NonexistentLocalTime,
+               tz.resolve_date_time(&dt(2017, 3, 26, 2, 30), Disambiguation::Reject).unwrap_err());
+    assert_eq!(ConversionError::AmbiguousLocalTime,
+               tz.resolve_date_time(&dt(2017, 10, 29, 2, 30), Disambiguation::Reject).unwrap_err());
+    // 2016 has a 5th Sunday in neither month, so "last" is the 4th
+    assert_eq!(OffsetValue::UtcOffset(120), offset_at(&tz, &dt(2016, 3, 27, 3, 0)));
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&tz, &dt(2016, 3, 27, 1, 0)));
+}
+
+#[test]
+fn us_default_rules() {
+    let explicit = PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    let default = PosixTz::parse("EST5EDT").unwrap();
+
+    assert_eq!(explicit, default);
+    assert_eq!(OffsetValue::UtcOffset(-300), offset_at(&default, &dt(2017, 3, 12, 1, 59)));
+    assert_eq!(OffsetValue::UtcOffset(-240), offset_at(&default, &dt(2017, 3, 12, 3, 0)));
+    assert_eq!(OffsetValue::UtcOffset(-240), offset_at(&default, &dt(2017, 11, 5, 0, 59)));
+    assert_eq!(OffsetValue::UtcOffset(-300), offset_at(&default, &dt(2017, 11, 5, 2, 0)));
+}
+
+#[test]
+fn southern_hemisphere() {
+    let tz = PosixTz::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+
+    assert_eq!(OffsetValue::UtcOffset(660), offset_at(&tz, &dt(2017, 1, 15, 12, 0)));
+    assert_eq!(OffsetValue::UtcOffset(600), offset_at(&tz, &dt(2017, 7, 1, 12, 0)));
+    assert_eq!(OffsetValue::UtcOffset(660), offset_at(&tz, &dt(2017, 12, 31, 23, 0)));
+    assert_eq!(OffsetValue::UtcOffset(600), offset_at(&tz, &dt(2017, 4, 2, 3, 0)));
+    assert_eq!(OffsetValue::UtcOffset(660), offset_at(&tz, &dt(2017, 10, 1, 3, 0)));
+}
+
+#[test]
+fn julian_days() {
+    // J60 is March 1 even in leap years; 59 is zero-based, so it's Feb 29 in leap years
+    let no_leap = PosixTz::parse("XXX0YYY,J60/0,J300").unwrap();
+    let leap = PosixTz::parse("XXX0YYY,59/0,J300").unwrap();
+
+    assert_eq!(OffsetValue::UtcOffset(0), offset_at(&no_leap, &dt(2016, 2, 29, 12, 0)));
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&no_leap, &dt(2016, 3, 1, 12, 0)));
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&leap, &dt(2016, 2, 29, 12, 0)));
+    assert_eq!(OffsetValue::UtcOffset(0), offset_at(&leap, &dt(2016, 2, 28, 12, 0)));
+}
+
+#[test]
+fn extended_transition_times() {
+    // RFC 8536 allows hours beyond 24 and negative times: DST here starts at 01:00 the day after
+    // the first Sunday of March, and ends at 23:00 the day before the first Sunday of November
+    let tz = PosixTz::parse("XXX0YYY,M3.1.0/25,M11.1.0/-1").unwrap();
+
+    assert_eq!(OffsetValue::UtcOffset(0), offset_at(&tz, &dt(2017, 3, 6, 0, 59)));
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&tz, &dt(2017, 3, 6, 2, 0)));
+    assert_eq!(OffsetValue::UtcOffset(60), offset_at(&tz, &dt(2017, 11, 4, 21, 59)));
+    assert_eq!(OffsetValue::UtcOffset(0), offset_at(&tz, &dt(2017, 11, 4, 23, 0)));
+}
+
+#[test]
+fn unrepresentable_offset() {
+    let tz = PosixTz::parse("<+0020>-0:20").unwrap();
+
+    assert_eq!(ConversionError::InvalidOffset,
+               tz.resolve_date_time(&dt(2017, 1, 15, 12, 0), Disambiguation::Reject).unwrap_err());
+    assert_eq!(ConversionError::InvalidOffset,
+               tz.to_local_date_time(&dto(2017, 1, 15, 12, 0, 0)).unwrap_err());
+}
+
+#[test]
+fn utc_to_local() {
+    let tz = PosixTz::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+
+    assert_eq!(dto(2017, 1, 15, 13, 0, 60),
+               tz.to_local_date_time(&dto(2017, 1, 15, 12, 0, 0)).unwrap());
+    assert_eq!(dto(2017, 7, 1, 14, 0, 120),
+               tz.to_local_date_time(&dto(2017, 7, 1, 12, 0, 0)).unwrap());
+    // any offset works, not just UTC
+    assert_eq!(dto(2017, 7, 1, 14, 0, 120),
+               tz.to_local_date_time(&dto(2017, 7, 1, 7, 0, -300)).unwrap());
+    // both sides of the fold
+    assert_eq!(dto(2017, 10, 29, 2, 30, 120),
+               tz.to_local_date_time(&dto(2017, 10, 29, 0, 30, 0)).unwrap());
+    assert_eq!(dto(2017, 10, 29, 2, 30, 60),
+               tz.to_local_date_time(&dto(2017, 10, 29, 1, 30, 0)).unwrap());
+
+    let utc = DateTimeSubSecondOffset::new(Some(2017), Some(7), Some(1), Some(12), Some(0), None,
+                                           FractionalSecond::Milliseconds(5),
+                                           OffsetValue::UtcOffset(0)).unwrap();
+    assert_eq!(DateTimeSubSecondOffset::new(Some(2017), Some(7), Some(1), Some(14), Some(0), None,
+                                            FractionalSecond::Milliseconds(5),
+                                            OffsetValue::UtcOffset(120)).unwrap(),
+               tz.to_local_date_time_subsecond(&utc).unwrap());
+}
+
+#[test]
+fn utc_to_local_unknown_offset() {
+    let tz = PosixTz::parse("UTC0").unwrap();
+    let elsewhere = DateTimeOffset::new(Some(2017), Some(7), Some(1), Some(12), Some(0), None,
+                                        OffsetValue::SpecifiedElsewhere).unwrap();
+
+    assert_eq!(ConversionError::UnknownOffset, tz.to_local_date_time(&elsewhere).unwrap_err());
+}
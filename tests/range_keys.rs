@@ -0,0 +1,207 @@
+extern crate temporenc;
+extern crate rand;
+
+mod common;
+
+use std::ops::Bound;
+use temporenc::*;
+use common::RandomFieldSource;
+
+fn encoded<T: Serializable>(value: &T) -> Vec<u8> {
+    let mut vec = Vec::new();
+    value.serialize(&mut vec).unwrap();
+    vec
+}
+
+fn key<T: OrderedKey>(value: &T, missing: MissingFields) -> Vec<u8> {
+    let mut vec = Vec::new();
+    value.serialize_key(missing, &mut vec).unwrap();
+    vec
+}
+
+fn start(range: &KeyRange) -> Vec<u8> {
+    let mut vec = Vec::new();
+    assert_eq!(range.key_size(), range.write_start(&mut vec).unwrap());
+    vec
+}
+
+fn end(range: &KeyRange, end: RangeEnd) -> Vec<u8> {
+    let mut vec = Vec::new();
+    assert_eq!(range.key_size(), range.write_end(end, &mut vec).unwrap());
+    vec
+}
+
+fn date(year: Option<u16>, month: Option<u8>, day: Option<u8>) -> DateOnly {
+    DateOnly::new(year, month, day).unwrap()
+}
+
+fn dt(day: u8, hour: u8) -> DateTime {
+    DateTime::new(Some(2017), Some(1), Some(day), Some(hour), Some(0), Some(0)).unwrap()
+}
+
+#[test]
+fn partial_date_encoded_month_straddles_bytes() {
+    let range = KeyRange::for_partial_encoded(&date(Some(2017), Some(1), None)).unwrap();
+
+    assert!(!range.is_empty());
+    assert_eq!(encoded(&date(Some(2017), Some(1), Some(1))), start(&range));
+    assert_eq!(encoded(&date(Some(2017), Some(1), None)), end(&range, RangeEnd::Inclusive));
+    assert_eq!(encoded(&date(Some(2017), Some(2), Some(1))), end(&range, RangeEnd::Exclusive));
+}
+
+#[test]
+fn partial_date_encoded_year() {
+    let range = KeyRange::for_partial_encoded(&date(Some(2017), None, None)).unwrap();
+
+    assert_eq!(encoded(&date(Some(2017), Some(1), Some(1))), start(&range));
+    assert_eq!(encoded(&date(Some(2017), None, None)), end(&range, RangeEnd::Inclusive));
+    assert_eq!(encoded(&date(Some(2018), Some(1), Some(1))), end(&range, RangeEnd::Exclusive));
+}
+
+#[test]
+fn partial_date_time_encoded_has_zero_tag() {
+    let partial = DateTime::new(Some(2017), Some(1), Some(15), None, None, None).unwrap();
+    let range = KeyRange::for_partial_encoded(&partial).unwrap();
+
+    assert_eq!(encoded(&dt(15, 0)), start(&range));
+    assert_eq!(encoded(&partial), end(&range, RangeEnd::Inclusive));
+    assert_eq!(encoded(&dt(16, 0)), end(&range, RangeEnd::Exclusive));
+}
+
+#[test]
+fn partial_date_time_offset_encoded_contains_month() {
+    let january = DateTimeOffset::new(Some(2017), Some(1), None, None, None, None,
+                                      OffsetValue::None).unwrap();
+    let range = KeyRange::for_partial_encoded(&january).unwrap();
+    let (s, e) = (start(&range), end(&range, RangeEnd::Exclusive));
+
+    let inside = [
+        DateTimeOffset::new(Some(2017), Some(1), Some(1), Some(0), Some(0), Some(0),
+                            OffsetValue::UtcOffset(-900)).unwrap(),
+        DateTimeOffset::new(Some(2017), Some(1), Some(31), Some(23), Some(59), Some(60),
+                            OffsetValue::SpecifiedElsewhere).unwrap(),
+        DateTimeOffset::new(Some(2017), Some(1), None, None, None, None, OffsetValue::None)
+            .unwrap(),
+    ];
+    for value in inside.iter() {
+        let bytes = encoded(value);
+        assert!(s <= bytes && bytes < e, "{:?}", value);
+    }
+
+    let outside = [
+        DateTimeOffset::new(Some(2016), Some(12), Some(31), Some(23), Some(59), Some(59),
+                            OffsetValue::None).unwrap(),
+        DateTimeOffset::new(Some(2017), Some(2), Some(1), Some(0), Some(0), Some(0),
+                            OffsetValue::UtcOffset(-900)).unwrap(),
+        DateTimeOffset::new(Some(2017), None, None, None, None, None, OffsetValue::None)
+            .unwrap(),
+    ];
+    for value in outside.iter() {
+        let bytes = encoded(value);
+        assert!(bytes < s || e <= bytes, "{:?}", value);
+    }
+}
+
+#[test]
+fn partial_with_gap_is_error() {
+    assert_eq!(ConversionError::MissingField,
+               KeyRange::for_partial_encoded(&date(Some(2017), None, Some(15))).unwrap_err());
+
+    let offset_only = DateTimeOffset::new(Some(2017), None, None, None, None, None,
+                                          OffsetValue::UtcOffset(60)).unwrap();
+    assert_eq!(ConversionError::MissingField,
+               KeyRange::for_partial(&offset_only, MissingFields::First).unwrap_err());
+}
+
+#[test]
+fn partial_with_nothing_present_is_everything() {
+    let range = KeyRange::for_partial(&date(None, None, None), MissingFields::First).unwrap();
+
+    assert_eq!(vec![0, 0, 0], start(&range));
+    assert_eq!(vec![0xFF, 0xFF, 0xF8], end(&range, RangeEnd::Inclusive));
+    assert_eq!(vec![0xFF, 0xFF, 0xF9], end(&range, RangeEnd::Exclusive));
+}
+
+#[test]
+fn partial_sub_second_key_contains_all_precisions() {
+    let partial = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18),
+                                               Some(45), Some(30), FractionalSecond::None,
+                                               OffsetValue::None).unwrap();
+
+    for &missing in [MissingFields::First, MissingFields::Last].iter() {
+        let range = KeyRange::for_partial(&partial, missing).unwrap();
+        let (s, e) = (start(&range), end(&range, RangeEnd::Exclusive));
+
+        for &frac in [FractionalSecond::Milliseconds(999), FractionalSecond::Microseconds(0),
+                      FractionalSecond::Nanoseconds(123_456_789), FractionalSecond::None].iter() {
+            let value = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18),
+                                                     Some(45), Some(30), frac,
+                                                     OffsetValue::UtcOffset(120)).unwrap();
+            let bytes = key(&value, missing);
+            assert!(s <= bytes && bytes < e, "{:?}", value);
+        }
+
+        let next = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18),
+                                                Some(45), Some(31), FractionalSecond::None,
+                                                OffsetValue::None).unwrap();
+        assert!(key(&next, missing) >= e);
+    }
+}
+
+#[test]
+fn range_encoded_bounds() {
+    let (a, b) = (dt(10, 6), dt(12, 18));
+
+    let range = KeyRange::for_range_encoded(Bound::Included(&a), Bound::Included(&b));
+    assert_eq!(encoded(&a), start(&range));
+    assert_eq!(encoded(&b), end(&range, RangeEnd::Inclusive));
+
+    let range = KeyRange::for_range_encoded(Bound::Excluded(&a), Bound::Excluded(&b));
+    assert!(encoded(&a) < start(&range));
+    assert!(encoded(&dt(10, 7)) >= start(&range));
+    assert_eq!(encoded(&b), end(&range, RangeEnd::Exclusive));
+    assert!(encoded(&dt(12, 17)) <= end(&range, RangeEnd::Inclusive));
+}
+
+#[test]
+fn range_unbounded_and_empty() {
+    let a = dt(10, 6);
+
+    let range = KeyRange::for_range_encoded::<DateTime>(Bound::Unbounded, Bound::Unbounded);
+    assert_eq!(vec![0, 0, 0, 0, 0], start(&range));
+    assert_eq!(vec![0x3F, 0xFF, 0xFF, 0xFF, 0xFF], end(&range, RangeEnd::Inclusive));
+    assert_eq!(vec![0x40, 0, 0, 0, 0], end(&range, RangeEnd::Exclusive));
+
+    assert!(KeyRange::for_range_encoded(Bound::Excluded(&a), Bound::Excluded(&a)).is_empty());
+    assert!(!KeyRange::for_range_encoded(Bound::Included(&a), Bound::Included(&a)).is_empty());
+    assert!(KeyRange::for_range_encoded(Bound::Included(&dt(11, 0)), Bound::Included(&a))
+        .is_empty());
+
+    let first = DateTime::new(Some(0), Some(1), Some(1), Some(0), Some(0), Some(0)).unwrap();
+    assert!(KeyRange::for_range_encoded(Bound::Unbounded, Bound::Excluded(&first)).is_empty());
+    let last = DateTime::new(None, None, None, None, None, None).unwrap();
+    assert!(KeyRange::for_range_encoded(Bound::Excluded(&last), Bound::Unbounded).is_empty());
+}
+
+#[test]
+fn range_key_contains_random_values_between_bounds() {
+    let mut source = RandomFieldSource::new(rand::weak_rng());
+
+    for _ in 0..10_000 {
+        let mut random = || DateTimeSubSecondOffset::new(source.year(), source.month(),
+                                                         source.day(), source.hour(),
+                                                         source.minute(), source.second(),
+                                                         source.fractional_second(),
+                                                         source.offset()).unwrap();
+        let (a, b, c) = (random(), random(), random());
+
+        for &missing in [MissingFields::First, MissingFields::Last].iter() {
+            let range = KeyRange::for_range(Bound::Included(&a), Bound::Excluded(&b), missing);
+            let (s, e) = (start(&range), end(&range, RangeEnd::Exclusive));
+            let bytes = key(&c, missing);
+
+            let expected = key(&a, missing) <= bytes && bytes < key(&b, missing);
+            assert_eq!(expected, s <= bytes && bytes < e);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dt(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> DateTime {
+    DateTime::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second))
+        .unwrap()
+}
+
+fn pattern(year: Option<u16>, month: Option<u8>, day: Option<u8>, hour: Option<u8>,
+           minute: Option<u8>, second: Option<u8>) -> DateTime {
+    DateTime::new(year, month, day, hour, minute, second).unwrap()
+}
+
+fn expand(pattern: &DateTime, start: &DateTime, end: &DateTime) -> Vec<DateTime> {
+    pattern.recurrences(start, end).unwrap().collect()
+}
+
+#[test]
+fn every_day_of_february() {
+    let feb_9am = pattern(None, Some(2), None, Some(9), Some(0), Some(0));
+    let all = expand(&feb_9am, &dt(2016, 1, 1, 0, 0, 0), &dt(2018, 1, 1, 0, 0, 0));
+
+    assert_eq!(29 + 28, all.len());
+    assert_eq!(dt(2016, 2, 1, 9, 0, 0), all[0]);
+    assert_eq!(dt(2016, 2, 29, 9, 0, 0), all[28]);
+    assert_eq!(dt(2017, 2, 1, 9, 0, 0), all[29]);
+    assert_eq!(dt(2017, 2, 28, 9, 0, 0), all[56]);
+}
+
+#[test]
+fn in_order_and_matching() {
+    let p = pattern(None, None, Some(30), None, Some(15), Some(0));
+    let all = expand(&p, &dt(2016, 12, 1, 0, 0, 0), &dt(2017, 4, 1, 0, 0, 0));
+
+    // 24 a day on Dec 30, Jan 30, and Mar 30
+    assert_eq!(24 * 3, all.len());
+    for pair in all.windows(2) {
+        assert!(pair[1].duration_since(&pair[0]).unwrap() > SignedDuration::from_secs(0));
+    }
+    for value in all.iter() {
+        assert!(p.matches(value));
+    }
+}
+
+#[test]
+fn skips_nonexistent_days() {
+    let feb_29 = pattern(None, Some(2), Some(29), Some(12), Some(0), Some(0));
+    let years: Vec<u16> = expand(&feb_29, &dt(2010, 1, 1, 0, 0, 0), &dt(2021, 1, 1, 0, 0, 0))
+        .iter().map(|dt| dt.year().unwrap()).collect();
+    assert_eq!(vec![2012, 2016, 2020], years);
+
+    let feb_30 = pattern(None, Some(2), Some(30), None, None, None);
+    assert!(expand(&feb_30, &dt(2000, 1, 1, 0, 0, 0), &dt(2100, 1, 1, 0, 0, 0)).is_empty());
+}
+
+#[test]
+fn missing_second() {
+    let p = pattern(None, None, None, Some(9), Some(30), None);
+    let all = expand(&p, &dt(2017, 1, 15, 0, 0, 0), &dt(2017, 1, 16, 0, 0, 0));
+
+    assert_eq!(60, all.len());
+    assert_eq!(dt(2017, 1, 15, 9, 30, 0), all[0]);
+    assert_eq!(dt(2017, 1, 15, 9, 30, 59), all[59]);
+}
+
+#[test]
+fn range_bounds() {
+    let hourly = pattern(None, None, None, None, Some(0), Some(0));
+
+    // start is inclusive, end is exclusive
+    let all = expand(&hourly, &dt(2017, 1, 15, 9, 0, 0), &dt(2017, 1, 15, 12, 0, 0));
+    assert_eq!(vec![dt(2017, 1, 15, 9, 0, 0), dt(2017, 1, 15, 10, 0, 0), dt(2017, 1, 15, 11, 0, 0)],
+               all);
+
+    assert!(expand(&hourly, &dt(2017, 1, 15, 12, 0, 0), &dt(2017, 1, 15, 9, 0, 0)).is_empty());
+
+    let in_the_past = pattern(Some(2016), None, None, None, None, None);
+    assert!(expand(&in_the_past, &dt(2017, 1, 1, 0, 0, 0), &dt(2018, 1, 1, 0, 0, 0)).is_empty());
+}
+
+#[test]
+fn every_second_skips_leap_second() {
+    let any = pattern(None, None, None, None, None, None);
+    let all = expand(&any, &dt(2016, 12, 31, 23, 59, 58), &dt(2017, 1, 1, 0, 0, 1));
+
+    assert_eq!(vec![dt(2016, 12, 31, 23, 59, 58), dt(2016, 12, 31, 23, 59, 59),
+                    dt(2017, 1, 1, 0, 0, 0)],
+               all);
+
+    let leap = pattern(None, None, None, None, None, Some(60));
+    assert!(expand(&leap, &dt(2016, 12, 31, 0, 0, 0), &dt(2017, 1, 2, 0, 0, 0)).is_empty());
+
+    // starting on a leap second moves on to the next minute
+    let first = any.recurrences(&dt(2016, 12, 31, 23, 59, 60), &dt(2017, 1, 2, 0, 0, 0)).unwrap()
+        .next();
+    assert_eq!(Some(dt(2017, 1, 1, 0, 0, 0)), first);
+}
+
+#[test]
+fn lazy_over_wide_range() {
+    let any = pattern(None, None, None, None, None, None);
+    let mut all = any.recurrences(&dt(0, 1, 1, 0, 0, 0), &dt(4094, 12, 31, 23, 59, 59)).unwrap();
+
+    assert_eq!(Some(dt(0, 1, 1, 0, 0, 0)), all.next());
+    assert_eq!(Some(dt(0, 1, 1, 0, 0, 1)), all.next());
+
+    let last_year = pattern(Some(4094), Some(12), Some(31), Some(23), Some(59), None);
+    let all = expand(&last_year, &dt(0, 1, 1, 0, 0, 0), &dt(4094, 12, 31, 23, 59, 59));
+    assert_eq!(59, all.len());
+}
+
+#[test]
+fn incomplete_range() {
+    let any = pattern(None, None, None, None, None, None);
+    let incomplete = pattern(Some(2017), Some(1), Some(1), None, None, None);
+
+    assert_eq!(ConversionError::MissingField,
+               any.recurrences(&incomplete, &dt(2018, 1, 1, 0, 0, 0)).unwrap_err());
+    assert_eq!(ConversionError::MissingField,
+               any.recurrences(&dt(2017, 1, 1, 0, 0, 0), &incomplete).unwrap_err());
+}
@@ -0,0 +1,99 @@
+extern crate temporenc;
+
+use std::io::Cursor;
+use temporenc::*;
+
+fn all_fields() -> TemporalFields {
+    TemporalFields {
+        year: Some(1983),
+        month: Some(1),
+        day: Some(15),
+        hour: Some(18),
+        minute: Some(25),
+        second: Some(12),
+        frac_second: FractionalSecond::Microseconds(123456),
+        offset: OffsetValue::UtcOffset(60),
+    }
+}
+
+/// Keep only the fields that `T` can hold.
+fn supported_fields<T: Temporal>(fields: TemporalFields) -> TemporalFields {
+    let mut f = TemporalFields::default();
+
+    if T::has_date() {
+        f.year = fields.year;
+        f.month = fields.month;
+        f.day = fields.day;
+    }
+    if T::has_time() {
+        f.hour = fields.hour;
+        f.minute = fields.minute;
+        f.second = fields.second;
+    }
+    if T::has_sub_second() {
+        f.frac_second = fields.frac_second;
+    }
+    if T::has_offset() {
+        f.offset = fields.offset;
+    }
+
+    f
+}
+
+fn check_roundtrip<T: Temporal + PartialEq + std::fmt::Debug>(tag: TypeTag) {
+    assert_eq!(tag, T::type_tag());
+    assert!(T::min_serialized_size() <= T::max_serialized_size());
+
+    let fields = supported_fields::<T>(all_fields());
+    let t = T::from_fields(fields).unwrap();
+    assert_eq!(fields, t.fields());
+
+    let mut vec = Vec::new();
+    let written = t.serialize(&mut vec).unwrap();
+    assert!(written >= T::min_serialized_size());
+    assert!(written <= T::max_serialized_size());
+    assert_eq!(t, T::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+
+    // only the type with every component accepts every field
+    let accepts_all = T::has_date() && T::has_time() && T::has_sub_second() && T::has_offset();
+    assert_eq!(accepts_all, T::from_fields(all_fields()).is_ok());
+}
+
+#[test]
+fn temporal_roundtrip_fields_all_types() {
+    check_roundtrip::<DateOnly>(TypeTag::DateOnly);
+    check_roundtrip::<TimeOnly>(TypeTag::TimeOnly);
+    check_roundtrip::<DateTime>(TypeTag::DateTime);
+    check_roundtrip::<DateTimeOffset>(TypeTag::DateTimeOffset);
+    check_roundtrip::<DateTimeSubSecond>(TypeTag::DateTimeSubSecond);
+    check_roundtrip::<DateTimeSubSecondOffset>(TypeTag::DateTimeSubSecondOffset);
+}
+
+#[test]
+fn temporal_absent_components_are_missing() {
+    let t = TimeOnly::new(Some(18), Some(25), Some(12)).unwrap();
+    let fields = t.fields();
+
+    assert_eq!(None, fields.year);
+    assert_eq!(FractionalSecond::None, fields.frac_second);
+    assert_eq!(OffsetValue::None, fields.offset);
+}
+
+#[test]
+fn temporal_sizes() {
+    assert_eq!(3, DateOnly::min_serialized_size());
+    assert_eq!(3, TimeOnly::min_serialized_size());
+    assert_eq!(5, DateTime::min_serialized_size());
+    assert_eq!(6, DateTimeOffset::min_serialized_size());
+    assert_eq!(6, DateTimeSubSecond::min_serialized_size());
+    assert_eq!(7, DateTimeSubSecondOffset::min_serialized_size());
+}
+
+#[test]
+fn temporal_from_fields_rejects_unsupported_component() {
+    let fields = TemporalFields { year: Some(2017), offset: OffsetValue::SpecifiedElsewhere,
+        ..TemporalFields::default() };
+
+    assert_eq!(CreationError::InvalidFieldValue, DateTime::from_fields(fields).unwrap_err());
+    assert!(DateTimeOffset::from_fields(fields).is_ok());
+}
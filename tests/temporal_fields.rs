@@ -0,0 +1,100 @@
+extern crate temporenc;
+
+use std::io::Cursor;
+use temporenc::*;
+
+#[test]
+fn smallest_no_fields_is_date() {
+    let fields = TemporalFields::default();
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::DateOnly, DateOnly::max_serialized_size()),
+               fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(vec!(0x9F, 0xFF, 0xFF), vec);
+}
+
+#[test]
+fn smallest_date_only() {
+    let fields = TemporalFields { year: Some(1983), month: Some(1), day: Some(15),
+        ..TemporalFields::default() };
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::DateOnly, 3), fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(DateOnly::new(Some(1983), Some(1), Some(15)).unwrap(),
+               DateOnly::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+}
+
+#[test]
+fn smallest_time_only() {
+    let fields = TemporalFields { hour: Some(18), minute: Some(25), second: Some(12),
+        ..TemporalFields::default() };
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::TimeOnly, 3), fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(TimeOnly::new(Some(18), Some(25), Some(12)).unwrap(),
+               TimeOnly::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+}
+
+#[test]
+fn smallest_date_time() {
+    let fields = TemporalFields { year: Some(1983), hour: Some(18), ..TemporalFields::default() };
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::DateTime, 5), fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(DateTime::new(Some(1983), None, None, Some(18), None, None).unwrap(),
+               DateTime::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+}
+
+#[test]
+fn smallest_offset_without_frac_second_is_dto() {
+    let fields = TemporalFields { year: Some(1983), month: Some(1), day: Some(15),
+        offset: OffsetValue::UtcOffset(60), ..TemporalFields::default() };
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::DateTimeOffset, 6), fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(DateTimeOffset::new(Some(1983), Some(1), Some(15), None, None, None,
+                                   OffsetValue::UtcOffset(60)).unwrap(),
+               DateTimeOffset::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+}
+
+#[test]
+fn smallest_frac_second_without_offset_is_dts() {
+    let fields = TemporalFields { hour: Some(18), second: Some(12),
+        frac_second: FractionalSecond::Milliseconds(123), ..TemporalFields::default() };
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::DateTimeSubSecond, 7), fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(DateTimeSubSecond::new(None, None, None, Some(18), None, Some(12),
+                                      FractionalSecond::Milliseconds(123)).unwrap(),
+               DateTimeSubSecond::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+}
+
+#[test]
+fn smallest_all_fields_is_dtso() {
+    let fields = TemporalFields {
+        year: Some(1983),
+        month: Some(1),
+        day: Some(15),
+        hour: Some(18),
+        minute: Some(25),
+        second: Some(12),
+        frac_second: FractionalSecond::Nanoseconds(123456789),
+        offset: OffsetValue::SpecifiedElsewhere,
+    };
+    let mut vec = Vec::new();
+
+    assert_eq!((TypeTag::DateTimeSubSecondOffset, 10), fields.serialize_smallest(&mut vec).unwrap());
+    assert_eq!(DateTimeSubSecondOffset::new(Some(1983), Some(1), Some(15), Some(18), Some(25),
+                                            Some(12), FractionalSecond::Nanoseconds(123456789),
+                                            OffsetValue::SpecifiedElsewhere).unwrap(),
+               DateTimeSubSecondOffset::deserialize(&mut Cursor::new(vec.as_slice())).unwrap());
+}
+
+#[test]
+fn smallest_invalid_field() {
+    let fields = TemporalFields { month: Some(18), ..TemporalFields::default() };
+    let mut vec = Vec::new();
+
+    assert_eq!(EncodingError::InvalidFieldValue, fields.serialize_smallest(&mut vec).unwrap_err());
+    assert_eq!(0, vec.len());
+}
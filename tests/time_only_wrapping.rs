@@ -0,0 +1,85 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn time(hour: Option<u8>, minute: Option<u8>, second: Option<u8>) -> TimeOnly {
+    TimeOnly::new(hour, minute, second).unwrap()
+}
+
+fn hms(hour: u8, minute: u8, second: u8) -> TimeOnly {
+    time(Some(hour), Some(minute), Some(second))
+}
+
+#[test]
+fn wrapping_add_same_day() {
+    assert_eq!(Ok((hms(18, 0, 30), 0)),
+               hms(9, 0, 0).wrapping_add(SignedDuration::from_secs(32_430)));
+}
+
+#[test]
+fn wrapping_add_past_midnight() {
+    let closing = hms(22, 30, 0);
+
+    assert_eq!(Ok((hms(6, 30, 0), 1)), closing.wrapping_add(SignedDuration::from_hours(8)));
+    assert_eq!(Ok((hms(0, 0, 0), 1)), closing.wrapping_add(SignedDuration::from_minutes(90)));
+    assert_eq!(Ok((hms(22, 30, 0), 3)), closing.wrapping_add(SignedDuration::from_days(3)));
+    assert_eq!(Ok((hms(23, 30, 0), 2)), closing.wrapping_add(SignedDuration::from_hours(49)));
+}
+
+#[test]
+fn wrapping_sub_before_midnight() {
+    let opening = hms(1, 15, 0);
+
+    assert_eq!(Ok((hms(23, 15, 0), -1)), opening.wrapping_sub(SignedDuration::from_hours(2)));
+    assert_eq!(Ok((hms(1, 15, 0), -2)), opening.wrapping_sub(SignedDuration::from_days(2)));
+    assert_eq!(opening.wrapping_sub(SignedDuration::from_hours(2)),
+               opening.wrapping_add(SignedDuration::from_hours(-2)));
+}
+
+#[test]
+fn partial_times() {
+    let no_minute = time(Some(9), None, None);
+    let no_second = time(Some(23), Some(45), None);
+
+    assert_eq!(Ok((time(Some(10), None, None), 1)),
+               no_minute.wrapping_add(SignedDuration::from_hours(25)));
+    assert_eq!(Ok((time(Some(0), Some(15), None), 1)),
+               no_second.wrapping_add(SignedDuration::from_minutes(30)));
+    assert_eq!(Ok((time(None, None, None), -1)),
+               time(None, None, None).wrapping_sub(SignedDuration::from_days(1)));
+
+    assert_eq!(Err(ConversionError::MissingField),
+               no_minute.wrapping_add(SignedDuration::from_minutes(30)));
+    assert_eq!(Err(ConversionError::MissingField),
+               time(None, Some(30), None).wrapping_add(SignedDuration::from_minutes(30)));
+}
+
+#[test]
+fn sub_second_duration() {
+    assert_eq!(Err(ConversionError::PrecisionLoss),
+               hms(9, 0, 0).wrapping_add(SignedDuration::from_millis(1)));
+}
+
+#[test]
+fn seconds_since_midnight() {
+    assert_eq!(Some(0), hms(0, 0, 0).seconds_since_midnight());
+    assert_eq!(Some(67_530), hms(18, 45, 30).seconds_since_midnight());
+    assert_eq!(Some(86_399), hms(23, 59, 59).seconds_since_midnight());
+    assert_eq!(Some(86_400), hms(23, 59, 60).seconds_since_midnight());
+    assert_eq!(None, time(Some(18), Some(45), None).seconds_since_midnight());
+    assert_eq!(None, time(None, Some(45), Some(30)).seconds_since_midnight());
+}
+
+#[test]
+fn from_seconds_since_midnight() {
+    assert_eq!(Ok(hms(0, 0, 0)), TimeOnly::from_seconds_since_midnight(0));
+    assert_eq!(Ok(hms(18, 45, 30)), TimeOnly::from_seconds_since_midnight(67_530));
+    assert_eq!(Ok(hms(23, 59, 60)), TimeOnly::from_seconds_since_midnight(86_400));
+    assert_eq!(Err(CreationError::InvalidFieldValue),
+               TimeOnly::from_seconds_since_midnight(86_401));
+
+    for seconds in 0..86_401 {
+        let time = TimeOnly::from_seconds_since_midnight(seconds).unwrap();
+        assert_eq!(Some(seconds), time.seconds_since_midnight());
+    }
+}
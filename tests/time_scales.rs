@@ -0,0 +1,110 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn utc(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, frac: FractionalSecond)
+       -> DateTimeSubSecondOffset {
+    DateTimeSubSecondOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute),
+                                 Some(second), frac, OffsetValue::UtcOffset(0)).unwrap()
+}
+
+fn tai(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, frac: FractionalSecond)
+       -> DateTimeSubSecond {
+    DateTimeSubSecond::new(Some(year), Some(month), Some(day), Some(hour), Some(minute),
+                           Some(second), frac).unwrap()
+}
+
+fn gps(week: u32, seconds_of_week: u32, nanos: u32) -> GpsTime {
+    GpsTime { week, seconds_of_week, nanos }
+}
+
+#[test]
+fn tai_around_leap_second() {
+    let none = FractionalSecond::None;
+
+    assert_eq!(Ok(utc(2016, 12, 31, 23, 59, 59, none)),
+               DateTimeSubSecondOffset::from_tai(&tai(2017, 1, 1, 0, 0, 35, none)));
+    assert_eq!(Ok(utc(2016, 12, 31, 23, 59, 60, none)),
+               DateTimeSubSecondOffset::from_tai(&tai(2017, 1, 1, 0, 0, 36, none)));
+    assert_eq!(Ok(utc(2017, 1, 1, 0, 0, 0, none)),
+               DateTimeSubSecondOffset::from_tai(&tai(2017, 1, 1, 0, 0, 37, none)));
+
+    assert_eq!(Ok(tai(2017, 1, 1, 0, 0, 35, none)), utc(2016, 12, 31, 23, 59, 59, none).to_tai());
+    assert_eq!(Ok(tai(2017, 1, 1, 0, 0, 36, none)), utc(2016, 12, 31, 23, 59, 60, none).to_tai());
+    assert_eq!(Ok(tai(2017, 1, 1, 0, 0, 37, none)), utc(2017, 1, 1, 0, 0, 0, none).to_tai());
+}
+
+#[test]
+fn tai_keeps_frac_second() {
+    let frac = FractionalSecond::Microseconds(123_456);
+
+    assert_eq!(Ok(tai(1972, 1, 1, 0, 0, 10, frac)), utc(1972, 1, 1, 0, 0, 0, frac).to_tai());
+    assert_eq!(Ok(utc(1972, 1, 1, 0, 0, 0, frac)),
+               DateTimeSubSecondOffset::from_tai(&tai(1972, 1, 1, 0, 0, 10, frac)));
+}
+
+#[test]
+fn tai_from_other_offset() {
+    let local = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(1), Some(0), Some(59),
+                                             Some(60), FractionalSecond::Nanoseconds(5),
+                                             OffsetValue::UtcOffset(60)).unwrap();
+
+    assert_eq!(Ok(tai(2017, 1, 1, 0, 0, 36, FractionalSecond::Nanoseconds(5))), local.to_tai());
+}
+
+#[test]
+fn tai_errors() {
+    let none = FractionalSecond::None;
+    let no_second = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(1), Some(0), Some(0),
+                                                 None, none, OffsetValue::UtcOffset(0)).unwrap();
+
+    assert_eq!(Err(ConversionError::InvalidLeapSecond), utc(2017, 3, 14, 3, 17, 60, none).to_tai());
+    assert_eq!(Err(ConversionError::OutOfRange), utc(1971, 12, 31, 23, 59, 59, none).to_tai());
+    assert_eq!(Err(ConversionError::MissingField), no_second.to_tai());
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               DateTimeSubSecondOffset::from_tai(&tai(2016, 12, 31, 23, 59, 60, none)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               DateTimeSubSecondOffset::from_tai(&tai(1972, 1, 1, 0, 0, 9, none)));
+}
+
+#[test]
+fn gps_epoch() {
+    assert_eq!(Ok(utc(1980, 1, 6, 0, 0, 0, FractionalSecond::Nanoseconds(0))),
+               DateTimeSubSecondOffset::from_gps(&gps(0, 0, 0)));
+    assert_eq!(Ok(gps(0, 0, 0)), utc(1980, 1, 6, 0, 0, 0, FractionalSecond::None).to_gps());
+    assert_eq!(Err(ConversionError::OutOfRange),
+               utc(1980, 1, 5, 23, 59, 59, FractionalSecond::None).to_gps());
+}
+
+#[test]
+fn gps_around_leap_second() {
+    // week 1930 starts at 2017-01-01T00:00:00 GPS, when GPS was 18 seconds ahead of UTC
+    let nanos = FractionalSecond::Nanoseconds(123_456_789);
+
+    assert_eq!(Ok(utc(2016, 12, 31, 23, 59, 59, nanos)),
+               DateTimeSubSecondOffset::from_gps(&gps(1930, 16, 123_456_789)));
+    assert_eq!(Ok(utc(2016, 12, 31, 23, 59, 60, nanos)),
+               DateTimeSubSecondOffset::from_gps(&gps(1930, 17, 123_456_789)));
+    assert_eq!(Ok(utc(2017, 1, 1, 0, 0, 0, nanos)),
+               DateTimeSubSecondOffset::from_gps(&gps(1930, 18, 123_456_789)));
+
+    assert_eq!(Ok(gps(1930, 17, 123_456_789)), utc(2016, 12, 31, 23, 59, 60, nanos).to_gps());
+    assert_eq!(Ok(gps(1930, 18, 123_000_000)),
+               utc(2017, 1, 1, 0, 0, 0, FractionalSecond::Milliseconds(123)).to_gps());
+}
+
+#[test]
+fn gps_end_of_week() {
+    assert_eq!(Ok(gps(1929, 604_799, 0)),
+               utc(2016, 12, 31, 23, 59, 42, FractionalSecond::None).to_gps());
+}
+
+#[test]
+fn gps_invalid() {
+    assert_eq!(Err(ConversionError::OutOfRange),
+               DateTimeSubSecondOffset::from_gps(&gps(1930, 604_800, 0)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               DateTimeSubSecondOffset::from_gps(&gps(1930, 0, 1_000_000_000)));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               DateTimeSubSecondOffset::from_gps(&gps(u32::MAX, 0, 0)));
+}
@@ -0,0 +1,121 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+/// Central European Time in 2017: +01:00, or +02:00 between 2017-03-26T01:00Z and 2017-10-29T01:00Z
+struct Cet2017;
+
+impl TimeZoneRules for Cet2017 {
+    fn offset_at_unix_seconds(&self, unix_seconds: i64) -> i32 {
+        if (1_490_490_000..1_509_238_800).contains(&unix_seconds) {
+            7200
+        } else {
+            3600
+        }
+    }
+}
+
+/// A zone whose offset isn't a multiple of 15 minutes, like local mean time
+struct Lmt;
+
+impl TimeZoneRules for Lmt {
+    fn offset_at_unix_seconds(&self, _: i64) -> i32 {
+        19 * 60 + 32
+    }
+}
+
+fn dt(month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+    DateTime::new(Some(2017), Some(month), Some(day), Some(hour), Some(minute), Some(0)).unwrap()
+}
+
+fn resolve(local: &DateTime, disambiguation: Disambiguation)
+           -> Result<DateTimeOffset, ConversionError> {
+    Cet2017.resolve_date_time(local, disambiguation)
+}
+
+fn dto(month: u8, day: u8, hour: u8, minute: u8, offset: i16) -> DateTimeOffset {
+    DateTimeOffset::new(Some(2017), Some(month), Some(day), Some(hour), Some(minute), Some(0),
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+#[test]
+fn resolve_unambiguous() {
+    assert_eq!(dto(1, 15, 12, 0, 60), resolve(&dt(1, 15, 12, 0), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(7, 1, 12, 0, 120), resolve(&dt(7, 1, 12, 0), Disambiguation::Reject).unwrap());
+    // just before and at the transitions
+    assert_eq!(dto(3, 26, 1, 59, 60), resolve(&dt(3, 26, 1, 59), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(3, 26, 3, 0, 120), resolve(&dt(3, 26, 3, 0), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(10, 29, 1, 59, 120), resolve(&dt(10, 29, 1, 59), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(10, 29, 3, 0, 60), resolve(&dt(10, 29, 3, 0), Disambiguation::Reject).unwrap());
+}
+
+#[test]
+fn resolve_gap() {
+    let local = dt(3, 26, 2, 30);
+
+    assert_eq!(ConversionError::NonexistentLocalTime,
+               resolve(&local, Disambiguation::Reject).unwrap_err());
+    assert_eq!(dto(3, 26, 3, 30, 120), resolve(&local, Disambiguation::Compatible).unwrap());
+    assert_eq!(dto(3, 26, 3, 30, 120), resolve(&local, Disambiguation::Later).unwrap());
+    assert_eq!(dto(3, 26, 1, 30, 60), resolve(&local, Disambiguation::Earlier).unwrap());
+}
+
+#[test]
+fn resolve_fold() {
+    let local = dt(10, 29, 2, 30);
+
+    assert_eq!(ConversionError::AmbiguousLocalTime,
+               resolve(&local, Disambiguation::Reject).unwrap_err());
+    assert_eq!(dto(10, 29, 2, 30, 120), resolve(&local, Disambiguation::Compatible).unwrap());
+    assert_eq!(dto(10, 29, 2, 30, 120), resolve(&local, Disambiguation::Earlier).unwrap());
+    assert_eq!(dto(10, 29, 2, 30, 60), resolve(&local, Disambiguation::Later).unwrap());
+}
+
+#[test]
+fn resolve_subsecond_keeps_frac_second() {
+    let local = DateTimeSubSecond::new(Some(2017), Some(3), Some(26), Some(2), Some(15), Some(7),
+                                       FractionalSecond::Nanoseconds(123456789)).unwrap();
+
+    assert_eq!(DateTimeSubSecondOffset::new(Some(2017), Some(3), Some(26), Some(3), Some(15),
+                                            Some(7), FractionalSecond::Nanoseconds(123456789),
+                                            OffsetValue::UtcOffset(120)).unwrap(),
+               Cet2017.resolve_date_time_subsecond(&local, Disambiguation::Compatible).unwrap());
+}
+
+#[test]
+fn resolve_missing_field() {
+    let local = DateTime::new(Some(2017), Some(3), None, Some(2), Some(30), Some(0)).unwrap();
+
+    assert_eq!(ConversionError::MissingField,
+               resolve(&local, Disambiguation::Compatible).unwrap_err());
+}
+
+#[test]
+fn resolve_unrepresentable_offset() {
+    assert_eq!(ConversionError::InvalidOffset,
+               Lmt.resolve_date_time(&dt(1, 15, 12, 0), Disambiguation::Compatible).unwrap_err());
+}
+
+#[test]
+fn resolve_rounding_unrepresentable_offset() {
+    let (value, adjustment) = Lmt.resolve_date_time_rounding(&dt(1, 15, 12, 0),
+                                                             Disambiguation::Compatible,
+                                                             OffsetRounding::NearestQuarterHour)
+        .unwrap();
+
+    assert_eq!(DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(11), Some(55), Some(28),
+                                   OffsetValue::UtcOffset(15)).unwrap(),
+               value);
+    assert_eq!(OffsetAdjustment { offset_seconds: 272, instant_seconds: 0 }, adjustment);
+}
+
+#[test]
+fn resolve_rounding_gap() {
+    let (value, adjustment) = Cet2017.resolve_date_time_rounding(&dt(3, 26, 2, 30),
+                                                                 Disambiguation::Compatible,
+                                                                 OffsetRounding::Utc).unwrap();
+
+    // representable offsets are unaffected by the rounding
+    assert_eq!(dto(3, 26, 3, 30, 120), value);
+    assert!(adjustment.is_exact());
+}
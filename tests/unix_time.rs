@@ -0,0 +1,109 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dto(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, offset: i16)
+       -> DateTimeOffset {
+    DateTimeOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(second),
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+fn dtso(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, nanos: u32)
+        -> DateTimeSubSecondOffset {
+    DateTimeSubSecondOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute),
+                                 Some(second), FractionalSecond::Nanoseconds(nanos),
+                                 OffsetValue::UtcOffset(0)).unwrap()
+}
+
+#[test]
+fn to_unix_seconds() {
+    assert_eq!(Ok(0), dto(1970, 1, 1, 0, 0, 0, 0).to_unix_seconds(LeapSecondMode::Strict));
+    assert_eq!(Ok(1_484_505_930),
+               dto(2017, 1, 15, 18, 45, 30, 0).to_unix_seconds(LeapSecondMode::Strict));
+    assert_eq!(Ok(1_484_505_930),
+               dto(2017, 1, 15, 20, 0, 30, 75).to_unix_seconds(LeapSecondMode::Strict));
+    assert_eq!(Ok(-62_167_219_200),
+               dto(0, 1, 1, 0, 0, 0, 0).to_unix_seconds(LeapSecondMode::Strict));
+}
+
+#[test]
+fn to_unix_seconds_missing_field() {
+    let no_second = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), None,
+                                        OffsetValue::UtcOffset(0)).unwrap();
+    let no_offset = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                        Some(0), OffsetValue::None).unwrap();
+
+    assert_eq!(Err(ConversionError::MissingField),
+               no_second.to_unix_seconds(LeapSecondMode::Clamp));
+    assert_eq!(Err(ConversionError::UnknownOffset),
+               no_offset.to_unix_seconds(LeapSecondMode::Clamp));
+}
+
+#[test]
+fn leap_second_clamped() {
+    let before = dto(2016, 12, 31, 23, 59, 59, 0);
+    let leap = dto(2016, 12, 31, 23, 59, 60, 0);
+
+    assert_eq!(before.to_unix_seconds(LeapSecondMode::Strict),
+               leap.to_unix_seconds(LeapSecondMode::Strict));
+    assert_eq!(Ok(1_483_228_800),
+               dto(2017, 1, 1, 0, 0, 0, 0).to_unix_seconds(LeapSecondMode::Strict));
+
+    // all of the leap second maps to the last nanosecond of the second before
+    assert_eq!(Ok(1_483_228_799_999_999_999),
+               dtso(2016, 12, 31, 23, 59, 60, 0).to_unix_nanos(LeapSecondMode::Strict));
+    assert_eq!(Ok(1_483_228_799_999_999_999),
+               dtso(2016, 12, 31, 23, 59, 60, 900_000_000).to_unix_nanos(LeapSecondMode::Strict));
+}
+
+#[test]
+fn bogus_leap_second() {
+    let bogus = dto(2017, 3, 14, 3, 17, 60, 0);
+
+    assert_eq!(Err(ConversionError::InvalidLeapSecond),
+               bogus.to_unix_seconds(LeapSecondMode::Strict));
+    assert_eq!(dto(2017, 3, 14, 3, 17, 59, 0).to_unix_seconds(LeapSecondMode::Strict),
+               bogus.to_unix_seconds(LeapSecondMode::Clamp));
+}
+
+#[test]
+fn to_unix_nanos_precisions() {
+    let with_frac = |frac| {
+        DateTimeSubSecondOffset::new(Some(1969), Some(12), Some(31), Some(23), Some(59), Some(59),
+                                     frac, OffsetValue::UtcOffset(0)).unwrap()
+            .to_unix_nanos(LeapSecondMode::Strict).unwrap()
+    };
+
+    assert_eq!(-1_000_000_000, with_frac(FractionalSecond::None));
+    assert_eq!(-999_000_000, with_frac(FractionalSecond::Milliseconds(1)));
+    assert_eq!(-999_999_000, with_frac(FractionalSecond::Microseconds(1)));
+    assert_eq!(-1, with_frac(FractionalSecond::Nanoseconds(999_999_999)));
+}
+
+#[test]
+fn from_unix() {
+    assert_eq!(Ok(dto(2017, 1, 15, 18, 45, 30, 0)),
+               DateTimeOffset::from_unix_seconds(1_484_505_930));
+    assert_eq!(Ok(dto(1969, 12, 31, 23, 59, 59, 0)), DateTimeOffset::from_unix_seconds(-1));
+    assert_eq!(Ok(dtso(1969, 12, 31, 23, 59, 59, 999_999_999)),
+               DateTimeSubSecondOffset::from_unix_nanos(-1));
+    assert_eq!(Ok(dtso(4094, 12, 31, 23, 59, 59, 999_999_999)),
+               DateTimeSubSecondOffset::from_unix_nanos(67_058_582_400_000_000_000 - 1));
+}
+
+#[test]
+fn from_unix_out_of_range() {
+    assert_eq!(Err(ConversionError::OutOfRange),
+               DateTimeOffset::from_unix_seconds(-62_167_219_201));
+    assert_eq!(Err(ConversionError::OutOfRange),
+               DateTimeSubSecondOffset::from_unix_nanos(67_058_582_400_000_000_000));
+    assert_eq!(Err(ConversionError::OutOfRange), DateTimeOffset::from_unix_seconds(i64::MAX));
+}
+
+#[test]
+fn roundtrip() {
+    for &seconds in [-62_167_219_200, -1, 0, 951_782_400, 1_483_228_800, 4_102_444_799].iter() {
+        let value = DateTimeOffset::from_unix_seconds(seconds).unwrap();
+        assert_eq!(Ok(seconds), value.to_unix_seconds(LeapSecondMode::Strict));
+    }
+}
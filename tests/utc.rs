@@ -0,0 +1,106 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn dto(year: u16, month: u8, day: u8, hour: u8, minute: u8, offset: i16) -> DateTimeOffset {
+    DateTimeOffset::new(Some(year), Some(month), Some(day), Some(hour), Some(minute), Some(30),
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+#[test]
+fn to_utc_same_day() {
+    assert_eq!(dto(2017, 1, 15, 16, 30, 0), dto(2017, 1, 15, 18, 45, 135).to_utc().unwrap());
+}
+
+#[test]
+fn to_utc_carries_into_previous_year() {
+    assert_eq!(dto(2016, 12, 31, 23, 15, 0), dto(2017, 1, 1, 1, 15, 120).to_utc().unwrap());
+}
+
+#[test]
+fn to_utc_carries_into_next_year() {
+    assert_eq!(dto(2017, 1, 1, 2, 0, 0), dto(2016, 12, 31, 20, 0, -360).to_utc().unwrap());
+}
+
+#[test]
+fn to_utc_leap_day() {
+    assert_eq!(dto(2016, 2, 29, 22, 0, 0), dto(2016, 3, 1, 1, 0, 180).to_utc().unwrap());
+    assert_eq!(dto(2017, 3, 1, 1, 0, 0), dto(2017, 2, 28, 23, 0, -120).to_utc().unwrap());
+    assert_eq!(dto(2000, 2, 29, 22, 0, 0), dto(2000, 3, 1, 1, 0, 180).to_utc().unwrap());
+    assert_eq!(dto(1900, 2, 28, 22, 0, 0), dto(1900, 3, 1, 1, 0, 180).to_utc().unwrap());
+}
+
+#[test]
+fn to_utc_keeps_second_and_frac_second() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2016), Some(12), Some(31), Some(23), Some(59),
+                                            Some(60), FractionalSecond::Nanoseconds(123456789),
+                                            OffsetValue::UtcOffset(-60)).unwrap();
+
+    assert_eq!(DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(1), Some(0), Some(59),
+                                            Some(60), FractionalSecond::Nanoseconds(123456789),
+                                            OffsetValue::UtcOffset(0)).unwrap(),
+               dtso.to_utc().unwrap());
+    assert_eq!(DateTimeSubSecond::new(Some(2017), Some(1), Some(1), Some(0), Some(59), Some(60),
+                                      FractionalSecond::Nanoseconds(123456789)).unwrap(),
+               dtso.to_naive_utc().unwrap());
+}
+
+#[test]
+fn to_naive_utc_drops_offset() {
+    assert_eq!(DateTime::new(Some(2017), Some(1), Some(15), Some(16), Some(30), Some(30)).unwrap(),
+               dto(2017, 1, 15, 18, 45, 135).to_naive_utc().unwrap());
+}
+
+#[test]
+fn to_offset_roundtrip() {
+    let original = dto(2017, 1, 15, 18, 45, 135);
+    let other = original.to_offset(-600).unwrap();
+
+    assert_eq!(dto(2017, 1, 15, 6, 30, -600), other);
+    assert_eq!(original, other.to_offset(135).unwrap());
+}
+
+#[test]
+fn to_offset_invalid_offset() {
+    assert_eq!(ConversionError::InvalidOffset, dto(2017, 1, 15, 18, 45, 0).to_offset(10).unwrap_err());
+    assert_eq!(ConversionError::InvalidOffset,
+               dto(2017, 1, 15, 18, 45, 0).to_offset(OFFSET_MAX + 15).unwrap_err());
+}
+
+#[test]
+fn to_utc_missing_field() {
+    let dto = DateTimeOffset::new(Some(2017), Some(1), Some(15), None, Some(45), Some(30),
+                                  OffsetValue::UtcOffset(60)).unwrap();
+
+    assert_eq!(ConversionError::MissingField, dto.to_utc().unwrap_err());
+}
+
+#[test]
+fn to_utc_missing_second_is_fine() {
+    let dto = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), None,
+                                  OffsetValue::UtcOffset(60)).unwrap();
+
+    assert_eq!(None, dto.to_utc().unwrap().second());
+}
+
+#[test]
+fn to_utc_unknown_offset() {
+    let elsewhere = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), None,
+                                        OffsetValue::SpecifiedElsewhere).unwrap();
+    let none = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), None,
+                                   OffsetValue::None).unwrap();
+
+    assert_eq!(ConversionError::UnknownOffset, elsewhere.to_utc().unwrap_err());
+    assert_eq!(ConversionError::UnknownOffset, none.to_utc().unwrap_err());
+}
+
+#[test]
+fn to_utc_invalid_date() {
+    assert_eq!(ConversionError::InvalidDate, dto(2017, 2, 30, 1, 0, 60).to_utc().unwrap_err());
+}
+
+#[test]
+fn to_utc_out_of_range() {
+    assert_eq!(ConversionError::OutOfRange, dto(YEAR_MIN, 1, 1, 0, 0, 60).to_utc().unwrap_err());
+    assert_eq!(ConversionError::OutOfRange, dto(YEAR_MAX, 12, 31, 23, 0, -60).to_utc().unwrap_err());
+}
@@ -0,0 +1,224 @@
+extern crate temporenc;
+
+use temporenc::*;
+
+fn date(year: u16, month: u8, day: u8) -> DateOnly {
+    DateOnly::new(Some(year), Some(month), Some(day)).unwrap()
+}
+
+fn week_date(year: u16, week: u8, weekday: Weekday) -> IsoWeekDate {
+    IsoWeekDate { year, week, weekday }
+}
+
+#[test]
+fn to_week_date() {
+    assert_eq!(week_date(2017, 2, Weekday::Sunday), date(2017, 1, 15).to_iso_week_date().unwrap());
+    // the week-numbering year differs from the calendar year
+    assert_eq!(week_date(2009, 1, Weekday::Monday), date(2008, 12, 29).to_iso_week_date().unwrap());
+    assert_eq!(week_date(2009, 53, Weekday::Sunday), date(2010, 1, 3).to_iso_week_date().unwrap());
+    assert_eq!(week_date(2004, 53, Weekday::Saturday),
+               date(2005, 1, 1).to_iso_week_date().unwrap());
+    assert_eq!(week_date(0, 1, Weekday::Monday), date(0, 1, 3).to_iso_week_date().unwrap());
+}
+
+#[test]
+fn from_week_date() {
+    assert_eq!(date(2017, 1, 15),
+               DateOnly::from_iso_week_date(&week_date(2017, 2, Weekday::Sunday)).unwrap());
+    assert_eq!(date(2010, 1, 3),
+               DateOnly::from_iso_week_date(&week_date(2009, 53, Weekday::Sunday)).unwrap());
+    assert_eq!(date(2008, 12, 29),
+               DateOnly::from_iso_week_date(&week_date(2009, 1, Weekday::Monday)).unwrap());
+}
+
+#[test]
+fn weeks_in_year() {
+    assert_eq!(53, IsoWeekDate::weeks_in_year(2004));
+    assert_eq!(53, IsoWeekDate::weeks_in_year(2009));
+    assert_eq!(53, IsoWeekDate::weeks_in_year(2015));
+    assert_eq!(52, IsoWeekDate::weeks_in_year(2016));
+    assert_eq!(52, IsoWeekDate::weeks_in_year(2017));
+    assert_eq!(53, IsoWeekDate::weeks_in_year(2020));
+}
+
+#[test]
+fn nonexistent_week() {
+    assert_eq!(ConversionError::InvalidDate,
+               DateOnly::from_iso_week_date(&week_date(2017, 53, Weekday::Monday)).unwrap_err());
+    assert_eq!(ConversionError::InvalidDate,
+               DateOnly::from_iso_week_date(&week_date(2017, 0, Weekday::Monday)).unwrap_err());
+}
+
+#[test]
+fn week_date_out_of_range() {
+    // 0000-01-01 is in the last week of year -1
+    assert_eq!(ConversionError::OutOfRange, date(0, 1, 1).to_iso_week_date().unwrap_err());
+    // 4094-W52 ends in 4095
+    let last = IsoWeekDate::weeks_in_year(4094);
+    assert_eq!(ConversionError::OutOfRange,
+               DateOnly::from_iso_week_date(&week_date(4094, last, Weekday::Sunday)).unwrap_err());
+}
+
+#[test]
+fn ordinal_date() {
+    assert_eq!(OrdinalDate { year: 2017, day: 15 }, date(2017, 1, 15).to_ordinal_date().unwrap());
+    assert_eq!(OrdinalDate { year: 2016, day: 366 }, date(2016, 12, 31).to_ordinal_date().unwrap());
+    assert_eq!(date(2016, 12, 31),
+               DateOnly::from_ordinal_date(&OrdinalDate { year: 2016, day: 366 }).unwrap());
+    assert_eq!(date(2017, 3, 1),
+               DateOnly::from_ordinal_date(&OrdinalDate { year: 2017, day: 60 }).unwrap());
+    assert_eq!(ConversionError::InvalidDate,
+               DateOnly::from_ordinal_date(&OrdinalDate { year: 2017, day: 366 }).unwrap_err());
+    assert_eq!(ConversionError::InvalidDate,
+               DateOnly::from_ordinal_date(&OrdinalDate { year: 2017, day: 0 }).unwrap_err());
+}
+
+#[test]
+fn roundtrip_every_day() {
+    for year in 1990..2031 {
+        for month in 1..13 {
+            for day in 1..(date(year, month, 1).days_in_month().unwrap() + 1) {
+                let d = date(year, month, day);
+                let week_date = d.to_iso_week_date().unwrap();
+                let ordinal_date = d.to_ordinal_date().unwrap();
+
+                assert_eq!(d.weekday().unwrap(), week_date.weekday);
+                assert_eq!(d, DateOnly::from_iso_week_date(&week_date).unwrap());
+                assert_eq!(d, DateOnly::from_ordinal_date(&ordinal_date).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn missing_or_invalid_fields() {
+    let no_day = DateOnly::new(Some(2017), Some(1), None).unwrap();
+    let no_year = DateOnly::new(None, Some(1), Some(15)).unwrap();
+
+    assert_eq!(ConversionError::MissingField, no_day.to_iso_week_date().unwrap_err());
+    assert_eq!(ConversionError::MissingField, no_year.to_ordinal_date().unwrap_err());
+    assert_eq!(ConversionError::InvalidDate, date(2017, 2, 30).to_iso_week_date().unwrap_err());
+    assert_eq!(ConversionError::InvalidDate, date(2017, 2, 30).to_ordinal_date().unwrap_err());
+}
+
+#[test]
+fn date_text() {
+    assert_eq!("2017-W02-7", week_date(2017, 2, Weekday::Sunday).to_string());
+    assert_eq!("0001-001", OrdinalDate { year: 1, day: 1 }.to_string());
+    assert_eq!(week_date(2009, 53, Weekday::Sunday), "2009-W53-7".parse().unwrap());
+    assert_eq!(OrdinalDate { year: 2016, day: 366 }, "2016-366".parse().unwrap());
+
+    assert_eq!(ParseError::InvalidSyntax, "2017-W2-7".parse::<IsoWeekDate>().unwrap_err());
+    assert_eq!(ParseError::InvalidSyntax, "2017-015 ".parse::<OrdinalDate>().unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue, "2017-W53-1".parse::<IsoWeekDate>().unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue, "2017-W02-8".parse::<IsoWeekDate>().unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue, "2017-366".parse::<OrdinalDate>().unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue, "4095-001".parse::<OrdinalDate>().unwrap_err());
+}
+
+#[test]
+fn parse_date_time_subsecond_offset() {
+    let dtso = DateTimeSubSecondOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45),
+                                            Some(30), FractionalSecond::Microseconds(123456),
+                                            OffsetValue::UtcOffset(135)).unwrap();
+
+    assert_eq!(dtso,
+               DateTimeSubSecondOffset::parse_iso_week_date("2017-W02-7T18:45:30.123456+02:15")
+                   .unwrap());
+    assert_eq!(dtso,
+               DateTimeSubSecondOffset::parse_ordinal_date("2017-015T18:45:30.123456+02:15")
+                   .unwrap());
+    assert_eq!("2017-W02-7T18:45:30.123456+02:15",
+               dtso.format_iso_week_date().unwrap().to_string());
+    assert_eq!("2017-015T18:45:30.123456+02:15", dtso.format_ordinal_date().unwrap().to_string());
+}
+
+#[test]
+fn parse_reduced_precision() {
+    let dt = DateTime::parse_ordinal_date("2010-003T12").unwrap();
+    assert_eq!(DateTime::new(Some(2010), Some(1), Some(3), Some(12), None, None).unwrap(), dt);
+    assert_eq!("2009-W53-7T12", dt.format_iso_week_date().unwrap().to_string());
+
+    let date_only = DateTimeSubSecond::parse_iso_week_date("2017-W02-7").unwrap();
+    assert_eq!(DateTimeSubSecond::new(Some(2017), Some(1), Some(15), None, None, None,
+                                      FractionalSecond::None).unwrap(),
+               date_only);
+    assert_eq!("2017-W02-7", date_only.format_iso_week_date().unwrap().to_string());
+}
+
+#[test]
+fn parse_offsets() {
+    let utc = DateTimeOffset::parse_ordinal_date("2017-015T18:45:30Z").unwrap();
+    assert_eq!(OffsetValue::UtcOffset(0), utc.offset());
+    assert_eq!("2017-015T18:45:30Z", utc.format_ordinal_date().unwrap().to_string());
+
+    let west = DateTimeOffset::parse_ordinal_date("2017-015T18:45-05:30").unwrap();
+    assert_eq!(OffsetValue::UtcOffset(-330), west.offset());
+    assert_eq!("2017-015T18:45-05:30", west.format_ordinal_date().unwrap().to_string());
+
+    let no_offset = DateTimeOffset::parse_ordinal_date("2017-015T18:45").unwrap();
+    assert_eq!(OffsetValue::None, no_offset.offset());
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(ParseError::InvalidSyntax, DateTime::parse_ordinal_date("2017-015T").unwrap_err());
+    assert_eq!(ParseError::InvalidSyntax, DateTime::parse_ordinal_date("2017-01-15").unwrap_err());
+    assert_eq!(ParseError::InvalidSyntax,
+               DateTime::parse_ordinal_date("2017-015T18:45:30:00").unwrap_err());
+    assert_eq!(ParseError::InvalidSyntax,
+               DateTimeSubSecond::parse_ordinal_date("2017-015T18:45:30.1234").unwrap_err());
+    assert_eq!(ParseError::InvalidSyntax,
+               DateTimeOffset::parse_ordinal_date("2017-015T18:45+0200").unwrap_err());
+    // Z can't follow a sign
+    assert_eq!(ParseError::InvalidSyntax,
+               DateTimeOffset::parse_ordinal_date("2017-015T18:00:00-Z").unwrap_err());
+    assert_eq!(ParseError::InvalidSyntax,
+               DateTimeOffset::parse_ordinal_date("2017-015T18:00:00+Z").unwrap_err());
+
+    assert_eq!(ParseError::InvalidFieldValue,
+               DateTime::parse_ordinal_date("2017-015T24:00").unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue,
+               DateTime::parse_iso_week_date("2017-W53-1T12").unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue,
+               DateTimeOffset::parse_ordinal_date("2017-015T18:45+02:10").unwrap_err());
+    // offset minutes past 59, which would otherwise carry into the hours
+    assert_eq!(ParseError::InvalidFieldValue,
+               DateTimeOffset::parse_ordinal_date("2017-015T18:00+01:75").unwrap_err());
+    // components the type doesn't have
+    assert_eq!(ParseError::InvalidFieldValue,
+               DateTime::parse_ordinal_date("2017-015T18:45Z").unwrap_err());
+    assert_eq!(ParseError::InvalidFieldValue,
+               DateTime::parse_ordinal_date("2017-015T18:45:30.123").unwrap_err());
+}
+
+#[test]
+fn format_nanoseconds() {
+    let dts = DateTimeSubSecond::new(Some(2017), Some(1), Some(15), Some(0), Some(0), Some(60),
+                                     FractionalSecond::Nanoseconds(123)).unwrap();
+
+    assert_eq!("2017-015T00:00:60.000000123", dts.format_ordinal_date().unwrap().to_string());
+    assert_eq!(dts, DateTimeSubSecond::parse_ordinal_date("2017-015T00:00:60.000000123").unwrap());
+}
+
+#[test]
+fn format_errors() {
+    let no_day = DateTime::new(Some(2017), Some(1), None, Some(12), Some(0), Some(0)).unwrap();
+    let no_hour = DateTime::new(Some(2017), Some(1), Some(15), None, Some(30), None).unwrap();
+    let offset_no_time = DateTimeOffset::new(Some(2017), Some(1), Some(15), None, None, None,
+                                             OffsetValue::UtcOffset(60)).unwrap();
+    let feb_30 = DateTime::new(Some(2017), Some(2), Some(30), None, None, None).unwrap();
+
+    assert_eq!(ConversionError::MissingField, no_day.format_iso_week_date().unwrap_err());
+    assert_eq!(ConversionError::MissingField, no_hour.format_ordinal_date().unwrap_err());
+    assert_eq!(ConversionError::MissingField, offset_no_time.format_ordinal_date().unwrap_err());
+    assert_eq!(ConversionError::InvalidDate, feb_30.format_iso_week_date().unwrap_err());
+}
+
+#[test]
+fn format_specified_elsewhere() {
+    let dto = DateTimeOffset::new(Some(2017), Some(1), Some(15), Some(18), Some(45), Some(30),
+                                  OffsetValue::SpecifiedElsewhere).unwrap();
+
+    assert_eq!("2017-W02-7T18:45:30", dto.format_iso_week_date().unwrap().to_string());
+}
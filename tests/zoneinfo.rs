@@ -0,0 +1,166 @@
+#![cfg(feature = "zoneinfo")]
+
+extern crate temporenc;
+
+use std::path::Path;
+use temporenc::*;
+use temporenc::zoneinfo::{TimeZone, ZoneInfoError};
+
+/// Build a version 2 TZif file with the same data in the v1 and v2 blocks.
+fn tzif(transitions: &[(i64, u8)], offsets: &[i32], footer: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for &time_size in [4, 8].iter() {
+        bytes.extend_from_slice(b"TZif2");
+        bytes.extend_from_slice(&[0; 15]);
+        // ut and std indicators, leap seconds, transitions, types, designation chars
+        for &count in [0, 0, 0, transitions.len(), offsets.len(), 4].iter() {
+            bytes.extend_from_slice(&(count as u32).to_be_bytes());
+        }
+
+        for &(t, _) in transitions.iter() {
+            if time_size == 4 {
+                bytes.extend_from_slice(&(t as i32).to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&t.to_be_bytes());
+            }
+        }
+        for &(_, type_index) in transitions.iter() {
+            bytes.push(type_index);
+        }
+        for &offset in offsets.iter() {
+            bytes.extend_from_slice(&offset.to_be_bytes());
+            // is_dst, designation index
+            bytes.extend_from_slice(&[0, 0]);
+        }
+        bytes.extend_from_slice(b"XYZ\0");
+    }
+
+    bytes.push(b'\n');
+    bytes.extend_from_slice(footer.as_bytes());
+    bytes.push(b'\n');
+    bytes
+}
+
+fn cet_2017() -> TimeZone {
+    TimeZone::from_tzif(&tzif(&[(1_490_490_000, 1), (1_509_238_800, 0)], &[3600, 7200], ""))
+        .unwrap()
+}
+
+fn dt(month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+    DateTime::new(Some(2017), Some(month), Some(day), Some(hour), Some(minute), Some(0)).unwrap()
+}
+
+fn dto(month: u8, day: u8, hour: u8, minute: u8, offset: i16) -> DateTimeOffset {
+    DateTimeOffset::new(Some(2017), Some(month), Some(day), Some(hour), Some(minute), Some(0),
+                        OffsetValue::UtcOffset(offset)).unwrap()
+}
+
+#[test]
+fn tzif_offsets() {
+    let zone = cet_2017();
+
+    assert_eq!(3600, zone.offset_at_unix_seconds(0));
+    assert_eq!(3600, zone.offset_at_unix_seconds(1_490_489_999));
+    assert_eq!(7200, zone.offset_at_unix_seconds(1_490_490_000));
+    assert_eq!(7200, zone.offset_at_unix_seconds(1_509_238_799));
+    assert_eq!(3600, zone.offset_at_unix_seconds(1_509_238_800));
+}
+
+#[test]
+fn tzif_resolve() {
+    let zone = cet_2017();
+
+    assert_eq!(dto(7, 1, 12, 0, 120),
+               zone.resolve_date_time(&dt(7, 1, 12, 0), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(3, 26, 3, 30, 120),
+               zone.resolve_date_time(&dt(3, 26, 2, 30), Disambiguation::Compatible).unwrap());
+    assert_eq!(ConversionError::AmbiguousLocalTime,
+               zone.resolve_date_time(&dt(10, 29, 2, 30), Disambiguation::Reject).unwrap_err());
+}
+
+#[test]
+fn tzif_no_transitions() {
+    let zone = TimeZone::from_tzif(&tzif(&[], &[-5 * 3600], "")).unwrap();
+
+    assert_eq!(dto(7, 1, 12, 0, -300),
+               zone.resolve_date_time(&dt(7, 1, 12, 0), Disambiguation::Reject).unwrap());
+}
+
+#[test]
+fn tzif_footer_after_last_transition() {
+    let zone = TimeZone::from_tzif(&tzif(&[(1_490_490_000, 1), (1_509_238_800, 0)],
+                                         &[3600, 7200], "CET-1CEST,M3.5.0,M10.5.0/3")).unwrap();
+    let summer_2030 = DateTime::new(Some(2030), Some(7), Some(1), Some(12), Some(0), Some(0))
+        .unwrap();
+
+    // within the transitions, same as without a footer
+    assert_eq!(3600, zone.offset_at_unix_seconds(0));
+    assert_eq!(dto(7, 1, 12, 0, 120),
+               zone.resolve_date_time(&dt(7, 1, 12, 0), Disambiguation::Reject).unwrap());
+    // after them, the footer's rules apply
+    assert_eq!(OffsetValue::UtcOffset(120),
+               zone.resolve_date_time(&summer_2030, Disambiguation::Reject).unwrap().offset());
+
+    let without_footer = cet_2017();
+    assert_eq!(OffsetValue::UtcOffset(60),
+               without_footer.resolve_date_time(&summer_2030, Disambiguation::Reject).unwrap()
+                   .offset());
+}
+
+#[test]
+fn tzif_invalid_footer() {
+    let bytes = tzif(&[(1_490_490_000, 1)], &[3600, 7200], "CET-1CEST,M3.5.0");
+
+    assert_eq!(ZoneInfoError::InvalidData, TimeZone::from_tzif(&bytes).unwrap_err());
+}
+
+#[test]
+fn tzif_invalid() {
+    let valid = tzif(&[(1_490_490_000, 1), (1_509_238_800, 0)], &[3600, 7200], "");
+
+    assert_eq!(ZoneInfoError::InvalidData, TimeZone::from_tzif(b"TZif").unwrap_err());
+    assert_eq!(ZoneInfoError::InvalidData, TimeZone::from_tzif(&valid[0..60]).unwrap_err());
+
+    let mut bad_magic = valid.clone();
+    bad_magic[0] = b'X';
+    assert_eq!(ZoneInfoError::InvalidData, TimeZone::from_tzif(&bad_magic).unwrap_err());
+
+    // transitions out of order
+    let unordered = tzif(&[(1_509_238_800, 1), (1_490_490_000, 0)], &[3600, 7200], "");
+    assert_eq!(ZoneInfoError::InvalidData, TimeZone::from_tzif(&unordered).unwrap_err());
+
+    // transition to a type that doesn't exist
+    let bad_type = tzif(&[(1_490_490_000, 2)], &[3600, 7200], "");
+    assert_eq!(ZoneInfoError::InvalidData, TimeZone::from_tzif(&bad_type).unwrap_err());
+}
+
+#[test]
+fn load_rejects_names_outside_dir() {
+    assert_eq!(ZoneInfoError::InvalidName, TimeZone::load("../etc/passwd").unwrap_err());
+    assert_eq!(ZoneInfoError::InvalidName, TimeZone::load("/etc/passwd").unwrap_err());
+    assert_eq!(ZoneInfoError::InvalidName, TimeZone::load("").unwrap_err());
+}
+
+#[test]
+fn load_system_zone() {
+    // not every system has a zoneinfo database
+    if !Path::new("/usr/share/zoneinfo/America/New_York").exists() {
+        return;
+    }
+
+    let zone = TimeZone::load_from_dir("/usr/share/zoneinfo", "America/New_York").unwrap();
+
+    assert_eq!(dto(1, 15, 12, 0, -300),
+               zone.resolve_date_time(&dt(1, 15, 12, 0), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(7, 1, 12, 0, -240),
+               zone.resolve_date_time(&dt(7, 1, 12, 0), Disambiguation::Reject).unwrap());
+    assert_eq!(dto(3, 12, 3, 30, -240),
+               zone.resolve_date_time(&dt(3, 12, 2, 30), Disambiguation::Compatible).unwrap());
+
+    // past the transitions in the file, so the footer applies
+    let summer_2100 = DateTime::new(Some(2100), Some(7), Some(1), Some(12), Some(0), Some(0))
+        .unwrap();
+    assert_eq!(OffsetValue::UtcOffset(-240),
+               zone.resolve_date_time(&summer_2100, Disambiguation::Reject).unwrap().offset());
+}